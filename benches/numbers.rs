@@ -0,0 +1,52 @@
+use bincode::config::{self, Config};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::num::Wrapping;
+
+/// `Wrapping<u32>` has the same encoding as `u32`, but is encoded one element at a time.
+fn encode_decode_u32<C: Config>(c: &mut Criterion, name: &str, config: C) {
+    let input: Vec<u32> = (0..100_000).collect();
+    let wrapped: Vec<Wrapping<u32>> = input.iter().copied().map(Wrapping).collect();
+    let bytes = bincode::encode_to_vec(&input, config).unwrap();
+
+    c.bench_function(&format!("{}_encode_vec_u32", name), |b| {
+        b.iter(|| black_box(bincode::encode_to_vec(black_box(&input), config).unwrap()))
+    });
+    c.bench_function(&format!("{}_encode_vec_u32_per_element", name), |b| {
+        b.iter(|| black_box(bincode::encode_to_vec(black_box(&wrapped), config).unwrap()))
+    });
+    c.bench_function(&format!("{}_decode_vec_u32", name), |b| {
+        b.iter(|| {
+            let _: (Vec<u32>, usize) =
+                black_box(bincode::decode_from_slice(black_box(&bytes), config).unwrap());
+        })
+    });
+    c.bench_function(&format!("{}_decode_vec_u32_per_element", name), |b| {
+        b.iter(|| {
+            let _: (Vec<Wrapping<u32>>, usize) =
+                black_box(bincode::decode_from_slice(black_box(&bytes), config).unwrap());
+        })
+    });
+}
+
+fn native_endian(c: &mut Criterion) {
+    encode_decode_u32(c, "native_endian", config::legacy());
+}
+
+fn swapped_endian(c: &mut Criterion) {
+    encode_decode_u32(c, "swapped_endian", config::legacy().with_big_endian());
+}
+
+fn decode_array_f64(c: &mut Criterion) {
+    let config = config::legacy();
+    let bytes = bincode::encode_to_vec([1.5f64; 1024], config).unwrap();
+
+    c.bench_function("decode_array_f64", |b| {
+        b.iter(|| {
+            let _: ([f64; 1024], usize) =
+                black_box(bincode::decode_from_slice(black_box(&bytes), config).unwrap());
+        })
+    });
+}
+
+criterion_group!(benches, native_endian, swapped_endian, decode_array_f64);
+criterion_main!(benches);
@@ -66,6 +66,22 @@ fn slice_varint_u64(c: &mut Criterion) {
     });
 }
 
+fn slice_varint_u64_small(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let dist = rand::distributions::Uniform::from(0..251);
+    let input: Vec<u64> = std::iter::from_fn(|| Some(dist.sample(&mut rng)))
+        .take(10_000)
+        .collect();
+    let config = config::standard();
+    let bytes = bincode::encode_to_vec(input, config).unwrap();
+
+    c.bench_function("slice_varint_u64_small", |b| {
+        b.iter(|| {
+            let _: (Vec<u64>, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+        })
+    });
+}
+
 fn bufreader_varint_u8(c: &mut Criterion) {
     let mut rng = rand::thread_rng();
     let dist = rand::distributions::Uniform::from(0..u8::MAX);
@@ -144,6 +160,7 @@ criterion_group!(
     slice_varint_u16,
     slice_varint_u32,
     slice_varint_u64,
+    slice_varint_u64_small,
     bufreader_varint_u8,
     bufreader_varint_u16,
     bufreader_varint_u32,
@@ -7,6 +7,9 @@ pub struct ContainerAttributes {
     pub decode_bounds: Option<(String, Literal)>,
     pub borrow_decode_bounds: Option<(String, Literal)>,
     pub encode_bounds: Option<(String, Literal)>,
+    pub versioned: bool,
+    pub tag_type: Option<(String, Literal)>,
+    pub length_prefixed: bool,
 }
 
 impl Default for ContainerAttributes {
@@ -17,6 +20,9 @@ impl Default for ContainerAttributes {
             decode_bounds: None,
             encode_bounds: None,
             borrow_decode_bounds: None,
+            versioned: false,
+            tag_type: None,
+            length_prefixed: false,
         }
     }
 }
@@ -76,6 +82,27 @@ impl FromAttribute for ContainerAttributes {
                         return Err(Error::custom_at("Should be a literal str", val.span()));
                     }
                 }
+                ParsedAttribute::Property(key, val) if key.to_string() == "tag_type" => {
+                    let val_string = val.to_string();
+                    match val_string.as_str() {
+                        "\"u8\"" | "\"u16\"" | "\"u32\"" => {
+                            result.tag_type =
+                                Some((val_string[1..val_string.len() - 1].to_string(), val));
+                        }
+                        _ => {
+                            return Err(Error::custom_at(
+                                "Should be \"u8\", \"u16\" or \"u32\"",
+                                val.span(),
+                            ))
+                        }
+                    }
+                }
+                ParsedAttribute::Tag(i) if i.to_string() == "versioned" => {
+                    result.versioned = true;
+                }
+                ParsedAttribute::Tag(i) if i.to_string() == "length_prefixed" => {
+                    result.length_prefixed = true;
+                }
                 ParsedAttribute::Tag(i) => {
                     return Err(Error::custom_at("Unknown field attribute", i.span()))
                 }
@@ -89,9 +116,83 @@ impl FromAttribute for ContainerAttributes {
     }
 }
 
+#[derive(Default)]
+pub struct VariantAttributes {
+    pub tag: Option<(u32, Literal)>,
+    pub other: bool,
+}
+
+impl FromAttribute for VariantAttributes {
+    fn parse(group: &Group) -> Result<Option<Self>> {
+        let attributes = match parse_tagged_attribute(group, "bincode")? {
+            Some(body) => body,
+            None => return Ok(None),
+        };
+        let mut result = Self::default();
+        for attribute in attributes {
+            match attribute {
+                ParsedAttribute::Property(key, val) if key.to_string() == "tag" => {
+                    result.tag = Some((parse_discriminant(&val)?, val));
+                }
+                ParsedAttribute::Tag(i) if i.to_string() == "other" => {
+                    result.other = true;
+                }
+                ParsedAttribute::Tag(i) => {
+                    return Err(Error::custom_at("Unknown variant attribute", i.span()))
+                }
+                ParsedAttribute::Property(key, _) => {
+                    return Err(Error::custom_at("Unknown variant attribute", key.span()))
+                }
+                _ => {}
+            }
+        }
+        Ok(Some(result))
+    }
+}
+
+/// Parses an integer literal like `5`, `5u8`, `0x1F`, `1_000` or `-1` as an `u32`. Negative values are converted like `value as u32`, so `-1` becomes `u32::MAX`.
+pub fn parse_discriminant(val: &Literal) -> Result<u32> {
+    let val_string = val.to_string().replace('_', "");
+    let (negative, val_str) = match val_string.strip_prefix('-') {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, val_string.as_str()),
+    };
+    let (digits, radix) = match val_str.get(..2) {
+        Some("0x") => (&val_str[2..], 16),
+        Some("0o") => (&val_str[2..], 8),
+        Some("0b") => (&val_str[2..], 2),
+        _ => (val_str, 10),
+    };
+    // Strip a type suffix like `u8` or `i32`
+    let digits = match digits.find(['u', 'i']) {
+        Some(idx) => &digits[..idx],
+        None => digits,
+    };
+    let value = u32::from_str_radix(digits, radix).ok().and_then(|value| {
+        if !negative {
+            Some(value)
+        } else if value <= i32::MAX as u32 + 1 {
+            Some(value.wrapping_neg())
+        } else {
+            None
+        }
+    });
+    value.ok_or_else(|| {
+        Error::custom_at(
+            "Should be an integer literal that fits in an u32 or i32, use `#[bincode(tag = N)]` to encode this variant with a different value",
+            val.span(),
+        )
+    })
+}
+
 #[derive(Default)]
 pub struct FieldAttributes {
     pub with_serde: bool,
+    pub skip: bool,
+    pub version: Option<(u32, Literal)>,
+    pub default: Option<(String, Literal)>,
+    pub encode_with: Option<(String, Literal)>,
+    pub decode_with: Option<(String, Literal)>,
 }
 
 impl FromAttribute for FieldAttributes {
@@ -106,6 +207,54 @@ impl FromAttribute for FieldAttributes {
                 ParsedAttribute::Tag(i) if i.to_string() == "with_serde" => {
                     result.with_serde = true;
                 }
+                ParsedAttribute::Tag(i) if i.to_string() == "skip" => {
+                    result.skip = true;
+                }
+                ParsedAttribute::Property(key, val) if key.to_string() == "version" => {
+                    match val.to_string().parse() {
+                        Ok(version) => result.version = Some((version, val)),
+                        Err(_) => {
+                            return Err(Error::custom_at("Should be an u32 literal", val.span()))
+                        }
+                    }
+                }
+                ParsedAttribute::Property(key, val) if key.to_string() == "default" => {
+                    let val_string = val.to_string();
+                    if val_string.starts_with('"') && val_string.ends_with('"') {
+                        result.default =
+                            Some((val_string[1..val_string.len() - 1].to_string(), val));
+                    } else {
+                        return Err(Error::custom_at("Should be a literal str", val.span()));
+                    }
+                }
+                ParsedAttribute::Property(key, val) if key.to_string() == "with" => {
+                    let val_string = val.to_string();
+                    if val_string.starts_with('"') && val_string.ends_with('"') {
+                        let module = &val_string[1..val_string.len() - 1];
+                        result.encode_with = Some((format!("{}::encode", module), val.clone()));
+                        result.decode_with = Some((format!("{}::decode", module), val));
+                    } else {
+                        return Err(Error::custom_at("Should be a literal str", val.span()));
+                    }
+                }
+                ParsedAttribute::Property(key, val) if key.to_string() == "encode_with" => {
+                    let val_string = val.to_string();
+                    if val_string.starts_with('"') && val_string.ends_with('"') {
+                        result.encode_with =
+                            Some((val_string[1..val_string.len() - 1].to_string(), val));
+                    } else {
+                        return Err(Error::custom_at("Should be a literal str", val.span()));
+                    }
+                }
+                ParsedAttribute::Property(key, val) if key.to_string() == "decode_with" => {
+                    let val_string = val.to_string();
+                    if val_string.starts_with('"') && val_string.ends_with('"') {
+                        result.decode_with =
+                            Some((val_string[1..val_string.len() - 1].to_string(), val));
+                    } else {
+                        return Err(Error::custom_at("Should be a literal str", val.span()));
+                    }
+                }
                 ParsedAttribute::Tag(i) => {
                     return Err(Error::custom_at("Unknown field attribute", i.span()))
                 }
@@ -115,6 +264,122 @@ impl FromAttribute for FieldAttributes {
                 _ => {}
             }
         }
+        if let Some((_, lit)) = result.encode_with.as_ref().or(result.decode_with.as_ref()) {
+            if result.skip || result.with_serde {
+                return Err(Error::custom_at(
+                    "`with`, `encode_with` and `decode_with` can not be used together with `skip` or `with_serde`",
+                    lit.span(),
+                ));
+            }
+        }
+        if let Some((_, lit)) = &result.default {
+            if !result.skip && result.version.is_none() {
+                return Err(Error::custom_at(
+                    "`default` can only be used together with `skip` or `version`",
+                    lit.span(),
+                ));
+            }
+        }
+        if let (true, Some((_, lit))) = (result.skip, &result.version) {
+            return Err(Error::custom_at(
+                "A field with `skip` can not have a `version`",
+                lit.span(),
+            ));
+        }
         Ok(Some(result))
     }
 }
+
+impl FieldAttributes {
+    /// The statement that encodes `value`, which is a reference to this field, into `encoder`. Returns an empty string if this field is skipped.
+    pub fn encode(&self, crate_name: &str, value: &str, encoder: &str) -> String {
+        if self.skip {
+            String::new()
+        } else if let Some((path, _)) = &self.encode_with {
+            format!("{}({}, {})?;", path, value, encoder)
+        } else if self.with_serde {
+            format!(
+                "{0}::Encode::encode(&{0}::serde::Compat({1}), {2})?;",
+                crate_name, value, encoder
+            )
+        } else {
+            format!("{}::Encode::encode({}, {})?;", crate_name, value, encoder)
+        }
+    }
+
+    /// The expression that decodes this field from `decoder`. `context` is a call to a constructor of `PathSegment` that adds this field to the path of the error, e.g. `field::<Self>("a")`
+    pub fn decode(&self, crate_name: &str, borrow: bool, context: &str) -> String {
+        // bincode::Decode::decode(decoder).map_err(|__e| bincode::de::Decoder::error_at(decoder, __e, bincode::error::PathSegment::field::<Self>("a")))?
+        let with_context = |call: String| {
+            format!(
+                "{0}.map_err(|__e| {1}::de::Decoder::error_at(decoder, __e, {1}::error::PathSegment::{2}))?",
+                call, crate_name, context
+            )
+        };
+        if let Some((path, _)) = &self.decode_with {
+            // The same function is used for `BorrowDecode`, as every `BorrowDecoder` is also a `Decoder`
+            return with_context(format!("{}(decoder)", path));
+        }
+        match (self.skip, self.with_serde, borrow) {
+            (true, _, _) => self.default_value(),
+            (false, true, false) => format!(
+                "({}).0",
+                with_context(format!(
+                    "<{0}::serde::Compat<_> as {0}::Decode>::decode(decoder)",
+                    crate_name
+                ))
+            ),
+            (false, true, true) => format!(
+                "({}).0",
+                with_context(format!(
+                    "<{0}::serde::BorrowCompat<_> as {0}::BorrowDecode>::borrow_decode(decoder)",
+                    crate_name
+                ))
+            ),
+            (false, false, false) => {
+                with_context(format!("{}::Decode::decode(decoder)", crate_name))
+            }
+            (false, false, true) => with_context(format!(
+                "{}::BorrowDecode::borrow_decode(decoder)",
+                crate_name
+            )),
+        }
+    }
+
+    /// The statement that skips this field in the decoder without building it, where `ty` is the type of the field
+    pub fn skip_decode(&self, crate_name: &str, ty: &str, borrow: bool) -> String {
+        if let Some((path, _)) = &self.decode_with {
+            return format!("let _: {} = {}(decoder)?;", ty, path);
+        }
+        match (self.skip, self.with_serde, borrow) {
+            // Skipped fields are not encoded
+            (true, _, _) => String::new(),
+            (false, true, false) => format!(
+                "<{0}::serde::Compat<{1}> as {0}::Decode>::skip(decoder)?;",
+                crate_name, ty
+            ),
+            (false, true, true) => format!(
+                "<{0}::serde::BorrowCompat<{1}> as {0}::BorrowDecode<'__de>>::borrow_skip(decoder)?;",
+                crate_name, ty
+            ),
+            (false, false, false) => format!("<{} as {}::Decode>::skip(decoder)?;", ty, crate_name),
+            (false, false, true) => format!(
+                "<{} as {}::BorrowDecode<'__de>>::borrow_skip(decoder)?;",
+                ty, crate_name
+            ),
+        }
+    }
+
+    /// Returns `true` if the layout of this field is not known to bincode, e.g. because it is encoded with serde or with a custom function
+    pub fn is_opaque(&self) -> bool {
+        self.with_serde || self.encode_with.is_some()
+    }
+
+    /// The expression that is used for this field when it is not in the encoded data
+    pub fn default_value(&self) -> String {
+        match &self.default {
+            Some((path, _)) => format!("{}()", path),
+            None => "core::default::Default::default()".to_string(),
+        }
+    }
+}
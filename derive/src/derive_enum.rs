@@ -1,4 +1,8 @@
-use crate::attribute::{ContainerAttributes, FieldAttributes};
+use crate::attribute::{
+    parse_discriminant, ContainerAttributes, FieldAttributes, VariantAttributes,
+};
+use crate::derive_struct::{field_types, generate_schema_fields};
+use virtue::parse::IdentOrIndex;
 use virtue::prelude::*;
 
 const TUPLE_FIELD_PREFIX: &str = "field_";
@@ -9,14 +13,195 @@ pub(crate) struct DeriveEnum {
 }
 
 impl DeriveEnum {
-    fn iter_fields(&self) -> EnumVariantIterator {
-        EnumVariantIterator {
-            idx: 0,
-            variants: &self.variants,
+    /// Returns the tag that every variant except the `#[bincode(other)]` variant is encoded with. This is the `#[bincode(tag = N)]` of the variant, its explicit discriminant (`Variant = N`), or one more than the tag of the previous variant.
+    fn tags(&self) -> Result<Vec<u32>> {
+        let mut tags: Vec<u32> = Vec::with_capacity(self.variants.len());
+        for variant in &self.variants {
+            let attributes = variant
+                .attributes
+                .get_attribute::<VariantAttributes>()?
+                .unwrap_or_default();
+            if attributes.other {
+                // The `other` variant does not have a tag of its own
+                continue;
+            }
+            let tag = if let Some((tag, _)) = attributes.tag {
+                tag
+            } else if let Some(value) = &variant.value {
+                parse_discriminant(value)?
+            } else {
+                match tags.last() {
+                    Some(previous) => previous.checked_add(1).ok_or_else(|| {
+                        Error::custom_at(
+                            "The tag of this variant overflows an u32",
+                            variant.name.span(),
+                        )
+                    })?,
+                    None => 0,
+                }
+            };
+            let max = match self.attributes.tag_type.as_ref().map(|(ty, _)| ty.as_str()) {
+                Some("u8") => u8::MAX as u32,
+                Some("u16") => u16::MAX as u32,
+                _ => u32::MAX,
+            };
+            if tag > max {
+                return Err(Error::custom_at(
+                    format!("Tag {} does not fit in the `tag_type` of this enum", tag),
+                    variant.name.span(),
+                ));
+            }
+            if tags.contains(&tag) {
+                return Err(Error::custom_at(
+                    format!("Tag {} is used by more than one variant", tag),
+                    variant.name.span(),
+                ));
+            }
+            tags.push(tag);
+        }
+        Ok(tags)
+    }
+
+    /// Returns every variant except the `#[bincode(other)]` variant, together with its tag
+    fn iter_fields(&self) -> Result<Vec<(u32, &EnumVariant)>> {
+        let other = self
+            .other_variant()?
+            .map(|variant| variant.name.to_string());
+        let variants = self
+            .variants
+            .iter()
+            .filter(|variant| Some(variant.name.to_string()) != other);
+        Ok(self.tags()?.into_iter().zip(variants).collect())
+    }
+
+    /// Returns the variant with `#[bincode(other)]`, if any
+    fn other_variant(&self) -> Result<Option<&EnumVariant>> {
+        let mut result = None;
+        for variant in &self.variants {
+            let attributes = variant
+                .attributes
+                .get_attribute::<VariantAttributes>()?
+                .unwrap_or_default();
+            if attributes.other {
+                if result.is_some() {
+                    return Err(Error::custom_at(
+                        "Only one variant can have `#[bincode(other)]`",
+                        variant.name.span(),
+                    ));
+                }
+                result = Some(variant);
+            }
+        }
+        Ok(result)
+    }
+
+    /// The statement that encodes `tag` as the variant index, either as the `#[bincode(tag_type = "..")]` or as configured in the encoder
+    fn encode_tag(&self, tag: u32) -> String {
+        let crate_name = self.attributes.crate_name.as_str();
+        match &self.attributes.tag_type {
+            Some((ty, _)) => format!(
+                "<{1} as {0}::Encode>::encode(&{2}{1}, encoder)?;",
+                crate_name, ty, tag
+            ),
+            None => format!(
+                "{}::enc::encode_enum_tag(encoder, {}u32)?;",
+                crate_name, tag
+            ),
+        }
+    }
+
+    /// The statement that encodes the `u32` stored in the `#[bincode(other)]` variant as the variant index
+    fn encode_dynamic_tag(&self, tag: &str) -> String {
+        let crate_name = self.attributes.crate_name.as_str();
+        match self.attributes.tag_type.as_ref().map(|(ty, _)| ty.as_str()) {
+            Some("u32") => format!("<u32 as {}::Encode>::encode({}, encoder)?;", crate_name, tag),
+            Some(ty) => format!(
+                "<{1} as {0}::Encode>::encode(&<{1} as core::convert::TryFrom<u32>>::try_from(*{2}).map_err(|_| {0}::error::EncodeError::EnumTagOverflow {{ tag: *{2}, tag_type: {0}::config::EnumTagType::{3} }})?, encoder)?;",
+                crate_name,
+                ty,
+                tag,
+                ty.to_uppercase()
+            ),
+            None => format!(
+                "{}::enc::encode_enum_tag(encoder, *{})?;",
+                crate_name, tag
+            ),
+        }
+    }
+
+    /// The statement that decodes the variant index as an `u32` into `variant_index`
+    fn decode_tag(&self) -> String {
+        let crate_name = self.attributes.crate_name.as_str();
+        match self.attributes.tag_type.as_ref().map(|(ty, _)| ty.as_str()) {
+            Some("u32") => format!(
+                "let variant_index = <u32 as {}::Decode>::decode(decoder)?;",
+                crate_name
+            ),
+            Some(ty) => format!(
+                "let variant_index = <u32 as core::convert::From<{1}>>::from(<{1} as {0}::Decode>::decode(decoder)?);",
+                crate_name, ty
+            ),
+            None => format!(
+                "let variant_index = {}::de::decode_enum_tag(decoder)?;",
+                crate_name
+            ),
         }
     }
 
+    /// Returns an error if an attribute is used that is only supported on structs
+    fn check_attributes(&self) -> Result {
+        if self.attributes.versioned {
+            return Err(Error::custom(
+                "`#[bincode(versioned)]` is only supported on structs",
+            ));
+        }
+        if let Some(variant) = self.other_variant()? {
+            let attributes = variant
+                .attributes
+                .get_attribute::<VariantAttributes>()?
+                .unwrap_or_default();
+            if let Some((_, lit)) = attributes.tag.as_ref() {
+                return Err(Error::custom_at(
+                    "The `other` variant can not have a tag",
+                    lit.span(),
+                ));
+            }
+            match variant.fields.as_ref().map(|fields| fields.names().len()) {
+                None | Some(0) => {}
+                Some(2) if self.attributes.length_prefixed => {}
+                Some(2) => {
+                    return Err(Error::custom_at(
+                        "An `other` variant with fields can only be used in an enum with `#[bincode(length_prefixed)]`",
+                        variant.name.span(),
+                    ))
+                }
+                Some(_) => {
+                    return Err(Error::custom_at(
+                        "The `other` variant should have no fields, or two fields: the tag as an `u32` and the raw bytes of the variant",
+                        variant.name.span(),
+                    ))
+                }
+            }
+        }
+        for variant in &self.variants {
+            for field in variant.fields.iter().flat_map(|fields| fields.names()) {
+                let attributes = field
+                    .attributes()
+                    .get_attribute::<FieldAttributes>()?
+                    .unwrap_or_default();
+                if let Some((_, lit)) = attributes.version {
+                    return Err(Error::custom_at(
+                        "`version` is only supported on fields of a struct",
+                        lit.span(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn generate_encode(self, generator: &mut Generator) -> Result<()> {
+        self.check_attributes()?;
         let crate_name = self.attributes.crate_name.as_str();
         generator
             .impl_for(format!("{}::Encode", crate_name))
@@ -52,7 +237,13 @@ impl DeriveEnum {
                     if self.variants.is_empty() {
                         self.encode_empty_enum_case(match_body)?;
                     }
-                    for (variant_index, variant) in self.iter_fields() {
+                    let other_variant = self.other_variant()?;
+                    let variants = self
+                        .iter_fields()?
+                        .into_iter()
+                        .map(|(tag, variant)| (Some(tag), variant))
+                        .chain(other_variant.map(|variant| (None, variant)));
+                    for (variant_index, variant) in variants {
                         // Self::Variant
                         match_body.ident_str("Self");
                         match_body.puncts("::");
@@ -67,9 +258,24 @@ impl DeriveEnum {
                                     if idx != 0 {
                                         field_body.punct(',');
                                     }
-                                    field_body.push(
-                                        field_name.to_token_tree_with_prefix(TUPLE_FIELD_PREFIX),
-                                    );
+                                    let attributes = field_name
+                                        .attributes()
+                                        .get_attribute::<FieldAttributes>()?
+                                        .unwrap_or_default();
+                                    if attributes.skip {
+                                        // Skipped fields are not encoded, so they are not bound
+                                        // Self::Variant { a: _, .. } or Self::Variant(_, ..)
+                                        if let IdentOrIndex::Ident { ident, .. } = &field_name {
+                                            field_body.ident(ident.clone());
+                                            field_body.punct(':');
+                                        }
+                                        field_body.ident_str("_");
+                                    } else {
+                                        field_body.push(
+                                            field_name
+                                                .to_token_tree_with_prefix(TUPLE_FIELD_PREFIX),
+                                        );
+                                    }
                                 }
                                 Ok(())
                             })?;
@@ -82,46 +288,63 @@ impl DeriveEnum {
                         // Body of this variant
                         // Note that the fields are available as locals because of the match destructuring above
                         // {
-                        //      encoder.encode_u32(n)?;
+                        //      bincode::enc::encode_enum_tag(encoder, n)?;
                         //      bincode::Encode::encode(a, encoder)?;
                         //      bincode::Encode::encode(b, encoder)?;
                         //      bincode::Encode::encode(c, encoder)?;
                         // }
                         match_body.group(Delimiter::Brace, |body| {
+                            let variant_index = match variant_index {
+                                Some(variant_index) => variant_index,
+                                None => return self.encode_other_variant(variant, body),
+                            };
                             // variant index
-                            body.push_parsed(format!("<u32 as {}::Encode>::encode", crate_name))?;
-                            body.group(Delimiter::Parenthesis, |args| {
-                                args.punct('&');
-                                args.group(Delimiter::Parenthesis, |num| {
-                                    num.extend(variant_index);
-                                    Ok(())
-                                })?;
-                                args.punct(',');
-                                args.push_parsed("encoder")?;
-                                Ok(())
-                            })?;
-                            body.punct('?');
-                            body.punct(';');
-                            // If we have any fields, encode them all one by one
-                            if let Some(fields) = variant.fields.as_ref() {
-                                for field_name in fields.names() {
+                            body.push_parsed(self.encode_tag(variant_index))?;
+                            let fields = variant
+                                .fields
+                                .iter()
+                                .flat_map(|fields| fields.names())
+                                .map(|field_name| {
                                     let attributes = field_name
                                         .attributes()
                                         .get_attribute::<FieldAttributes>()?
                                         .unwrap_or_default();
-                                    if attributes.with_serde {
-                                        body.push_parsed(format!(
-                                        "{0}::Encode::encode(&{0}::serde::Compat({1}), encoder)?;",
-                                        crate_name,
+                                    Ok((
                                         field_name.to_string_with_prefix(TUPLE_FIELD_PREFIX),
-                                    ))?;
-                                    } else {
-                                        body.push_parsed(format!(
-                                            "{0}::Encode::encode({1}, encoder)?;",
-                                            crate_name,
-                                            field_name.to_string_with_prefix(TUPLE_FIELD_PREFIX),
-                                        ))?;
-                                    }
+                                        attributes,
+                                    ))
+                                })
+                                .collect::<Result<Vec<_>>>()?;
+                            if self.attributes.length_prefixed {
+                                // The length of the body, so unknown variants can be skipped
+                                // bincode::enc::encode_length_prefixed(encoder, |encoder| {
+                                //     bincode::Encode::encode(a, encoder)?;
+                                //     Ok(())
+                                // })?;
+                                body.push_parsed(format!(
+                                    "{}::enc::encode_length_prefixed",
+                                    crate_name
+                                ))?;
+                                body.group(Delimiter::Parenthesis, |args| {
+                                    args.push_parsed("encoder, |encoder|")?;
+                                    args.group(Delimiter::Brace, |fields_body| {
+                                        for (field, attributes) in &fields {
+                                            fields_body.push_parsed(
+                                                attributes.encode(crate_name, field, "encoder"),
+                                            )?;
+                                        }
+                                        fields_body.push_parsed("Ok(())")?;
+                                        Ok(())
+                                    })?;
+                                    Ok(())
+                                })?;
+                                body.push_parsed("?;")?;
+                            } else {
+                                // If we have any fields, encode them all one by one
+                                for (field, attributes) in &fields {
+                                    body.push_parsed(
+                                        attributes.encode(crate_name, field, "encoder"),
+                                    )?;
                                 }
                             }
                             body.push_parsed("Ok(())")?;
@@ -136,6 +359,34 @@ impl DeriveEnum {
         Ok(())
     }
 
+    /// The body of the match arm that encodes the `#[bincode(other)]` variant. A unit variant can not be encoded, because its original tag and contents are unknown. A variant that kept its tag and raw bytes writes them back as they were read.
+    fn encode_other_variant(&self, variant: &EnumVariant, body: &mut StreamBuilder) -> Result {
+        let crate_name = self.attributes.crate_name.as_str();
+        match variant.fields.as_ref().map(|fields| fields.names()) {
+            Some(names) if !names.is_empty() => {
+                // bincode::enc::encode_enum_tag(encoder, *tag)?;
+                // bincode::Encode::encode(bytes, encoder)?;
+                // Ok(())
+                body.push_parsed(
+                    self.encode_dynamic_tag(&names[0].to_string_with_prefix(TUPLE_FIELD_PREFIX)),
+                )?;
+                body.push_parsed(format!(
+                    "{}::Encode::encode({}, encoder)?;",
+                    crate_name,
+                    names[1].to_string_with_prefix(TUPLE_FIELD_PREFIX)
+                ))?;
+                body.push_parsed("Ok(())")?;
+            }
+            _ => {
+                body.push_parsed(format!(
+                    "core::result::Result::Err({}::error::EncodeError::Other(\"The `other` variant of an enum does not know which variant it was decoded from, and can not be encoded\"))",
+                    crate_name
+                ))?;
+            }
+        }
+        Ok(())
+    }
+
     /// If we're encoding an empty enum, we need to add an empty case in the form of:
     /// `_ => core::unreachable!(),`
     fn encode_empty_enum_case(&self, builder: &mut StreamBuilder) -> Result {
@@ -182,7 +433,8 @@ impl DeriveEnum {
                 variant_inner.ident_str("allowed");
                 variant_inner.punct(':');
 
-                if self.variants.iter().any(|i| i.value.is_some()) {
+                let tags = self.tags()?;
+                if tags.iter().enumerate().any(|(idx, tag)| *tag != idx as u32) {
                     // we have fixed values, implement AllowedEnumVariants::Allowed
                     variant_inner.push_parsed(format!(
                         "&{}::error::AllowedEnumVariants::Allowed",
@@ -191,11 +443,11 @@ impl DeriveEnum {
                     variant_inner.group(Delimiter::Parenthesis, |allowed_inner| {
                         allowed_inner.punct('&');
                         allowed_inner.group(Delimiter::Bracket, |allowed_slice| {
-                            for (idx, (ident, _)) in self.iter_fields().enumerate() {
+                            for (idx, tag) in tags.iter().enumerate() {
                                 if idx != 0 {
                                     allowed_slice.punct(',');
                                 }
-                                allowed_slice.extend(ident);
+                                allowed_slice.push(TokenTree::Literal(Literal::u32_suffixed(*tag)));
                             }
                             Ok(())
                         })?;
@@ -206,7 +458,7 @@ impl DeriveEnum {
                     variant_inner.push_parsed(format!(
                         "&{0}::error::AllowedEnumVariants::Range {{ min: 0, max: {1} }}",
                         crate_name,
-                        self.variants.len() - 1
+                        tags.len() - 1
                     ))?;
                 }
                 Ok(())
@@ -217,121 +469,78 @@ impl DeriveEnum {
     }
 
     pub fn generate_decode(self, generator: &mut Generator) -> Result<()> {
+        self.check_attributes()?;
         let crate_name = self.attributes.crate_name.as_str();
 
-        // Remember to keep this mostly in sync with generate_borrow_decode
-
         let enum_name = generator.target_name().to_string();
 
-        generator
-            .impl_for(format!("{}::Decode", crate_name))
-            .modify_generic_constraints(|generics, where_constraints| {
-                if let Some((bounds, lit)) = (self.attributes.decode_bounds.as_ref()).or(self.attributes.bounds.as_ref()) {
-                    where_constraints.clear();
-                    where_constraints.push_parsed_constraint(bounds).map_err(|e| e.with_span(lit.span()))?;
-                } else {
-                    for g in generics.iter_generics() {
-                        where_constraints.push_constraint(g, format!("{}::Decode", crate_name))?;
-                    }
-                }
-                Ok(())
-            })?
-            .generate_fn("decode")
-            .with_generic_deps("__D", [format!("{}::de::Decoder", crate_name)])
-            .with_arg("decoder", "&mut __D")
-            .with_return_type(format!("core::result::Result<Self, {}::error::DecodeError>", crate_name))
-            .body(|fn_builder| {
-                if self.variants.is_empty() {
-                    fn_builder.push_parsed(format!(
-                        "core::result::Result::Err({}::error::DecodeError::EmptyEnum {{ type_name: core::any::type_name::<Self>() }})",
-                        crate_name
-                    ))?;
-                } else {
-                    fn_builder
-                        .push_parsed(format!(
-                            "let variant_index = <u32 as {}::Decode>::decode(decoder)?;",
-                            crate_name
-                        ))?;
-                    fn_builder.push_parsed("match variant_index")?;
-                    fn_builder.group(Delimiter::Brace, |variant_case| {
-                        for (mut variant_index, variant) in self.iter_fields() {
-                            // idx => Ok(..)
-                            if variant_index.len() > 1 {
-                                variant_case.push_parsed("x if x == ")?;
-                                variant_case.extend(variant_index);
-                            } else {
-                                variant_case.push(variant_index.remove(0));
-                            }
-                            variant_case.puncts("=>");
-                            variant_case.ident_str("Ok");
-                            variant_case.group(Delimiter::Parenthesis, |variant_case_body| {
-                                // Self::Variant { }
-                                // Self::Variant { 0: ..., 1: ... 2: ... },
-                                // Self::Variant { a: ..., b: ... c: ... },
-                                variant_case_body.ident_str("Self");
-                                variant_case_body.puncts("::");
-                                variant_case_body.ident(variant.name.clone());
-
-                                variant_case_body.group(Delimiter::Brace, |variant_body| {
-                                    if let Some(fields) = variant.fields.as_ref() {
-                                        let is_tuple = matches!(fields, Fields::Tuple(_));
-                                        for (idx, field) in fields.names().into_iter().enumerate() {
-                                            if is_tuple {
-                                                variant_body.lit_usize(idx);
-                                            } else {
-                                                variant_body.ident(field.unwrap_ident().clone());
-                                            }
-                                            variant_body.punct(':');
-                                            let attributes = field.attributes().get_attribute::<FieldAttributes>()?.unwrap_or_default();
-                                            if attributes.with_serde {
-                                                variant_body
-                                                    .push_parsed(format!(
-                                                        "<{0}::serde::Compat<_> as {0}::Decode>::decode(decoder)?.0,",
-                                                        crate_name
-                                                    ))?;
-                                            } else {
-                                                variant_body
-                                                    .push_parsed(format!(
-                                                        "{}::Decode::decode(decoder)?,",
-                                                        crate_name
-                                                    ))?;
-                                            }
-                                        }
-                                    }
-                                    Ok(())
-                                })?;
-                                Ok(())
-                            })?;
-                            variant_case.punct(',');
+        {
+            let mut impl_for = generator.impl_for(format!("{}::Decode", crate_name));
+            impl_for
+                .modify_generic_constraints(|generics, where_constraints| {
+                    if let Some((bounds, lit)) =
+                        (self.attributes.decode_bounds.as_ref()).or(self.attributes.bounds.as_ref())
+                    {
+                        where_constraints.clear();
+                        where_constraints
+                            .push_parsed_constraint(bounds)
+                            .map_err(|e| e.with_span(lit.span()))?;
+                    } else {
+                        for g in generics.iter_generics() {
+                            where_constraints
+                                .push_constraint(g, format!("{}::Decode", crate_name))?;
                         }
-
-                        // invalid idx
-                        self.invalid_variant_case(&enum_name, variant_case)
-                    })?;
-                }
-                Ok(())
-            })?;
+                    }
+                    Ok(())
+                })?
+                .generate_fn("decode")
+                .with_generic_deps("__D", [format!("{}::de::Decoder", crate_name)])
+                .with_arg("decoder", "&mut __D")
+                .with_return_type(format!(
+                    "core::result::Result<Self, {}::error::DecodeError>",
+                    crate_name
+                ))
+                .body(|fn_builder| self.generate_decode_body(&enum_name, fn_builder, false))?;
+            impl_for
+                .generate_fn("skip")
+                .with_generic_deps("__D", [format!("{}::de::Decoder", crate_name)])
+                .with_arg("decoder", "&mut __D")
+                .with_return_type(format!(
+                    "core::result::Result<(), {}::error::DecodeError>",
+                    crate_name
+                ))
+                .body(|fn_builder| self.generate_skip_body(&enum_name, fn_builder, false))?;
+        }
         self.generate_borrow_decode(generator)?;
         Ok(())
     }
 
     pub fn generate_borrow_decode(self, generator: &mut Generator) -> Result<()> {
+        self.check_attributes()?;
         let crate_name = &self.attributes.crate_name;
 
-        // Remember to keep this mostly in sync with generate_decode
         let enum_name = generator.target_name().to_string();
 
-        generator.impl_for_with_lifetimes(format!("{}::BorrowDecode", crate_name), ["__de"])
+        let mut impl_for =
+            generator.impl_for_with_lifetimes(format!("{}::BorrowDecode", crate_name), ["__de"]);
+        impl_for
             .modify_generic_constraints(|generics, where_constraints| {
-                if let Some((bounds, lit)) = (self.attributes.borrow_decode_bounds.as_ref()).or(self.attributes.bounds.as_ref()) {
+                if let Some((bounds, lit)) = (self.attributes.borrow_decode_bounds.as_ref())
+                    .or(self.attributes.bounds.as_ref())
+                {
                     where_constraints.clear();
-                    where_constraints.push_parsed_constraint(bounds).map_err(|e| e.with_span(lit.span()))?;
+                    where_constraints
+                        .push_parsed_constraint(bounds)
+                        .map_err(|e| e.with_span(lit.span()))?;
                 } else {
                     for g in generics.iter_generics() {
-                        where_constraints.push_constraint(g, format!("{}::de::BorrowDecode<'__de>", crate_name)).unwrap();
+                        where_constraints
+                            .push_constraint(g, format!("{}::de::BorrowDecode<'__de>", crate_name))
+                            .unwrap();
                     }
                     for lt in generics.iter_lifetimes() {
-                        where_constraints.push_parsed_constraint(format!("'__de: '{}", lt.ident))?;
+                        where_constraints
+                            .push_parsed_constraint(format!("'__de: '{}", lt.ident))?;
                     }
                 }
                 Ok(())
@@ -339,87 +548,365 @@ impl DeriveEnum {
             .generate_fn("borrow_decode")
             .with_generic_deps("__D", [format!("{}::de::BorrowDecoder<'__de>", crate_name)])
             .with_arg("decoder", "&mut __D")
-            .with_return_type(format!("core::result::Result<Self, {}::error::DecodeError>", crate_name))
-            .body(|fn_builder| {
-                if self.variants.is_empty() {
-                    fn_builder.push_parsed(format!(
-                        "core::result::Result::Err({}::error::DecodeError::EmptyEnum {{ type_name: core::any::type_name::<Self>() }})",
-                        crate_name
-                    ))?;
-                } else {
-                    fn_builder
-                        .push_parsed(format!("let variant_index = <u32 as {}::Decode>::decode(decoder)?;", crate_name))?;
-                    fn_builder.push_parsed("match variant_index")?;
-                    fn_builder.group(Delimiter::Brace, |variant_case| {
-                        for (mut variant_index, variant) in self.iter_fields() {
-                            // idx => Ok(..)
-                            if variant_index.len() > 1 {
-                                variant_case.push_parsed("x if x == ")?;
-                                variant_case.extend(variant_index);
+            .with_return_type(format!(
+                "core::result::Result<Self, {}::error::DecodeError>",
+                crate_name
+            ))
+            .body(|fn_builder| self.generate_decode_body(&enum_name, fn_builder, true))?;
+        impl_for
+            .generate_fn("borrow_skip")
+            .with_generic_deps("__D", [format!("{}::de::BorrowDecoder<'__de>", crate_name)])
+            .with_arg("decoder", "&mut __D")
+            .with_return_type(format!(
+                "core::result::Result<(), {}::error::DecodeError>",
+                crate_name
+            ))
+            .body(|fn_builder| self.generate_skip_body(&enum_name, fn_builder, true))?;
+        Ok(())
+    }
+
+    fn generate_decode_body(
+        &self,
+        enum_name: &str,
+        fn_builder: &mut StreamBuilder,
+        borrow: bool,
+    ) -> Result {
+        let crate_name = self.attributes.crate_name.as_str();
+        if self.variants.is_empty() {
+            fn_builder.push_parsed(format!(
+                "core::result::Result::Err({}::error::DecodeError::EmptyEnum {{ type_name: core::any::type_name::<Self>() }})",
+                crate_name
+            ))?;
+            return Ok(());
+        }
+        fn_builder.push_parsed(self.decode_tag())?;
+        fn_builder.push_parsed(format!(
+            "{}::de::Decoder::increase_depth(decoder)?;",
+            crate_name
+        ))?;
+        fn_builder.push_parsed("let __result = match variant_index")?;
+        fn_builder.group(Delimiter::Brace, |variant_case| {
+            for (variant_index, variant) in self.iter_fields()? {
+                // idx => {
+                //     Ok(Self::Variant { 0: ..., 1: ... 2: ... })
+                // }
+                //
+                // or, if length_prefixed:
+                //
+                // idx => {
+                //     let __len: usize = bincode::Decode::decode(decoder)?;
+                //     bincode::de::Decoder::decode_length_prefixed(decoder, __len, |decoder| {
+                //         Ok(Self::Variant { 0: ..., 1: ... 2: ... })
+                //     })
+                // }
+                variant_case.push(TokenTree::Literal(Literal::u32_suffixed(variant_index)));
+                variant_case.puncts("=>");
+                let mut value = StreamBuilder::new();
+                value.ident_str("Self");
+                value.puncts("::");
+                value.ident(variant.name.clone());
+                value.group(Delimiter::Brace, |variant_body| {
+                    if let Some(fields) = variant.fields.as_ref() {
+                        let is_tuple = matches!(fields, Fields::Tuple(_));
+                        for (idx, field) in fields.names().into_iter().enumerate() {
+                            if is_tuple {
+                                variant_body.lit_usize(idx);
                             } else {
-                                variant_case.push(variant_index.remove(0));
+                                variant_body.ident(field.unwrap_ident().clone());
                             }
-                            variant_case.puncts("=>");
-                            variant_case.ident_str("Ok");
-                            variant_case.group(Delimiter::Parenthesis, |variant_case_body| {
-                                // Self::Variant { }
-                                // Self::Variant { 0: ..., 1: ... 2: ... },
-                                // Self::Variant { a: ..., b: ... c: ... },
-                                variant_case_body.ident_str("Self");
-                                variant_case_body.puncts("::");
-                                variant_case_body.ident(variant.name.clone());
-
-                                variant_case_body.group(Delimiter::Brace, |variant_body| {
-                                    if let Some(fields) = variant.fields.as_ref() {
-                                        let is_tuple = matches!(fields, Fields::Tuple(_));
-                                        for (idx, field) in fields.names().into_iter().enumerate() {
-                                            if is_tuple {
-                                                variant_body.lit_usize(idx);
-                                            } else {
-                                                variant_body.ident(field.unwrap_ident().clone());
-                                            }
-                                            variant_body.punct(':');
-                                            let attributes = field.attributes().get_attribute::<FieldAttributes>()?.unwrap_or_default();
-                                            if attributes.with_serde {
-                                                variant_body
-                                                    .push_parsed(format!("<{0}::serde::BorrowCompat<_> as {0}::BorrowDecode>::borrow_decode(decoder)?.0,", crate_name))?;
-                                            } else {
-                                                variant_body.push_parsed(format!("{}::BorrowDecode::borrow_decode(decoder)?,", crate_name))?;
-                                            }
-                                        }
-                                    }
-                                    Ok(())
-                                })?;
+                            variant_body.punct(':');
+                            let attributes = field
+                                .attributes()
+                                .get_attribute::<FieldAttributes>()?
+                                .unwrap_or_default();
+                            // PathSegment::variant_field::<Self>("Variant", "a")
+                            let context = format!(
+                                "variant_field::<Self>(\"{}\", \"{}\")",
+                                variant.name.to_string().trim_start_matches("r#"),
+                                field.to_string().trim_start_matches("r#")
+                            );
+                            variant_body.push_parsed(format!(
+                                "{},",
+                                attributes.decode(crate_name, borrow, &context)
+                            ))?;
+                        }
+                    }
+                    Ok(())
+                })?;
+                variant_case.group(Delimiter::Brace, |variant_case_body| {
+                    if self.attributes.length_prefixed {
+                        variant_case_body.push_parsed(format!(
+                            "let __len: usize = {}::Decode::decode(decoder)?;",
+                            crate_name
+                        ))?;
+                        // The bytes of fields that a newer version of this enum added to this variant are skipped
+                        if borrow {
+                            variant_case_body.push_parsed(format!(
+                                "{}::de::BorrowDecoder::borrow_decode_length_prefixed",
+                                crate_name
+                            ))?;
+                        } else {
+                            variant_case_body.push_parsed(format!(
+                                "{}::de::Decoder::decode_length_prefixed",
+                                crate_name
+                            ))?;
+                        }
+                        variant_case_body.group(Delimiter::Parenthesis, |args| {
+                            args.push_parsed("decoder, __len, |decoder| Ok")?;
+                            args.group(Delimiter::Parenthesis, |ok_body| {
+                                ok_body.append(value);
                                 Ok(())
                             })?;
-                            variant_case.punct(',');
-                        }
+                            Ok(())
+                        })?;
+                    } else {
+                        variant_case_body.ident_str("Ok");
+                        variant_case_body.group(Delimiter::Parenthesis, |ok_body| {
+                            ok_body.append(value);
+                            Ok(())
+                        })?;
+                    }
+                    Ok(())
+                })?;
+            }
 
-                        // invalid idx
-                        self.invalid_variant_case(&enum_name, variant_case)
-                    })?;
-                }
-                Ok(())
-            })?;
+            match self.other_variant()? {
+                Some(variant) => self.other_variant_case(variant, variant_case, borrow),
+                // invalid idx
+                None => self.invalid_variant_case(enum_name, variant_case),
+            }
+        })?;
+        fn_builder.punct(';');
+        fn_builder.push_parsed(format!(
+            "{}::de::Decoder::decrease_depth(decoder);",
+            crate_name
+        ))?;
+        fn_builder.push_parsed("__result")?;
         Ok(())
     }
-}
 
-struct EnumVariantIterator<'a> {
-    variants: &'a [EnumVariant],
-    idx: usize,
-}
+    fn generate_skip_body(
+        &self,
+        enum_name: &str,
+        fn_builder: &mut StreamBuilder,
+        borrow: bool,
+    ) -> Result {
+        let crate_name = self.attributes.crate_name.as_str();
+        if self.variants.is_empty() {
+            fn_builder.push_parsed(format!(
+                "core::result::Result::Err({}::error::DecodeError::EmptyEnum {{ type_name: core::any::type_name::<Self>() }})",
+                crate_name
+            ))?;
+            return Ok(());
+        }
+        // A length prefixed body can be skipped in one step
+        // let __len: usize = bincode::Decode::decode(decoder)?;
+        // bincode::de::skip_bytes(decoder, __len)?;
+        let skip_body = format!(
+            "let __len: usize = {0}::Decode::decode(decoder)?; {0}::de::skip_bytes(decoder, __len)?;",
+            crate_name
+        );
+        fn_builder.push_parsed(self.decode_tag())?;
+        fn_builder.push_parsed(format!(
+            "{}::de::Decoder::increase_depth(decoder)?;",
+            crate_name
+        ))?;
+        fn_builder.push_parsed("let __result = match variant_index")?;
+        fn_builder.group(Delimiter::Brace, |variant_case| {
+            for (variant_index, variant) in self.iter_fields()? {
+                // idx => {
+                //     <u32 as bincode::Decode>::skip(decoder)?;
+                //     Ok(())
+                // }
+                variant_case.push(TokenTree::Literal(Literal::u32_suffixed(variant_index)));
+                variant_case.puncts("=>");
+                variant_case.group(Delimiter::Brace, |variant_case_body| {
+                    if self.attributes.length_prefixed {
+                        variant_case_body.push_parsed(&skip_body)?;
+                    } else if let Some(fields) = variant.fields.as_ref() {
+                        let types = field_types(Some(fields), borrow);
+                        for (field, ty) in fields.names().into_iter().zip(&types) {
+                            let attributes = field
+                                .attributes()
+                                .get_attribute::<FieldAttributes>()?
+                                .unwrap_or_default();
+                            variant_case_body
+                                .push_parsed(attributes.skip_decode(crate_name, ty, borrow))?;
+                        }
+                    }
+                    variant_case_body.push_parsed("Ok(())")?;
+                    Ok(())
+                })?;
+            }
 
-impl<'a> Iterator for EnumVariantIterator<'a> {
-    type Item = (Vec<TokenTree>, &'a EnumVariant);
+            if self.other_variant()?.is_some() {
+                // The `other` variant either has no body, or a length prefixed body
+                variant_case.push_parsed("_ =>")?;
+                variant_case.group(Delimiter::Brace, |body| {
+                    if self.attributes.length_prefixed {
+                        body.push_parsed(&skip_body)?;
+                    }
+                    body.push_parsed("Ok(())")?;
+                    Ok(())
+                })?;
+                Ok(())
+            } else {
+                self.invalid_variant_case(enum_name, variant_case)
+            }
+        })?;
+        fn_builder.punct(';');
+        fn_builder.push_parsed(format!(
+            "{}::de::Decoder::decrease_depth(decoder);",
+            crate_name
+        ))?;
+        fn_builder.push_parsed("__result")?;
+        Ok(())
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let idx = self.idx;
-        let variant = self.variants.get(self.idx)?;
-        self.idx += 1;
+    /// Build the catch-all case that decodes unknown variants into the `#[bincode(other)]` variant
+    fn other_variant_case(
+        &self,
+        variant: &EnumVariant,
+        result: &mut StreamBuilder,
+        borrow: bool,
+    ) -> Result {
+        let crate_name = self.attributes.crate_name.as_str();
+        result.ident_str("variant");
+        result.puncts("=>");
+        result.group(Delimiter::Brace, |body| {
+            match variant.fields.as_ref() {
+                Some(fields) if !fields.names().is_empty() => {
+                    // Keep the tag and the raw bytes. The length prefix of the variant is the same as the length of a slice, so the bytes can be decoded as e.g. a `Vec<u8>`.
+                    // Ok(Self::Other { 0: variant, 1: bincode::Decode::decode(decoder)? })
+                    let decode = if borrow {
+                        format!("{}::BorrowDecode::borrow_decode(decoder)?", crate_name)
+                    } else {
+                        format!("{}::Decode::decode(decoder)?", crate_name)
+                    };
+                    let names = fields.names();
+                    let name = |idx: usize| match &names[idx] {
+                        IdentOrIndex::Ident { ident, .. } => ident.to_string(),
+                        IdentOrIndex::Index { index, .. } => index.to_string(),
+                    };
+                    body.push_parsed(format!(
+                        "Ok(Self::{} {{ {}: variant, {}: {} }})",
+                        variant.name,
+                        name(0),
+                        name(1),
+                        decode
+                    ))?;
+                }
+                _ => {
+                    if self.attributes.length_prefixed {
+                        // let __len: usize = bincode::Decode::decode(decoder)?;
+                        // bincode::de::skip_bytes(decoder, __len)?;
+                        body.push_parsed(format!(
+                            "let __len: usize = {}::Decode::decode(decoder)?;",
+                            crate_name
+                        ))?;
+                        body.push_parsed(format!(
+                            "{}::de::skip_bytes(decoder, __len)?;",
+                            crate_name
+                        ))?;
+                    } else {
+                        // There is no length, so there is nothing that can be skipped
+                        body.push_parsed("let _ = variant;")?;
+                    }
+                    body.push_parsed(format!("Ok(Self::{})", variant.name))?;
+                }
+            }
+            Ok(())
+        })?;
+        Ok(())
+    }
 
-        let tokens = vec![TokenTree::Literal(Literal::u32_suffixed(idx as u32))];
+    pub fn generate_schema(self, generator: &mut Generator) -> Result<()> {
+        self.check_attributes()?;
+        if self.attributes.length_prefixed {
+            return Err(Error::custom(
+                "The Schema derive does not support `#[bincode(length_prefixed)]` enums",
+            ));
+        }
+        if let Some(variant) = self.other_variant()? {
+            return Err(Error::custom_at(
+                "The Schema derive does not support `#[bincode(other)]` variants",
+                variant.name.span(),
+            ));
+        }
+        let crate_name = &self.attributes.crate_name;
+        let enum_name = generator.target_name().to_string();
 
-        Some((tokens, variant))
+        generator
+            .impl_for(format!("{}::schema::Schema", crate_name))
+            .modify_generic_constraints(|generics, where_constraints| {
+                for g in generics.iter_generics() {
+                    where_constraints
+                        .push_constraint(g, format!("{}::schema::Schema", crate_name))?;
+                }
+                Ok(())
+            })?
+            .generate_const("SCHEMA", format!("{}::schema::Type", crate_name))
+            .with_value(|value| {
+                // bincode::schema::Type::Enum {
+                //     name: "<enum_name>",
+                //     type_name: core::any::type_name::<Self>,
+                //     tag_type: Some(bincode::config::EnumTagType::U8), // or None without #[bincode(tag_type)]
+                //     variants: &[
+                //         bincode::schema::Variant { name: "<variant>", index: <idx>, fields: &[ ... ] },
+                //         ...
+                //     ],
+                // }
+                value.push_parsed(format!("{}::schema::Type::Enum", crate_name))?;
+                value.group(Delimiter::Brace, |enum_body| {
+                    enum_body.ident_str("name");
+                    enum_body.punct(':');
+                    enum_body.lit_str(enum_name);
+                    enum_body.punct(',');
+                    enum_body.ident_str("type_name");
+                    enum_body.punct(':');
+                    enum_body.push_parsed("core::any::type_name::<Self>,")?;
+                    enum_body.ident_str("tag_type");
+                    enum_body.punct(':');
+                    match &self.attributes.tag_type {
+                        Some((ty, _)) => enum_body.push_parsed(format!(
+                            "core::option::Option::Some({}::config::EnumTagType::{}),",
+                            crate_name,
+                            ty.to_uppercase()
+                        ))?,
+                        None => enum_body.push_parsed("core::option::Option::None,")?,
+                    };
+                    enum_body.ident_str("variants");
+                    enum_body.punct(':');
+                    enum_body.punct('&');
+                    enum_body.group(Delimiter::Bracket, |variants| {
+                        for (variant_index, variant) in self.iter_fields()? {
+                            variants.push_parsed(format!("{}::schema::Variant", crate_name))?;
+                            variants.group(Delimiter::Brace, |variant_body| {
+                                variant_body.ident_str("name");
+                                variant_body.punct(':');
+                                variant_body.lit_str(variant.name.to_string());
+                                variant_body.punct(',');
+                                variant_body.ident_str("index");
+                                variant_body.punct(':');
+                                variant_body
+                                    .push(TokenTree::Literal(Literal::u32_suffixed(variant_index)));
+                                variant_body.punct(',');
+                                variant_body.ident_str("fields");
+                                variant_body.punct(':');
+                                generate_schema_fields(
+                                    crate_name,
+                                    variant.fields.as_ref(),
+                                    variant_body,
+                                )
+                            })?;
+                            variants.punct(',');
+                        }
+                        Ok(())
+                    })?;
+                    Ok(())
+                })?;
+                Ok(())
+            })?;
+        Ok(())
     }
 }
@@ -1,6 +1,6 @@
 use crate::attribute::{ContainerAttributes, FieldAttributes};
 use virtue::generate::Generator;
-use virtue::parse::Fields;
+use virtue::parse::{Fields, IdentOrIndex};
 use virtue::prelude::*;
 
 pub(crate) struct DeriveStruct {
@@ -9,10 +9,27 @@ pub(crate) struct DeriveStruct {
 }
 
 impl DeriveStruct {
+    /// Returns an error if an attribute is used that is only supported on enums
+    fn check_attributes(&self) -> Result {
+        if let Some((_, lit)) = &self.attributes.tag_type {
+            return Err(Error::custom_at(
+                "`tag_type` is only supported on enums",
+                lit.span(),
+            ));
+        }
+        if self.attributes.length_prefixed {
+            return Err(Error::custom(
+                "`length_prefixed` is only supported on enums",
+            ));
+        }
+        Ok(())
+    }
+
     pub fn generate_encode(self, generator: &mut Generator) -> Result<()> {
+        self.check_attributes()?;
         let crate_name = &self.attributes.crate_name;
         generator
-            .impl_for(&format!("{}::Encode", crate_name))
+            .impl_for(format!("{}::Encode", crate_name))
             .modify_generic_constraints(|generics, where_constraints| {
                 if let Some((bounds, lit)) =
                     (self.attributes.encode_bounds.as_ref()).or(self.attributes.bounds.as_ref())
@@ -39,24 +56,15 @@ impl DeriveStruct {
                 crate_name
             ))
             .body(|fn_body| {
-                if let Some(fields) = self.fields.as_ref() {
-                    for field in fields.names() {
-                        let attributes = field
-                            .attributes()
-                            .get_attribute::<FieldAttributes>()?
-                            .unwrap_or_default();
-                        if attributes.with_serde {
-                            fn_body.push_parsed(format!(
-                                "{0}::Encode::encode(&{0}::serde::Compat(&self.{1}), encoder)?;",
-                                crate_name, field
-                            ))?;
-                        } else {
-                            fn_body.push_parsed(format!(
-                                "{}::Encode::encode(&self.{}, encoder)?;",
-                                crate_name, field
-                            ))?;
-                        }
-                    }
+                if let Some(version) = self.version()? {
+                    return self.generate_versioned_encode(version, fn_body);
+                }
+                for (field, attributes) in self.fields_with_attributes()? {
+                    fn_body.push_parsed(attributes.encode(
+                        crate_name,
+                        &format!("&self.{}", field),
+                        "encoder",
+                    ))?;
                 }
                 fn_body.push_parsed("Ok(())")?;
                 Ok(())
@@ -65,84 +73,75 @@ impl DeriveStruct {
     }
 
     pub fn generate_decode(self, generator: &mut Generator) -> Result<()> {
-        // Remember to keep this mostly in sync with generate_borrow_decode
+        self.check_attributes()?;
         let crate_name = &self.attributes.crate_name;
 
-        generator
-            .impl_for(format!("{}::Decode", crate_name))
-            .modify_generic_constraints(|generics, where_constraints| {
-                if let Some((bounds, lit)) = (self.attributes.decode_bounds.as_ref()).or(self.attributes.bounds.as_ref()) {
-                    where_constraints.clear();
-                    where_constraints.push_parsed_constraint(bounds).map_err(|e| e.with_span(lit.span()))?;
-                } else {
-                    for g in generics.iter_generics() {
-                        where_constraints.push_constraint(g, format!("{}::Decode", crate_name)).unwrap();
-                    }
-                }
-                Ok(())
-            })?
-            .generate_fn("decode")
-            .with_generic_deps("__D", [format!("{}::de::Decoder", crate_name)])
-            .with_arg("decoder", "&mut __D")
-            .with_return_type(format!("core::result::Result<Self, {}::error::DecodeError>", crate_name))
-            .body(|fn_body| {
-                // Ok(Self {
-                fn_body.ident_str("Ok");
-                fn_body.group(Delimiter::Parenthesis, |ok_group| {
-                    ok_group.ident_str("Self");
-                    ok_group.group(Delimiter::Brace, |struct_body| {
-                        // Fields
-                        // {
-                        //      a: bincode::Decode::decode(decoder)?,
-                        //      b: bincode::Decode::decode(decoder)?,
-                        //      ...
-                        // }
-                        if let Some(fields) = self.fields.as_ref() {
-                            for field in fields.names() {
-                                let attributes = field.attributes().get_attribute::<FieldAttributes>()?.unwrap_or_default();
-                                if attributes.with_serde {
-                                    struct_body
-                                        .push_parsed(format!(
-                                            "{1}: (<{0}::serde::Compat<_> as {0}::Decode>::decode(decoder)?).0,",
-                                            crate_name,
-                                            field
-                                        ))?;
-                                } else {
-                                    struct_body
-                                        .push_parsed(format!(
-                                            "{1}: {0}::Decode::decode(decoder)?,",
-                                            crate_name,
-                                            field
-                                        ))?;
-                                }
-                            }
+        {
+            let mut impl_for = generator.impl_for(format!("{}::Decode", crate_name));
+            impl_for
+                .modify_generic_constraints(|generics, where_constraints| {
+                    if let Some((bounds, lit)) =
+                        (self.attributes.decode_bounds.as_ref()).or(self.attributes.bounds.as_ref())
+                    {
+                        where_constraints.clear();
+                        where_constraints
+                            .push_parsed_constraint(bounds)
+                            .map_err(|e| e.with_span(lit.span()))?;
+                    } else {
+                        for g in generics.iter_generics() {
+                            where_constraints
+                                .push_constraint(g, format!("{}::Decode", crate_name))
+                                .unwrap();
                         }
-                        Ok(())
-                    })?;
+                    }
                     Ok(())
-                })?;
-                Ok(())
-            })?;
+                })?
+                .generate_fn("decode")
+                .with_generic_deps("__D", [format!("{}::de::Decoder", crate_name)])
+                .with_arg("decoder", "&mut __D")
+                .with_return_type(format!(
+                    "core::result::Result<Self, {}::error::DecodeError>",
+                    crate_name
+                ))
+                .body(|fn_body| self.generate_decode_body(fn_body, false))?;
+            impl_for
+                .generate_fn("skip")
+                .with_generic_deps("__D", [format!("{}::de::Decoder", crate_name)])
+                .with_arg("decoder", "&mut __D")
+                .with_return_type(format!(
+                    "core::result::Result<(), {}::error::DecodeError>",
+                    crate_name
+                ))
+                .body(|fn_body| self.generate_skip_body(fn_body, false))?;
+        }
         self.generate_borrow_decode(generator)?;
         Ok(())
     }
 
     pub fn generate_borrow_decode(self, generator: &mut Generator) -> Result<()> {
-        // Remember to keep this mostly in sync with generate_decode
-        let crate_name = self.attributes.crate_name;
+        self.check_attributes()?;
+        let crate_name = &self.attributes.crate_name;
 
-        generator
-            .impl_for_with_lifetimes(format!("{}::BorrowDecode", crate_name), ["__de"])
+        let mut impl_for =
+            generator.impl_for_with_lifetimes(format!("{}::BorrowDecode", crate_name), ["__de"]);
+        impl_for
             .modify_generic_constraints(|generics, where_constraints| {
-                if let Some((bounds, lit)) = (self.attributes.borrow_decode_bounds.as_ref()).or(self.attributes.bounds.as_ref()) {
+                if let Some((bounds, lit)) = (self.attributes.borrow_decode_bounds.as_ref())
+                    .or(self.attributes.bounds.as_ref())
+                {
                     where_constraints.clear();
-                    where_constraints.push_parsed_constraint(bounds).map_err(|e| e.with_span(lit.span()))?;
+                    where_constraints
+                        .push_parsed_constraint(bounds)
+                        .map_err(|e| e.with_span(lit.span()))?;
                 } else {
                     for g in generics.iter_generics() {
-                        where_constraints.push_constraint(g, format!("{}::de::BorrowDecode<'__de>", crate_name)).unwrap();
+                        where_constraints
+                            .push_constraint(g, format!("{}::de::BorrowDecode<'__de>", crate_name))
+                            .unwrap();
                     }
                     for lt in generics.iter_lifetimes() {
-                        where_constraints.push_parsed_constraint(format!("'__de: '{}", lt.ident))?;
+                        where_constraints
+                            .push_parsed_constraint(format!("'__de: '{}", lt.ident))?;
                     }
                 }
                 Ok(())
@@ -150,39 +149,470 @@ impl DeriveStruct {
             .generate_fn("borrow_decode")
             .with_generic_deps("__D", [format!("{}::de::BorrowDecoder<'__de>", crate_name)])
             .with_arg("decoder", "&mut __D")
-            .with_return_type(format!("core::result::Result<Self, {}::error::DecodeError>", crate_name))
-            .body(|fn_body| {
-                // Ok(Self {
-                fn_body.ident_str("Ok");
-                fn_body.group(Delimiter::Parenthesis, |ok_group| {
-                    ok_group.ident_str("Self");
-                    ok_group.group(Delimiter::Brace, |struct_body| {
-                        if let Some(fields) = self.fields.as_ref() {
-                            for field in fields.names() {
-                                let attributes = field.attributes().get_attribute::<FieldAttributes>()?.unwrap_or_default();
-                                if attributes.with_serde {
-                                    struct_body
-                                        .push_parsed(format!(
-                                            "{1}: (<{0}::serde::BorrowCompat<_> as {0}::BorrowDecode>::borrow_decode(decoder)?).0,",
-                                            crate_name,
-                                            field
-                                        ))?;
-                                } else {
-                                    struct_body
-                                        .push_parsed(format!(
-                                            "{1}: {0}::BorrowDecode::borrow_decode(decoder)?,",
-                                            crate_name,
-                                            field
-                                        ))?;
-                                }
-                            }
-                        }
-                        Ok(())
-                    })?;
+            .with_return_type(format!(
+                "core::result::Result<Self, {}::error::DecodeError>",
+                crate_name
+            ))
+            .body(|fn_body| self.generate_decode_body(fn_body, true))?;
+        impl_for
+            .generate_fn("borrow_skip")
+            .with_generic_deps("__D", [format!("{}::de::BorrowDecoder<'__de>", crate_name)])
+            .with_arg("decoder", "&mut __D")
+            .with_return_type(format!(
+                "core::result::Result<(), {}::error::DecodeError>",
+                crate_name
+            ))
+            .body(|fn_body| self.generate_skip_body(fn_body, true))?;
+        Ok(())
+    }
+
+    pub fn generate_schema(self, generator: &mut Generator) -> Result<()> {
+        self.check_attributes()?;
+        let crate_name = &self.attributes.crate_name;
+        if self.attributes.versioned {
+            return Err(Error::custom(
+                "Schema can not be derived for a `#[bincode(versioned)]` struct",
+            ));
+        }
+        let struct_name = generator.target_name().to_string();
+
+        generator
+            .impl_for(format!("{}::schema::Schema", crate_name))
+            .modify_generic_constraints(|generics, where_constraints| {
+                for g in generics.iter_generics() {
+                    where_constraints
+                        .push_constraint(g, format!("{}::schema::Schema", crate_name))?;
+                }
+                Ok(())
+            })?
+            .generate_const("SCHEMA", format!("{}::schema::Type", crate_name))
+            .with_value(|value| {
+                // bincode::schema::Type::Struct {
+                //     name: "<struct_name>",
+                //     type_name: core::any::type_name::<Self>,
+                //     fields: &[ ... ],
+                // }
+                value.push_parsed(format!("{}::schema::Type::Struct", crate_name))?;
+                value.group(Delimiter::Brace, |struct_body| {
+                    struct_body.ident_str("name");
+                    struct_body.punct(':');
+                    struct_body.lit_str(struct_name);
+                    struct_body.punct(',');
+                    struct_body.ident_str("type_name");
+                    struct_body.punct(':');
+                    struct_body.push_parsed("core::any::type_name::<Self>,")?;
+                    struct_body.ident_str("fields");
+                    struct_body.punct(':');
+                    generate_schema_fields(crate_name, self.fields.as_ref(), struct_body)
+                })?;
+                Ok(())
+            })?;
+        Ok(())
+    }
+
+    /// Returns the fields of this struct together with their `#[bincode(..)]` attributes
+    fn fields_with_attributes(&self) -> Result<Vec<(IdentOrIndex, FieldAttributes)>> {
+        let mut result = Vec::new();
+        if let Some(fields) = self.fields.as_ref() {
+            for field in fields.names() {
+                let attributes = field
+                    .attributes()
+                    .get_attribute::<FieldAttributes>()?
+                    .unwrap_or_default();
+                result.push((field, attributes));
+            }
+        }
+        Ok(result)
+    }
+
+    /// Returns the current version of a `#[bincode(versioned)]` struct, which is the highest `#[bincode(version = N)]` of its fields. Returns `None` if the struct is not versioned.
+    fn version(&self) -> Result<Option<u32>> {
+        let mut current = 0;
+        for (field, attributes) in self.fields_with_attributes()? {
+            if attributes.skip {
+                // Skipped fields are never encoded, so they don't belong to any version
+                continue;
+            }
+            let (version, span) = match &attributes.version {
+                Some((version, lit)) => (*version, lit.span()),
+                None => (0, field_span(&field)),
+            };
+            if attributes.version.is_some() && !self.attributes.versioned {
+                return Err(Error::custom_at(
+                    "`version` can only be used in a struct with `#[bincode(versioned)]`",
+                    span,
+                ));
+            }
+            if version < current {
+                return Err(Error::custom_at(
+                    "Fields should be ordered by their version",
+                    span,
+                ));
+            }
+            current = version;
+        }
+        Ok(if self.attributes.versioned {
+            Some(current)
+        } else {
+            None
+        })
+    }
+
+    fn generate_versioned_encode(&self, version: u32, fn_body: &mut StreamBuilder) -> Result {
+        let crate_name = &self.attributes.crate_name;
+        let fields = self.fields_with_attributes()?;
+        // bincode::Encode::encode(&<version>u32, encoder)?;
+        fn_body.push_parsed(format!(
+            "{}::Encode::encode(&{}u32, encoder)?;",
+            crate_name, version
+        ))?;
+        for (field, attributes) in fields.iter().filter(|(_, a)| a.version.is_none()) {
+            fn_body.push_parsed(attributes.encode(
+                crate_name,
+                &format!("&self.{}", field),
+                "encoder",
+            ))?;
+        }
+        // Every version after the first one is prefixed with its length, so older versions of this struct can skip it
+        for group in 1..=version {
+            let group_fields: Vec<_> = fields
+                .iter()
+                .filter(|(_, a)| matches!(a.version, Some((v, _)) if v == group))
+                .collect();
+            if group_fields.is_empty() {
+                fn_body.push_parsed(format!(
+                    "{}::Encode::encode(&0usize, encoder)?;",
+                    crate_name
+                ))?;
+                continue;
+            }
+            // bincode::enc::encode_length_prefixed(encoder, |encoder| {
+            //     bincode::Encode::encode(&self.field, encoder)?;
+            //     Ok(())
+            // })?;
+            fn_body.push_parsed(format!("{}::enc::encode_length_prefixed", crate_name))?;
+            fn_body.group(Delimiter::Parenthesis, |args| {
+                args.push_parsed("encoder, |encoder|")?;
+                args.group(Delimiter::Brace, |group_body| {
+                    for (field, attributes) in &group_fields {
+                        group_body.push_parsed(attributes.encode(
+                            crate_name,
+                            &format!("&self.{}", field),
+                            "encoder",
+                        ))?;
+                    }
+                    group_body.push_parsed("Ok(())")?;
                     Ok(())
                 })?;
                 Ok(())
             })?;
+            fn_body.push_parsed("?;")?;
+        }
+        fn_body.push_parsed("Ok(())")?;
+        Ok(())
+    }
+
+    fn generate_decode_body(&self, fn_body: &mut StreamBuilder, borrow: bool) -> Result {
+        let crate_name = &self.attributes.crate_name;
+        // bincode::de::Decoder::increase_depth(decoder)?;
+        fn_body.push_parsed(format!(
+            "{}::de::Decoder::increase_depth(decoder)?;",
+            crate_name
+        ))?;
+        if let Some(version) = self.version()? {
+            self.generate_versioned_decode(version, fn_body, borrow)?;
+        } else {
+            // let __result = Self {
+            //      a: bincode::Decode::decode(decoder)?,
+            //      b: bincode::Decode::decode(decoder)?,
+            //      ...
+            // };
+            fn_body.push_parsed("let __result = Self")?;
+            fn_body.group(Delimiter::Brace, |struct_body| {
+                for (field, attributes) in self.fields_with_attributes()? {
+                    struct_body.push_parsed(format!(
+                        "{}: {},",
+                        field,
+                        attributes.decode(crate_name, borrow, &field_context(&field))
+                    ))?;
+                }
+                Ok(())
+            })?;
+            fn_body.punct(';');
+        }
+        // bincode::de::Decoder::decrease_depth(decoder);
+        // Ok(__result)
+        fn_body.push_parsed(format!(
+            "{}::de::Decoder::decrease_depth(decoder);",
+            crate_name
+        ))?;
+        fn_body.push_parsed("Ok(__result)")?;
+        Ok(())
+    }
+
+    fn generate_skip_body(&self, fn_body: &mut StreamBuilder, borrow: bool) -> Result {
+        let crate_name = &self.attributes.crate_name;
+        let fields = self.fields_with_attributes()?;
+        let types = field_types(self.fields.as_ref(), borrow);
+        fn_body.push_parsed(format!(
+            "{}::de::Decoder::increase_depth(decoder)?;",
+            crate_name
+        ))?;
+        if self.version()?.is_some() {
+            // Only the fields of version 0 have to be skipped one by one, every version after that starts with its length
+            // let __version: u32 = bincode::Decode::decode(decoder)?;
+            // <u32 as bincode::Decode>::skip(decoder)?;
+            // for _ in 0..__version {
+            //     let __len: usize = bincode::Decode::decode(decoder)?;
+            //     bincode::de::skip_bytes(decoder, __len)?;
+            // }
+            fn_body.push_parsed(format!(
+                "let __version: u32 = {}::Decode::decode(decoder)?;",
+                crate_name
+            ))?;
+            for ((_, attributes), ty) in fields.iter().zip(&types) {
+                if attributes.version.is_none() {
+                    fn_body.push_parsed(attributes.skip_decode(crate_name, ty, borrow))?;
+                }
+            }
+            fn_body.push_parsed(format!(
+                "for _ in 0..__version {{ let __len: usize = {0}::Decode::decode(decoder)?; {0}::de::skip_bytes(decoder, __len)?; }}",
+                crate_name
+            ))?;
+        } else {
+            // <u32 as bincode::Decode>::skip(decoder)?;
+            // <alloc::string::String as bincode::Decode>::skip(decoder)?;
+            for ((_, attributes), ty) in fields.iter().zip(&types) {
+                fn_body.push_parsed(attributes.skip_decode(crate_name, ty, borrow))?;
+            }
+        }
+        fn_body.push_parsed(format!(
+            "{}::de::Decoder::decrease_depth(decoder);",
+            crate_name
+        ))?;
+        fn_body.push_parsed("Ok(())")?;
         Ok(())
     }
+
+    fn generate_versioned_decode(
+        &self,
+        version: u32,
+        fn_body: &mut StreamBuilder,
+        borrow: bool,
+    ) -> Result {
+        let crate_name = &self.attributes.crate_name;
+        let fields = self.fields_with_attributes()?;
+        // let __version: u32 = bincode::Decode::decode(decoder)?;
+        fn_body.push_parsed(format!(
+            "let __version: u32 = {}::Decode::decode(decoder)?;",
+            crate_name
+        ))?;
+        for (field, attributes) in fields.iter().filter(|(_, a)| a.version.is_none()) {
+            fn_body.push_parsed(format!(
+                "let {} = {};",
+                binding_name(field),
+                attributes.decode(crate_name, borrow, &field_context(field))
+            ))?;
+        }
+        for group in 1..=version {
+            let group_fields: Vec<_> = fields
+                .iter()
+                .filter(|(_, a)| matches!(a.version, Some((v, _)) if v == group))
+                .collect();
+            if group_fields.is_empty() {
+                // if __version >= <group>u32 {
+                //     let __len: usize = bincode::Decode::decode(decoder)?;
+                //     bincode::de::skip_bytes(decoder, __len)?;
+                // }
+                fn_body.push_parsed(format!("if __version >= {}u32", group))?;
+                fn_body.group(Delimiter::Brace, |skip_body| {
+                    skip_body.push_parsed(format!(
+                        "let __len: usize = {}::Decode::decode(decoder)?;",
+                        crate_name
+                    ))?;
+                    skip_body
+                        .push_parsed(format!("{}::de::skip_bytes(decoder, __len)?;", crate_name))?;
+                    Ok(())
+                })?;
+                continue;
+            }
+            // let (__field_a,) = if __version >= <group>u32 {
+            //     let __len: usize = bincode::Decode::decode(decoder)?;
+            //     bincode::de::Decoder::decode_length_prefixed(decoder, __len, |decoder| {
+            //         Ok((bincode::Decode::decode(decoder)?,))
+            //     })?
+            // } else {
+            //     (core::default::Default::default(),)
+            // };
+            let bindings: String = group_fields
+                .iter()
+                .map(|(field, _)| format!("{},", binding_name(field)))
+                .collect();
+            let values: String = group_fields
+                .iter()
+                .map(|(field, attributes)| {
+                    format!(
+                        "{},",
+                        attributes.decode(crate_name, borrow, &field_context(field))
+                    )
+                })
+                .collect();
+            let defaults: String = group_fields
+                .iter()
+                .map(|(_, attributes)| match &attributes.default {
+                    Some((path, _)) => format!("{}(),", path),
+                    None => "core::default::Default::default(),".to_string(),
+                })
+                .collect();
+            let decode_length_prefixed = if borrow {
+                "BorrowDecoder::borrow_decode_length_prefixed"
+            } else {
+                "Decoder::decode_length_prefixed"
+            };
+            // The bytes of fields that a newer version of this struct added to this version are skipped
+            fn_body.push_parsed(format!(
+                "let ({1}) = if __version >= {2}u32 {{ let __len: usize = {0}::Decode::decode(decoder)?; {0}::de::{3}(decoder, __len, |decoder| Ok(({4})))? }} else {{ ({5}) }};",
+                crate_name, bindings, group, decode_length_prefixed, values, defaults
+            ))?;
+        }
+        // Skip the fields of versions that are newer than this struct
+        // for _ in <version>u32..__version {
+        //     let __len: usize = bincode::Decode::decode(decoder)?;
+        //     bincode::de::skip_bytes(decoder, __len)?;
+        // }
+        fn_body.push_parsed(format!("for _ in {}u32..__version", version))?;
+        fn_body.group(Delimiter::Brace, |skip_body| {
+            skip_body.push_parsed(format!(
+                "let __len: usize = {}::Decode::decode(decoder)?;",
+                crate_name
+            ))?;
+            skip_body.push_parsed(format!("{}::de::skip_bytes(decoder, __len)?;", crate_name))?;
+            Ok(())
+        })?;
+        // let __result = Self { a: __field_a, .. };
+        fn_body.push_parsed("let __result = Self")?;
+        fn_body.group(Delimiter::Brace, |struct_body| {
+            for (field, _) in &fields {
+                struct_body.push_parsed(format!("{}: {},", field, binding_name(field)))?;
+            }
+            Ok(())
+        })?;
+        fn_body.punct(';');
+        Ok(())
+    }
+}
+
+/// The `PathSegment` of `field` that is added to the path of an error, see `FieldAttributes::decode`
+fn field_context(field: &IdentOrIndex) -> String {
+    format!(
+        "field::<Self>(\"{}\")",
+        field.to_string().trim_start_matches("r#")
+    )
+}
+
+/// The name of the local variable that a decoded field is stored in
+fn binding_name(field: &IdentOrIndex) -> String {
+    format!("__field_{}", field.to_string().trim_start_matches("r#"))
+}
+
+fn field_span(field: &IdentOrIndex) -> Span {
+    match field {
+        IdentOrIndex::Ident { ident, .. } => ident.span(),
+        IdentOrIndex::Index { span, .. } => *span,
+    }
+}
+
+/// Returns the types of the given fields, in the same order as `Fields::names`.
+///
+/// If `borrow` is set, every lifetime except `'static` is replaced with `'__de`, the lifetime of the generated `BorrowDecode` implementation. The fields are decoded as `'__de` and then shortened to their own lifetime, so their type has to be named with `'__de` as well.
+pub(crate) fn field_types(fields: Option<&Fields>, borrow: bool) -> Vec<String> {
+    let types: Vec<&UnnamedField> = match fields {
+        Some(Fields::Tuple(fields)) => fields.iter().collect(),
+        Some(Fields::Struct(fields)) => fields.iter().map(|(_, field)| field).collect(),
+        None => Vec::new(),
+    };
+    types
+        .into_iter()
+        .map(|field| {
+            let mut tokens = field.r#type.clone();
+            if borrow {
+                replace_lifetimes(&mut tokens);
+            }
+            tokens.into_iter().collect::<TokenStream>().to_string()
+        })
+        .collect()
+}
+
+fn replace_lifetimes(tokens: &mut [TokenTree]) {
+    for idx in 0..tokens.len() {
+        let is_lifetime =
+            idx > 0 && matches!(&tokens[idx - 1], TokenTree::Punct(p) if p.as_char() == '\'');
+        match &mut tokens[idx] {
+            TokenTree::Ident(ident) if is_lifetime && ident.to_string() != "static" => {
+                *ident = Ident::new("__de", ident.span());
+            }
+            TokenTree::Group(group) => {
+                let mut inner: Vec<TokenTree> = group.stream().into_iter().collect();
+                replace_lifetimes(&mut inner);
+                let mut replaced = Group::new(
+                    group.delimiter(),
+                    inner.into_iter().collect::<TokenStream>(),
+                );
+                replaced.set_span(group.span());
+                *group = replaced;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Generates the `&[bincode::schema::Field { .. }, ..]` slice that describes the given fields
+pub(crate) fn generate_schema_fields(
+    crate_name: &str,
+    fields: Option<&Fields>,
+    builder: &mut StreamBuilder,
+) -> Result {
+    builder.punct('&');
+    builder.group(Delimiter::Bracket, |fields_body| {
+        let fields = match fields {
+            Some(Fields::Tuple(fields)) => fields.iter().map(|field| (None, field)).collect(),
+            Some(Fields::Struct(fields)) => fields
+                .iter()
+                .map(|(ident, field)| (Some(ident), field))
+                .collect(),
+            None => Vec::new(),
+        };
+        for (ident, field) in fields {
+            let attributes = field
+                .attributes
+                .get_attribute::<FieldAttributes>()?
+                .unwrap_or_default();
+            if attributes.skip {
+                continue;
+            }
+            let name = match ident {
+                Some(ident) => format!("core::option::Option::Some(\"{}\")", ident),
+                None => "core::option::Option::None".to_string(),
+            };
+            // bincode::schema::Field { name: .., schema: bincode::schema::schema_of::<field_type> },
+            fields_body.push_parsed(format!("{}::schema::Field", crate_name))?;
+            fields_body.group(Delimiter::Brace, |field_body| {
+                field_body.push_parsed(format!("name: {},", name))?;
+                field_body.ident_str("schema");
+                field_body.punct(':');
+                if attributes.is_opaque() {
+                    // The layout of a serde type or a custom encoding is not known to bincode
+                    field_body.push_parsed(format!("|| {}::schema::Type::Opaque", crate_name))?;
+                } else {
+                    field_body.push_parsed(format!("{}::schema::schema_of::<", crate_name))?;
+                    field_body.extend(field.r#type.clone());
+                    field_body.punct('>');
+                }
+                Ok(())
+            })?;
+            fields_body.punct(',');
+        }
+        Ok(())
+    })?;
+    Ok(())
 }
@@ -103,3 +103,36 @@ fn derive_borrow_decode_inner(input: TokenStream) -> Result<TokenStream> {
     generator.export_to_file("bincode", "BorrowDecode");
     generator.finish()
 }
+
+#[proc_macro_derive(Schema, attributes(bincode))]
+pub fn derive_schema(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    derive_schema_inner(input).unwrap_or_else(|e| e.into_token_stream())
+}
+
+fn derive_schema_inner(input: TokenStream) -> Result<TokenStream> {
+    let parse = Parse::new(input)?;
+    let (mut generator, attributes, body) = parse.into_generator();
+    let attributes = attributes
+        .get_attribute::<ContainerAttributes>()?
+        .unwrap_or_default();
+
+    match body {
+        Body::Struct(body) => {
+            derive_struct::DeriveStruct {
+                fields: body.fields,
+                attributes,
+            }
+            .generate_schema(&mut generator)?;
+        }
+        Body::Enum(body) => {
+            derive_enum::DeriveEnum {
+                variants: body.variants,
+                attributes,
+            }
+            .generate_schema(&mut generator)?;
+        }
+    }
+
+    generator.export_to_file("bincode", "Schema");
+    generator.finish()
+}
@@ -37,11 +37,16 @@ enum AllTypes {
     NonZeroI32(NonZeroI32),
     NonZeroU128(NonZeroU128),
     NonZeroI128(NonZeroI128),
+    U16(u16),
+    I32(i32),
+    U64(u64),
+    I64(i64),
+    U128(u128),
+    I128(i128),
     // Cow(Cow<'static, [u8]>), Blocked, see comment on decode
 }
 
-fuzz_target!(|data: &[u8]| {
-    let config = bincode::config::standard().with_limit::<1024>();
+fn roundtrip<C: bincode::config::Config>(data: &[u8], config: C) {
     let result: Result<(AllTypes, _), _> = bincode::decode_from_slice(data, config);
 
     if let Ok((before, _)) = result {
@@ -49,4 +54,14 @@ fuzz_target!(|data: &[u8]| {
         let (after, _) = bincode::decode_from_slice(&encoded, config).unwrap();
         assert_eq!(before, after);
     }
+}
+
+fuzz_target!(|data: &[u8]| {
+    roundtrip(data, bincode::config::standard().with_limit::<1024>());
+    roundtrip(
+        data,
+        bincode::config::standard()
+            .with_leb128_int_encoding()
+            .with_limit::<1024>(),
+    );
 });
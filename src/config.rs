@@ -11,6 +11,7 @@
 //!     .with_little_endian()
 //!     // pick one of:
 //!     .with_variable_int_encoding()
+//!     .with_leb128_int_encoding()
 //!     .with_fixed_int_encoding();
 //! ```
 //!
@@ -25,22 +26,57 @@ use core::marker::PhantomData;
 /// The following methods are mutually exclusive and will overwrite each other. The last call to one of these methods determines the behavior of the configuration:
 ///
 /// - [with_little_endian] and [with_big_endian]
-/// - [with_fixed_int_encoding] and [with_variable_int_encoding]
-///
+/// - [with_fixed_int_encoding], [with_variable_int_encoding] and [with_leb128_int_encoding]
+/// - [with_limit], [with_runtime_limit] and [with_no_limit]
+/// - [with_max_depth] and [with_no_max_depth]
+/// - [with_reject_trailing_bytes] and [with_allow_trailing_bytes]
+/// - [with_u8_enum_tags], [with_u16_enum_tags] and [with_u32_enum_tags]
+/// - [with_canonical_encoding] and [with_non_canonical_encoding]
+/// - [with_strict_decoding] and [with_lenient_decoding]
 ///
 /// [with_little_endian]: #method.with_little_endian
 /// [with_big_endian]: #method.with_big_endian
 /// [with_fixed_int_encoding]: #method.with_fixed_int_encoding
 /// [with_variable_int_encoding]: #method.with_variable_int_encoding
+/// [with_leb128_int_encoding]: #method.with_leb128_int_encoding
+/// [with_limit]: #method.with_limit
+/// [with_runtime_limit]: #method.with_runtime_limit
+/// [with_no_limit]: #method.with_no_limit
+/// [with_max_depth]: #method.with_max_depth
+/// [with_no_max_depth]: #method.with_no_max_depth
+/// [with_reject_trailing_bytes]: #method.with_reject_trailing_bytes
+/// [with_allow_trailing_bytes]: #method.with_allow_trailing_bytes
+/// [with_u8_enum_tags]: #method.with_u8_enum_tags
+/// [with_u16_enum_tags]: #method.with_u16_enum_tags
+/// [with_u32_enum_tags]: #method.with_u32_enum_tags
+/// [with_canonical_encoding]: #method.with_canonical_encoding
+/// [with_non_canonical_encoding]: #method.with_non_canonical_encoding
+/// [with_strict_decoding]: #method.with_strict_decoding
+/// [with_lenient_decoding]: #method.with_lenient_decoding
 #[derive(Copy, Clone)]
-pub struct Configuration<E = LittleEndian, I = Varint, L = NoLimit> {
+pub struct Configuration<
+    E = LittleEndian,
+    I = Varint,
+    L = NoLimit,
+    D = NoMaxDepth,
+    T = AllowTrailingBytes,
+    V = U32EnumTag,
+    K = NonCanonical,
+    S = LenientDecoding,
+> {
     _e: PhantomData<E>,
     _i: PhantomData<I>,
-    _l: PhantomData<L>,
+    limit: L,
+    _d: PhantomData<D>,
+    _t: PhantomData<T>,
+    _v: PhantomData<V>,
+    _k: PhantomData<K>,
+    _s: PhantomData<S>,
 }
 
 // When adding more features to configuration, follow these steps:
 // - Create 2 or more structs that can be used as a type (e.g. Limit and NoLimit)
+//   - If the struct needs to store a runtime value (e.g. RuntimeLimit), store the struct itself in `Configuration` instead of a `PhantomData`
 // - Add an `Internal...Config` to the `internal` module
 // - Make sure `Config` and `impl<T> Config for T` extend from this new trait
 // - Add a generic to `Configuration`
@@ -53,39 +89,44 @@ pub struct Configuration<E = LittleEndian, I = Varint, L = NoLimit> {
 /// - Little endian
 /// - Variable int encoding
 pub const fn standard() -> Configuration {
-    generate()
+    generate(NoLimit {})
 }
 
 /// Creates the "legacy" default config. This is the default config that was present in bincode 1.0
 /// - Little endian
 /// - Fixed int length encoding
 pub const fn legacy() -> Configuration<LittleEndian, Fixint, NoLimit> {
-    generate()
+    generate(NoLimit {})
 }
 
-impl<E, I, L> Default for Configuration<E, I, L> {
+impl<E, I, L: Default, D, T, V, K, S> Default for Configuration<E, I, L, D, T, V, K, S> {
     fn default() -> Self {
-        generate()
+        generate(L::default())
     }
 }
 
-const fn generate<E, I, L>() -> Configuration<E, I, L> {
+const fn generate<E, I, L, D, T, V, K, S>(limit: L) -> Configuration<E, I, L, D, T, V, K, S> {
     Configuration {
         _e: PhantomData,
         _i: PhantomData,
-        _l: PhantomData,
+        limit,
+        _d: PhantomData,
+        _t: PhantomData,
+        _v: PhantomData,
+        _k: PhantomData,
+        _s: PhantomData,
     }
 }
 
-impl<E, I, L> Configuration<E, I, L> {
+impl<E, I, L: Copy, D, T, V, K, S> Configuration<E, I, L, D, T, V, K, S> {
     /// Makes bincode encode all integer types in big endian.
-    pub const fn with_big_endian(self) -> Configuration<BigEndian, I, L> {
-        generate()
+    pub const fn with_big_endian(self) -> Configuration<BigEndian, I, L, D, T, V, K, S> {
+        generate(self.limit)
     }
 
     /// Makes bincode encode all integer types in little endian.
-    pub const fn with_little_endian(self) -> Configuration<LittleEndian, I, L> {
-        generate()
+    pub const fn with_little_endian(self) -> Configuration<LittleEndian, I, L, D, T, V, K, S> {
+        generate(self.limit)
     }
 
     /// Makes bincode encode all integer types with a variable integer encoding.
@@ -97,7 +138,7 @@ impl<E, I, L> Configuration<E, I, L> {
     /// 3. If `2**16 <= u < 2**32`, encode it as a literal byte 252, followed by a u32 with value `u`.
     /// 4. If `2**32 <= u < 2**64`, encode it as a literal byte 253, followed by a u64 with value `u`.
     /// 5. If `2**64 <= u < 2**128`, encode it as a literal byte 254, followed by a
-    ///   u128 with value `u`.
+    ///    u128 with value `u`.
     ///
     /// Then, for signed integers, we first convert to unsigned using the zigzag algorithm,
     /// and then encode them as we do for unsigned integers generally. The reason we use this
@@ -145,8 +186,21 @@ impl<E, I, L> Configuration<E, I, L> {
     ///
     /// Note that u256 and the like are unsupported by this format; if and when they are added to the
     /// language, they may be supported via the extension point given by the 255 byte.
-    pub const fn with_variable_int_encoding(self) -> Configuration<E, Varint, L> {
-        generate()
+    pub const fn with_variable_int_encoding(self) -> Configuration<E, Varint, L, D, T, V, K, S> {
+        generate(self.limit)
+    }
+
+    /// Makes bincode encode all integer types with unsigned LEB128, the variable integer encoding that is used by protobuf, DWARF and WebAssembly.
+    ///
+    /// Each byte holds 7 bits of the value, starting with the least significant bits. The high bit of a byte is set if more bytes follow. Values below 128 are encoded as a single byte, and a `u64` takes at most 10 bytes. The endianness of the config is not used.
+    ///
+    /// Signed integers are converted to unsigned with the same zigzag algorithm as [with_variable_int_encoding], so `-1` is encoded as `[1]` and `1` as `[2]`. This matches the `sint32` and `sint64` types of protobuf.
+    ///
+    /// `u8`, `i8`, floats and `char` are encoded the same as with the other integer encodings.
+    ///
+    /// [with_variable_int_encoding]: #method.with_variable_int_encoding
+    pub const fn with_leb128_int_encoding(self) -> Configuration<E, Leb128, L, D, T, V, K, S> {
+        generate(self.limit)
     }
 
     /// Fixed-size integer encoding.
@@ -154,24 +208,156 @@ impl<E, I, L> Configuration<E, I, L> {
     /// * Fixed size integers are encoded directly
     /// * Enum discriminants are encoded as u32
     /// * Lengths and usize are encoded as u64
-    pub const fn with_fixed_int_encoding(self) -> Configuration<E, Fixint, L> {
-        generate()
+    pub const fn with_fixed_int_encoding(self) -> Configuration<E, Fixint, L, D, T, V, K, S> {
+        generate(self.limit)
+    }
+
+    /// Sets the byte limit to `limit`.
+    pub const fn with_limit<const N: usize>(self) -> Configuration<E, I, Limit<N>, D, T, V, K, S> {
+        generate(Limit {})
     }
 
     /// Sets the byte limit to `limit`.
-    pub const fn with_limit<const N: usize>(self) -> Configuration<E, I, Limit<N>> {
-        generate()
+    ///
+    /// Unlike [with_limit], the limit does not have to be known at compile time. This is useful when e.g. the limit is read from a configuration file.
+    ///
+    /// ```
+    /// # let max_message_size: usize = 1024;
+    /// let config = bincode::config::standard().with_runtime_limit(max_message_size);
+    /// # use bincode::config::Config;
+    /// assert_eq!(config.limit(), Some(1024));
+    /// ```
+    ///
+    /// [with_limit]: #method.with_limit
+    pub const fn with_runtime_limit(
+        self,
+        limit: usize,
+    ) -> Configuration<E, I, RuntimeLimit, D, T, V, K, S> {
+        generate(RuntimeLimit { limit })
     }
 
     /// Clear the byte limit.
-    pub const fn with_no_limit(self) -> Configuration<E, I, NoLimit> {
-        generate()
+    pub const fn with_no_limit(self) -> Configuration<E, I, NoLimit, D, T, V, K, S> {
+        generate(NoLimit {})
+    }
+
+    /// Sets the maximum nesting depth to `N`.
+    ///
+    /// Every container (e.g. `Vec`, `HashMap` and arrays), `Box`, `Rc`, `Arc` and derived struct and enum that is being decoded counts as one level of nesting. If a value is nested deeper than `N` levels, decoding fails with [DecodeError::DepthLimitExceeded] instead of overflowing the stack.
+    ///
+    /// [DecodeError::DepthLimitExceeded]: ../error/enum.DecodeError.html#variant.DepthLimitExceeded
+    pub const fn with_max_depth<const N: usize>(
+        self,
+    ) -> Configuration<E, I, L, MaxDepth<N>, T, V, K, S> {
+        generate(self.limit)
+    }
+
+    /// Clear the maximum nesting depth.
+    pub const fn with_no_max_depth(self) -> Configuration<E, I, L, NoMaxDepth, T, V, K, S> {
+        generate(self.limit)
+    }
+
+    /// Makes `decode_from_slice` and `borrow_decode_from_slice` fail with [DecodeError::TrailingBytes] if the slice contains more bytes than the decoded value.
+    ///
+    /// This does not affect functions that decode from a reader, as there is no way to know if a reader has more bytes left.
+    ///
+    /// [DecodeError::TrailingBytes]: ../error/enum.DecodeError.html#variant.TrailingBytes
+    pub const fn with_reject_trailing_bytes(
+        self,
+    ) -> Configuration<E, I, L, D, RejectTrailingBytes, V, K, S> {
+        generate(self.limit)
+    }
+
+    /// Allows the slice given to `decode_from_slice` and `borrow_decode_from_slice` to contain more bytes than the decoded value. This is the default.
+    pub const fn with_allow_trailing_bytes(
+        self,
+    ) -> Configuration<E, I, L, D, AllowTrailingBytes, V, K, S> {
+        generate(self.limit)
+    }
+
+    /// Makes derived enums encode their variant index as an `u8`. Encoding a variant with an index above `u8::MAX` fails with [EncodeError::EnumTagOverflow].
+    ///
+    /// This only affects enums that derive `Encode` and `Decode` without a `#[bincode(tag_type = "..")]` attribute. Built-in types like `Result` always use an `u32`.
+    ///
+    /// [EncodeError::EnumTagOverflow]: ../error/enum.EncodeError.html#variant.EnumTagOverflow
+    pub const fn with_u8_enum_tags(self) -> Configuration<E, I, L, D, T, U8EnumTag, K, S> {
+        generate(self.limit)
+    }
+
+    /// Makes derived enums encode their variant index as an `u16`. Encoding a variant with an index above `u16::MAX` fails with [EncodeError::EnumTagOverflow].
+    ///
+    /// This only affects enums that derive `Encode` and `Decode` without a `#[bincode(tag_type = "..")]` attribute. Built-in types like `Result` always use an `u32`.
+    ///
+    /// [EncodeError::EnumTagOverflow]: ../error/enum.EncodeError.html#variant.EnumTagOverflow
+    pub const fn with_u16_enum_tags(self) -> Configuration<E, I, L, D, T, U16EnumTag, K, S> {
+        generate(self.limit)
+    }
+
+    /// Makes derived enums encode their variant index as an `u32`. This is the default.
+    pub const fn with_u32_enum_tags(self) -> Configuration<E, I, L, D, T, U32EnumTag, K, S> {
+        generate(self.limit)
+    }
+
+    /// Makes every value have exactly one encoding, so that equal values always encode to the same bytes. This is useful when the encoded bytes are hashed or signed.
+    ///
+    /// When encoding:
+    /// - The entries of a `HashMap` and the items of a `HashSet` are sorted by the encoded bytes of their keys, instead of being written in iteration order.
+    ///
+    /// When decoding, input that the encoder would not have written is rejected:
+    /// - The keys of a `HashMap` and `HashSet` have to be in ascending order of their encoded bytes, and the keys of a `BTreeMap` and `BTreeSet` in ascending order of their `Ord` implementation. Otherwise decoding fails with [DecodeError::UnsortedKeys].
+    /// - Everything that [with_strict_decoding] rejects.
+    ///
+    /// Types with a custom `Encode` implementation are responsible for their own encoding being deterministic. The `serde` integration writes maps in iteration order regardless of this setting.
+    ///
+    /// [DecodeError::UnsortedKeys]: ../error/enum.DecodeError.html#variant.UnsortedKeys
+    /// [with_strict_decoding]: #method.with_strict_decoding
+    pub const fn with_canonical_encoding(self) -> Configuration<E, I, L, D, T, V, Canonical, S> {
+        generate(self.limit)
+    }
+
+    /// Writes maps and sets in iteration order, and accepts every encoding that decodes to a valid value. This is the default.
+    pub const fn with_non_canonical_encoding(
+        self,
+    ) -> Configuration<E, I, L, D, T, V, NonCanonical, S> {
+        generate(self.limit)
+    }
+
+    /// Rejects input that has more than one valid encoding, instead of silently picking one of the values:
+    /// - A `HashMap`, `HashSet`, `BTreeMap` or `BTreeSet` that contains the same key more than once fails with [DecodeError::DuplicateMapKey].
+    /// - A varint or LEB128 integer that does not use the least amount of bytes for its value, e.g. `5` encoded as `[251, 5, 0]`, fails with [DecodeError::NonCanonicalVarint].
+    ///
+    /// This only affects decoding. The encoder never writes these encodings.
+    ///
+    /// [DecodeError::DuplicateMapKey]: ../error/enum.DecodeError.html#variant.DuplicateMapKey
+    /// [DecodeError::NonCanonicalVarint]: ../error/enum.DecodeError.html#variant.NonCanonicalVarint
+    pub const fn with_strict_decoding(self) -> Configuration<E, I, L, D, T, V, K, StrictDecoding> {
+        generate(self.limit)
+    }
+
+    /// Accepts duplicate map keys and integers that are encoded with more bytes than needed. This is the default.
+    ///
+    /// The last value of a duplicate key is kept. Note that [with_canonical_encoding] still rejects these encodings.
+    ///
+    /// [with_canonical_encoding]: #method.with_canonical_encoding
+    pub const fn with_lenient_decoding(
+        self,
+    ) -> Configuration<E, I, L, D, T, V, K, LenientDecoding> {
+        generate(self.limit)
     }
 }
 
 /// Indicates a type is valid for controlling the bincode configuration
 pub trait Config:
-    InternalEndianConfig + InternalIntEncodingConfig + InternalLimitConfig + Copy + Clone
+    InternalEndianConfig
+    + InternalIntEncodingConfig
+    + InternalLimitConfig
+    + InternalDepthLimitConfig
+    + InternalTrailingBytesConfig
+    + InternalEnumTagConfig
+    + InternalCanonicalConfig
+    + InternalStrictConfig
+    + Copy
+    + Clone
 {
     /// This configuration's Endianness
     fn endianness(&self) -> Endianness;
@@ -181,11 +367,35 @@ pub trait Config:
 
     /// This configuration's byte limit, or `None` if no limit is configured
     fn limit(&self) -> Option<usize>;
+
+    /// This configuration's maximum nesting depth, or `None` if no maximum is configured
+    fn max_depth(&self) -> Option<usize>;
+
+    /// Whether this configuration rejects trailing bytes when decoding from a slice
+    fn reject_trailing_bytes(&self) -> bool;
+
+    /// The type that derived enums encode their variant index as
+    fn enum_tag_type(&self) -> EnumTagType;
+
+    /// Whether this configuration uses the canonical encoding
+    fn canonical(&self) -> bool;
+
+    /// Whether this configuration rejects duplicate map keys and integers that are encoded with more bytes than needed
+    fn strict_decoding(&self) -> bool;
 }
 
 impl<T> Config for T
 where
-    T: InternalEndianConfig + InternalIntEncodingConfig + InternalLimitConfig + Copy + Clone,
+    T: InternalEndianConfig
+        + InternalIntEncodingConfig
+        + InternalLimitConfig
+        + InternalDepthLimitConfig
+        + InternalTrailingBytesConfig
+        + InternalEnumTagConfig
+        + InternalCanonicalConfig
+        + InternalStrictConfig
+        + Copy
+        + Clone,
 {
     fn endianness(&self) -> Endianness {
         <T as InternalEndianConfig>::ENDIAN
@@ -196,7 +406,27 @@ where
     }
 
     fn limit(&self) -> Option<usize> {
-        <T as InternalLimitConfig>::LIMIT
+        <T as InternalLimitConfig>::byte_limit(self)
+    }
+
+    fn max_depth(&self) -> Option<usize> {
+        <T as InternalDepthLimitConfig>::MAX_DEPTH
+    }
+
+    fn reject_trailing_bytes(&self) -> bool {
+        <T as InternalTrailingBytesConfig>::REJECT_TRAILING_BYTES
+    }
+
+    fn enum_tag_type(&self) -> EnumTagType {
+        <T as InternalEnumTagConfig>::ENUM_TAG_TYPE
+    }
+
+    fn canonical(&self) -> bool {
+        <T as InternalCanonicalConfig>::CANONICAL
+    }
+
+    fn strict_decoding(&self) -> bool {
+        <T as InternalStrictConfig>::STRICT
     }
 }
 
@@ -232,22 +462,125 @@ impl InternalIntEncodingConfig for Varint {
     const INT_ENCODING: IntEncoding = IntEncoding::Variable;
 }
 
-/// Sets an unlimited byte limit.
+/// Use unsigned LEB128 integer encoding.
 #[derive(Copy, Clone)]
+pub struct Leb128 {}
+
+impl InternalIntEncodingConfig for Leb128 {
+    const INT_ENCODING: IntEncoding = IntEncoding::Leb128;
+}
+
+/// Sets an unlimited byte limit.
+#[derive(Copy, Clone, Default)]
 pub struct NoLimit {}
 impl InternalLimitConfig for NoLimit {
-    const LIMIT: Option<usize> = None;
+    #[inline]
+    fn byte_limit(&self) -> Option<usize> {
+        None
+    }
 }
 
 /// Sets the byte limit to N.
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Default)]
 pub struct Limit<const N: usize> {}
 impl<const N: usize> InternalLimitConfig for Limit<N> {
-    const LIMIT: Option<usize> = Some(N);
+    #[inline]
+    fn byte_limit(&self) -> Option<usize> {
+        Some(N)
+    }
+}
+
+/// Sets the byte limit to a value that is only known at runtime.
+#[derive(Copy, Clone)]
+pub struct RuntimeLimit {
+    limit: usize,
+}
+impl InternalLimitConfig for RuntimeLimit {
+    #[inline]
+    fn byte_limit(&self) -> Option<usize> {
+        Some(self.limit)
+    }
+}
+
+/// Sets an unlimited nesting depth.
+#[derive(Copy, Clone)]
+pub struct NoMaxDepth {}
+impl InternalDepthLimitConfig for NoMaxDepth {
+    const MAX_DEPTH: Option<usize> = None;
+}
+
+/// Sets the maximum nesting depth to N.
+#[derive(Copy, Clone)]
+pub struct MaxDepth<const N: usize> {}
+impl<const N: usize> InternalDepthLimitConfig for MaxDepth<N> {
+    const MAX_DEPTH: Option<usize> = Some(N);
+}
+
+/// Allows bytes after the decoded value.
+#[derive(Copy, Clone)]
+pub struct AllowTrailingBytes {}
+impl InternalTrailingBytesConfig for AllowTrailingBytes {
+    const REJECT_TRAILING_BYTES: bool = false;
+}
+
+/// Rejects bytes after the decoded value.
+#[derive(Copy, Clone)]
+pub struct RejectTrailingBytes {}
+impl InternalTrailingBytesConfig for RejectTrailingBytes {
+    const REJECT_TRAILING_BYTES: bool = true;
+}
+
+/// Encodes the variant index of derived enums as an `u8`.
+#[derive(Copy, Clone)]
+pub struct U8EnumTag {}
+impl InternalEnumTagConfig for U8EnumTag {
+    const ENUM_TAG_TYPE: EnumTagType = EnumTagType::U8;
+}
+
+/// Encodes the variant index of derived enums as an `u16`.
+#[derive(Copy, Clone)]
+pub struct U16EnumTag {}
+impl InternalEnumTagConfig for U16EnumTag {
+    const ENUM_TAG_TYPE: EnumTagType = EnumTagType::U16;
+}
+
+/// Encodes the variant index of derived enums as an `u32`.
+#[derive(Copy, Clone)]
+pub struct U32EnumTag {}
+impl InternalEnumTagConfig for U32EnumTag {
+    const ENUM_TAG_TYPE: EnumTagType = EnumTagType::U32;
+}
+
+/// Writes maps and sets in iteration order.
+#[derive(Copy, Clone)]
+pub struct NonCanonical {}
+impl InternalCanonicalConfig for NonCanonical {
+    const CANONICAL: bool = false;
+}
+
+/// Gives every value exactly one encoding.
+#[derive(Copy, Clone)]
+pub struct Canonical {}
+impl InternalCanonicalConfig for Canonical {
+    const CANONICAL: bool = true;
+}
+
+/// Accepts duplicate map keys and integers that are encoded with more bytes than needed.
+#[derive(Copy, Clone)]
+pub struct LenientDecoding {}
+impl InternalStrictConfig for LenientDecoding {
+    const STRICT: bool = false;
+}
+
+/// Rejects duplicate map keys and integers that are encoded with more bytes than needed.
+#[derive(Copy, Clone)]
+pub struct StrictDecoding {}
+impl InternalStrictConfig for StrictDecoding {
+    const STRICT: bool = true;
 }
 
 /// Endianness of a `Configuration`.
-#[derive(PartialEq, Eq)]
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
 #[non_exhaustive]
 pub enum Endianness {
     /// Little Endian encoding, see `LittleEndian`.
@@ -264,16 +597,42 @@ pub enum IntEncoding {
     Fixed,
     /// Variable Integer Encoding, see `Varint`.
     Variable,
+    /// Unsigned LEB128 Integer Encoding, see `Leb128`.
+    Leb128,
+}
+
+/// The type that derived enums encode their variant index as.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+#[non_exhaustive]
+pub enum EnumTagType {
+    /// An `u8`, see `U8EnumTag`.
+    U8,
+    /// An `u16`, see `U16EnumTag`.
+    U16,
+    /// An `u32`, see `U32EnumTag`.
+    U32,
+}
+
+impl core::fmt::Display for EnumTagType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::U8 => "u8",
+            Self::U16 => "u16",
+            Self::U32 => "u32",
+        })
+    }
 }
 
 mod internal {
-    use super::{Configuration, Endianness, IntEncoding};
+    use super::{Configuration, Endianness, EnumTagType, IntEncoding};
 
     pub trait InternalEndianConfig {
         const ENDIAN: Endianness;
     }
 
-    impl<E: InternalEndianConfig, I, L> InternalEndianConfig for Configuration<E, I, L> {
+    impl<E: InternalEndianConfig, I, L, D, T, V, K, S> InternalEndianConfig
+        for Configuration<E, I, L, D, T, V, K, S>
+    {
         const ENDIAN: Endianness = E::ENDIAN;
     }
 
@@ -281,15 +640,73 @@ mod internal {
         const INT_ENCODING: IntEncoding;
     }
 
-    impl<E, I: InternalIntEncodingConfig, L> InternalIntEncodingConfig for Configuration<E, I, L> {
+    impl<E, I: InternalIntEncodingConfig, L, D, T, V, K, S> InternalIntEncodingConfig
+        for Configuration<E, I, L, D, T, V, K, S>
+    {
         const INT_ENCODING: IntEncoding = I::INT_ENCODING;
     }
 
     pub trait InternalLimitConfig {
-        const LIMIT: Option<usize>;
+        fn byte_limit(&self) -> Option<usize>;
+    }
+
+    impl<E, I, L: InternalLimitConfig, D, T, V, K, S> InternalLimitConfig
+        for Configuration<E, I, L, D, T, V, K, S>
+    {
+        #[inline]
+        fn byte_limit(&self) -> Option<usize> {
+            self.limit.byte_limit()
+        }
+    }
+
+    pub trait InternalDepthLimitConfig {
+        const MAX_DEPTH: Option<usize>;
+    }
+
+    impl<E, I, L, D: InternalDepthLimitConfig, T, V, K, S> InternalDepthLimitConfig
+        for Configuration<E, I, L, D, T, V, K, S>
+    {
+        const MAX_DEPTH: Option<usize> = D::MAX_DEPTH;
+    }
+
+    pub trait InternalTrailingBytesConfig {
+        const REJECT_TRAILING_BYTES: bool;
+    }
+
+    impl<E, I, L, D, T: InternalTrailingBytesConfig, V, K, S> InternalTrailingBytesConfig
+        for Configuration<E, I, L, D, T, V, K, S>
+    {
+        const REJECT_TRAILING_BYTES: bool = T::REJECT_TRAILING_BYTES;
+    }
+
+    pub trait InternalEnumTagConfig {
+        const ENUM_TAG_TYPE: EnumTagType;
+    }
+
+    impl<E, I, L, D, T, V: InternalEnumTagConfig, K, S> InternalEnumTagConfig
+        for Configuration<E, I, L, D, T, V, K, S>
+    {
+        const ENUM_TAG_TYPE: EnumTagType = V::ENUM_TAG_TYPE;
+    }
+
+    pub trait InternalCanonicalConfig {
+        const CANONICAL: bool;
+    }
+
+    impl<E, I, L, D, T, V, K: InternalCanonicalConfig, S> InternalCanonicalConfig
+        for Configuration<E, I, L, D, T, V, K, S>
+    {
+        const CANONICAL: bool = K::CANONICAL;
+    }
+
+    pub trait InternalStrictConfig {
+        const STRICT: bool;
     }
 
-    impl<E, I, L: InternalLimitConfig> InternalLimitConfig for Configuration<E, I, L> {
-        const LIMIT: Option<usize> = L::LIMIT;
+    // The canonical encoding implies strict decoding
+    impl<E, I, L, D, T, V, K: InternalCanonicalConfig, S: InternalStrictConfig> InternalStrictConfig
+        for Configuration<E, I, L, D, T, V, K, S>
+    {
+        const STRICT: bool = S::STRICT || K::CANONICAL;
     }
 }
@@ -1,8 +1,14 @@
 use super::{
-    read::{BorrowReader, Reader},
+    read::{BorrowReader, Reader, Take},
     BorrowDecoder, Decoder,
 };
-use crate::{config::Config, error::DecodeError, utils::Sealed};
+use crate::{
+    config::Config,
+    error::{ContextError, DecodeError, PathSegment},
+    utils::Sealed,
+};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 /// A Decoder that reads bytes from a given reader `R`.
 ///
@@ -24,19 +30,118 @@ pub struct DecoderImpl<R, C: Config> {
     reader: R,
     config: C,
     bytes_read: usize,
+    depth: usize,
+    /// The path of the error that is being returned, starting at the innermost value, or `None` if the path is not kept
+    #[cfg(feature = "alloc")]
+    error_path: Option<Vec<PathSegment>>,
 }
 
 impl<R: Reader, C: Config> DecoderImpl<R, C> {
     /// Construct a new Decoder
     pub const fn new(reader: R, config: C) -> DecoderImpl<R, C> {
+        Self::with_state(reader, config, DecoderState::new())
+    }
+
+    /// Construct a new Decoder that continues from the depth and the claimed bytes of another decoder, see [Decoder::state].
+    pub const fn with_state(reader: R, config: C, state: DecoderState) -> DecoderImpl<R, C> {
         DecoderImpl {
             reader,
             config,
+            bytes_read: state.bytes_read,
+            depth: state.depth,
+            #[cfg(feature = "alloc")]
+            error_path: None,
+        }
+    }
+
+    /// Makes this decoder keep the path that is passed to [Decoder::error_at], for the `*_with_context` functions
+    #[cfg(feature = "alloc")]
+    pub(crate) fn with_error_path(self) -> Self {
+        DecoderImpl {
+            error_path: Some(Vec::new()),
+            ..self
+        }
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub(crate) fn with_error_path(self) -> Self {
+        self
+    }
+
+    /// Wraps `error` in a [ContextError] with the path that was kept. `offset` is the amount of bytes that were read from the input.
+    #[cfg(feature = "alloc")]
+    #[cold]
+    pub(crate) fn context_error(&mut self, error: DecodeError, offset: usize) -> ContextError {
+        let mut path = self.error_path.take().unwrap_or_default();
+        // The segments were added from the innermost value outwards
+        path.reverse();
+        ContextError::new(error, path, offset)
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    #[cold]
+    pub(crate) fn context_error(&mut self, error: DecodeError, offset: usize) -> ContextError {
+        ContextError::new(error, offset)
+    }
+
+    /// Decodes a value of `len` bytes with a decoder that reads from `self.reader` as `IR`, see [Decoder::decode_length_prefixed]
+    fn decode_take<IR, T>(
+        &mut self,
+        len: usize,
+        as_inner: impl FnOnce(&mut R) -> &mut IR,
+        decode: impl FnOnce(&mut DecoderImpl<Take<'_, IR>, C>) -> Result<T, DecodeError>,
+    ) -> Result<T, DecodeError>
+    where
+        IR: Reader + ?Sized,
+    {
+        // Continue from the claimed bytes before `len`, so that the bytes of the value are not counted twice
+        let state = self.state();
+        self.claim_bytes_read(len)?;
+        let mut inner = DecoderImpl::with_state(
+            Take::new(as_inner(&mut self.reader), len),
+            self.config,
+            state,
+        );
+        // Keep the path of an error inside the value
+        #[cfg(feature = "alloc")]
+        {
+            inner.error_path = self.error_path.take();
+        }
+        let result = decode(&mut inner).and_then(|value| {
+            inner.reader.skip_remaining()?;
+            Ok(value)
+        });
+        #[cfg(feature = "alloc")]
+        {
+            self.error_path = inner.error_path.take();
+        }
+        result
+    }
+}
+
+/// The depth and the amount of claimed bytes of a decoder, see [Decoder::state].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecoderState {
+    depth: usize,
+    bytes_read: usize,
+}
+
+impl DecoderState {
+    /// The state of a new decoder, at depth 0 without any claimed bytes
+    pub const fn new() -> Self {
+        DecoderState {
+            depth: 0,
             bytes_read: 0,
         }
     }
 }
 
+impl Default for DecoderState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<R, C: Config> Sealed for DecoderImpl<R, C> {}
 
 impl<'de, R: BorrowReader<'de>, C: Config> BorrowDecoder<'de> for DecoderImpl<R, C> {
@@ -45,6 +150,23 @@ impl<'de, R: BorrowReader<'de>, C: Config> BorrowDecoder<'de> for DecoderImpl<R,
     fn borrow_reader(&mut self) -> &mut Self::BR {
         &mut self.reader
     }
+
+    fn borrow_decode_length_prefixed<T, F>(
+        &mut self,
+        len: usize,
+        decode: F,
+    ) -> Result<T, DecodeError>
+    where
+        F: FnOnce(
+            &mut DecoderImpl<Take<'_, dyn BorrowReader<'de> + '_>, Self::C>,
+        ) -> Result<T, DecodeError>,
+    {
+        self.decode_take(
+            len,
+            |reader| -> &mut (dyn BorrowReader<'de> + '_) { reader },
+            decode,
+        )
+    }
 }
 
 impl<R: Reader, C: Config> Decoder for DecoderImpl<R, C> {
@@ -60,10 +182,34 @@ impl<R: Reader, C: Config> Decoder for DecoderImpl<R, C> {
         &self.config
     }
 
+    #[inline]
+    fn position(&self) -> Option<usize> {
+        self.reader.position()
+    }
+
+    #[inline]
+    fn state(&self) -> DecoderState {
+        DecoderState {
+            depth: self.depth,
+            bytes_read: self.bytes_read,
+        }
+    }
+
+    #[cold]
+    fn error_at(&mut self, error: DecodeError, segment: PathSegment) -> DecodeError {
+        #[cfg(feature = "alloc")]
+        if let Some(path) = &mut self.error_path {
+            path.push(segment);
+        }
+        #[cfg(not(feature = "alloc"))]
+        let _ = segment;
+        error
+    }
+
     #[inline]
     fn claim_bytes_read(&mut self, n: usize) -> Result<(), DecodeError> {
-        // C::LIMIT is a const so this check should get compiled away
-        if let Some(limit) = C::LIMIT {
+        // For `NoLimit` and `Limit<N>` this is a constant, so this check should get compiled away
+        if let Some(limit) = self.config.byte_limit() {
             // Make sure we don't accidentally overflow `bytes_read`
             self.bytes_read = self
                 .bytes_read
@@ -81,10 +227,38 @@ impl<R: Reader, C: Config> Decoder for DecoderImpl<R, C> {
 
     #[inline]
     fn unclaim_bytes_read(&mut self, n: usize) {
-        // C::LIMIT is a const so this check should get compiled away
-        if C::LIMIT.is_some() {
+        // For `NoLimit` and `Limit<N>` this is a constant, so this check should get compiled away
+        if self.config.byte_limit().is_some() {
             // We should always be claiming more than we unclaim, so this should never underflow
             self.bytes_read -= n;
         }
     }
+
+    #[inline]
+    fn increase_depth(&mut self) -> Result<(), DecodeError> {
+        // C::MAX_DEPTH is a const so this check should get compiled away
+        if let Some(max_depth) = C::MAX_DEPTH {
+            if self.depth >= max_depth {
+                return Err(DecodeError::DepthLimitExceeded);
+            }
+            self.depth += 1;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn decrease_depth(&mut self) {
+        // C::MAX_DEPTH is a const so this check should get compiled away
+        if C::MAX_DEPTH.is_some() {
+            // We should always be increasing the depth before decreasing it, so this should never underflow
+            self.depth -= 1;
+        }
+    }
+
+    fn decode_length_prefixed<T, F>(&mut self, len: usize, decode: F) -> Result<T, DecodeError>
+    where
+        F: FnOnce(&mut DecoderImpl<Take<'_, dyn Reader + '_>, Self::C>) -> Result<T, DecodeError>,
+    {
+        self.decode_take(len, |reader| -> &mut (dyn Reader + '_) { reader }, decode)
+    }
 }
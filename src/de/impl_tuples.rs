@@ -17,6 +17,12 @@ macro_rules! impl_tuple {
                     $($extra :: borrow_decode(decoder)?, )*
                 ))
             }
+
+            fn borrow_skip<BD: BorrowDecoder<'de>>(decoder: &mut BD) -> Result<(), DecodeError> {
+                $first::borrow_skip(decoder)?;
+                $($extra :: borrow_skip(decoder)?; )*
+                Ok(())
+            }
         }
 
         impl<$first $(, $extra)*> Decode for ($first, $($extra, )*)
@@ -32,6 +38,12 @@ macro_rules! impl_tuple {
                     $($extra :: decode(decoder)?, )*
                 ))
             }
+
+            fn skip<DE: Decoder>(decoder: &mut DE) -> Result<(), DecodeError> {
+                $first::skip(decoder)?;
+                $($extra :: skip(decoder)?; )*
+                Ok(())
+            }
         }
     }
 }
@@ -1,11 +1,15 @@
 use super::{
     read::{BorrowReader, Reader},
-    BorrowDecode, BorrowDecoder, Decode, Decoder,
+    BorrowDecode, BorrowDecoder, Decode, Decoder, NumberSlice,
 };
 use crate::{
-    config::{Endianness, IntEncoding, InternalEndianConfig, InternalIntEncodingConfig},
-    error::{DecodeError, IntegerType},
+    config::{
+        Endianness, IntEncoding, InternalEndianConfig, InternalIntEncodingConfig,
+        InternalStrictConfig,
+    },
+    error::{DecodeError, IntegerType, PathSegment},
     impl_borrow_decode,
+    utils::FixedWidthNumber,
 };
 use core::{
     cell::{Cell, RefCell},
@@ -60,8 +64,9 @@ impl Decode for u16 {
         decoder.claim_bytes_read(2)?;
         match D::C::INT_ENCODING {
             IntEncoding::Variable => {
-                crate::varint::varint_decode_u16(decoder.reader(), D::C::ENDIAN)
+                crate::varint::varint_decode_u16(decoder.reader(), D::C::ENDIAN, D::C::STRICT)
             }
+            IntEncoding::Leb128 => crate::varint::leb128_decode_u16(decoder.reader(), D::C::STRICT),
             IntEncoding::Fixed => {
                 let mut bytes = [0u8; 2];
                 decoder.reader().read(&mut bytes)?;
@@ -89,8 +94,9 @@ impl Decode for u32 {
         decoder.claim_bytes_read(4)?;
         match D::C::INT_ENCODING {
             IntEncoding::Variable => {
-                crate::varint::varint_decode_u32(decoder.reader(), D::C::ENDIAN)
+                crate::varint::varint_decode_u32(decoder.reader(), D::C::ENDIAN, D::C::STRICT)
             }
+            IntEncoding::Leb128 => crate::varint::leb128_decode_u32(decoder.reader(), D::C::STRICT),
             IntEncoding::Fixed => {
                 let mut bytes = [0u8; 4];
                 decoder.reader().read(&mut bytes)?;
@@ -118,8 +124,9 @@ impl Decode for u64 {
         decoder.claim_bytes_read(8)?;
         match D::C::INT_ENCODING {
             IntEncoding::Variable => {
-                crate::varint::varint_decode_u64(decoder.reader(), D::C::ENDIAN)
+                crate::varint::varint_decode_u64(decoder.reader(), D::C::ENDIAN, D::C::STRICT)
             }
+            IntEncoding::Leb128 => crate::varint::leb128_decode_u64(decoder.reader(), D::C::STRICT),
             IntEncoding::Fixed => {
                 let mut bytes = [0u8; 8];
                 decoder.reader().read(&mut bytes)?;
@@ -147,7 +154,10 @@ impl Decode for u128 {
         decoder.claim_bytes_read(16)?;
         match D::C::INT_ENCODING {
             IntEncoding::Variable => {
-                crate::varint::varint_decode_u128(decoder.reader(), D::C::ENDIAN)
+                crate::varint::varint_decode_u128(decoder.reader(), D::C::ENDIAN, D::C::STRICT)
+            }
+            IntEncoding::Leb128 => {
+                crate::varint::leb128_decode_u128(decoder.reader(), D::C::STRICT)
             }
             IntEncoding::Fixed => {
                 let mut bytes = [0u8; 16];
@@ -176,7 +186,10 @@ impl Decode for usize {
         decoder.claim_bytes_read(8)?;
         match D::C::INT_ENCODING {
             IntEncoding::Variable => {
-                crate::varint::varint_decode_usize(decoder.reader(), D::C::ENDIAN)
+                crate::varint::varint_decode_usize(decoder.reader(), D::C::ENDIAN, D::C::STRICT)
+            }
+            IntEncoding::Leb128 => {
+                crate::varint::leb128_decode_usize(decoder.reader(), D::C::STRICT)
             }
             IntEncoding::Fixed => {
                 let mut bytes = [0u8; 8];
@@ -229,8 +242,9 @@ impl Decode for i16 {
         decoder.claim_bytes_read(2)?;
         match D::C::INT_ENCODING {
             IntEncoding::Variable => {
-                crate::varint::varint_decode_i16(decoder.reader(), D::C::ENDIAN)
+                crate::varint::varint_decode_i16(decoder.reader(), D::C::ENDIAN, D::C::STRICT)
             }
+            IntEncoding::Leb128 => crate::varint::leb128_decode_i16(decoder.reader(), D::C::STRICT),
             IntEncoding::Fixed => {
                 let mut bytes = [0u8; 2];
                 decoder.reader().read(&mut bytes)?;
@@ -258,8 +272,9 @@ impl Decode for i32 {
         decoder.claim_bytes_read(4)?;
         match D::C::INT_ENCODING {
             IntEncoding::Variable => {
-                crate::varint::varint_decode_i32(decoder.reader(), D::C::ENDIAN)
+                crate::varint::varint_decode_i32(decoder.reader(), D::C::ENDIAN, D::C::STRICT)
             }
+            IntEncoding::Leb128 => crate::varint::leb128_decode_i32(decoder.reader(), D::C::STRICT),
             IntEncoding::Fixed => {
                 let mut bytes = [0u8; 4];
                 decoder.reader().read(&mut bytes)?;
@@ -287,8 +302,9 @@ impl Decode for i64 {
         decoder.claim_bytes_read(8)?;
         match D::C::INT_ENCODING {
             IntEncoding::Variable => {
-                crate::varint::varint_decode_i64(decoder.reader(), D::C::ENDIAN)
+                crate::varint::varint_decode_i64(decoder.reader(), D::C::ENDIAN, D::C::STRICT)
             }
+            IntEncoding::Leb128 => crate::varint::leb128_decode_i64(decoder.reader(), D::C::STRICT),
             IntEncoding::Fixed => {
                 let mut bytes = [0u8; 8];
                 decoder.reader().read(&mut bytes)?;
@@ -316,7 +332,10 @@ impl Decode for i128 {
         decoder.claim_bytes_read(16)?;
         match D::C::INT_ENCODING {
             IntEncoding::Variable => {
-                crate::varint::varint_decode_i128(decoder.reader(), D::C::ENDIAN)
+                crate::varint::varint_decode_i128(decoder.reader(), D::C::ENDIAN, D::C::STRICT)
+            }
+            IntEncoding::Leb128 => {
+                crate::varint::leb128_decode_i128(decoder.reader(), D::C::STRICT)
             }
             IntEncoding::Fixed => {
                 let mut bytes = [0u8; 16];
@@ -345,7 +364,10 @@ impl Decode for isize {
         decoder.claim_bytes_read(8)?;
         match D::C::INT_ENCODING {
             IntEncoding::Variable => {
-                crate::varint::varint_decode_isize(decoder.reader(), D::C::ENDIAN)
+                crate::varint::varint_decode_isize(decoder.reader(), D::C::ENDIAN, D::C::STRICT)
+            }
+            IntEncoding::Leb128 => {
+                crate::varint::leb128_decode_isize(decoder.reader(), D::C::STRICT)
             }
             IntEncoding::Fixed => {
                 let mut bytes = [0u8; 8];
@@ -399,23 +421,39 @@ impl<T: Decode> Decode for Wrapping<T> {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         Ok(Wrapping(T::decode(decoder)?))
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        T::skip(decoder)
+    }
 }
 impl<'de, T: BorrowDecode<'de>> BorrowDecode<'de> for Wrapping<T> {
     fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
         Ok(Wrapping(T::borrow_decode(decoder)?))
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        T::borrow_skip(decoder)
+    }
 }
 
 impl<T: Decode> Decode for Reverse<T> {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         Ok(Reverse(T::decode(decoder)?))
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        T::skip(decoder)
+    }
 }
 
 impl<'de, T: BorrowDecode<'de>> BorrowDecode<'de> for Reverse<T> {
     fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
         Ok(Reverse(T::borrow_decode(decoder)?))
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        T::borrow_skip(decoder)
+    }
 }
 
 impl Decode for char {
@@ -456,11 +494,65 @@ impl<'a, 'de: 'a> BorrowDecode<'de> for &'a [u8] {
     }
 }
 
+macro_rules! impl_borrow_number_slice {
+    ($($ty:ty),*) => {
+        $(
+            impl<'a, 'de: 'a> BorrowDecode<'de> for &'a [$ty] {
+                fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
+                    let len = super::decode_slice_len(decoder)?;
+                    match super::borrow_number_slice::<$ty, D>(decoder, len)? {
+                        NumberSlice::Borrowed(slice) => Ok(slice),
+                        _ => Err(DecodeError::CannotBorrowSlice {
+                            type_name: core::any::type_name::<$ty>(),
+                        }),
+                    }
+                }
+
+                fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+                    super::borrow_skip_seq::<$ty, D>(decoder)
+                }
+            }
+        )*
+    };
+}
+
+impl_borrow_number_slice!(u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
 impl<'a, 'de: 'a> BorrowDecode<'de> for &'a str {
     fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
         let slice = <&[u8]>::borrow_decode(decoder)?;
         core::str::from_utf8(slice).map_err(|inner| DecodeError::Utf8 { inner })
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        <&[u8]>::borrow_skip(decoder)
+    }
+}
+
+/// Decodes a `[u32; N]` or `[u64; N]` with batched varint decoding. Returns `None` if `T` is another type, or if the config does not use varint encoding or uses strict decoding. The caller has to claim the bytes that are read.
+fn decode_varint_array<T, D: Decoder, const N: usize>(
+    decoder: &mut D,
+) -> Result<Option<[T; N]>, DecodeError> {
+    macro_rules! batch {
+        ($ty:ty) => {
+            if unty::type_equal::<T, $ty>() {
+                decoder.increase_depth()?;
+                let mut array = [0 as $ty; N];
+                crate::varint::varint_decode_slice(decoder, &mut array)?;
+                decoder.decrease_depth();
+                // Safety: [T; N] is [$ty; N]
+                return Ok(Some(unsafe {
+                    (&array as *const [$ty; N]).cast::<[T; N]>().read()
+                }));
+            }
+        };
+    }
+
+    if D::C::INT_ENCODING == IntEncoding::Variable && !D::C::STRICT {
+        batch!(u32);
+        batch!(u64);
+    }
+    Ok(None)
 }
 
 impl<T, const N: usize> Decode for [T; N]
@@ -479,18 +571,42 @@ where
             // translate an array of u8 into an array of T
             let res = unsafe { ptr.read() };
             Ok(res)
+        } else if let Some(number) = FixedWidthNumber::<T>::new(decoder.config()) {
+            let mut array = number.zeroed_array::<N>();
+            super::read_number_slice(decoder, number, &mut array)?;
+            Ok(array)
+        } else if let Some(array) = decode_varint_array(decoder)? {
+            Ok(array)
         } else {
-            let result = super::impl_core::collect_into_array(&mut (0..N).map(|_| {
+            decoder.increase_depth()?;
+            let result = super::impl_core::collect_into_array(&mut (0..N).map(|index| {
                 // See the documentation on `unclaim_bytes_read` as to why we're doing this here
                 decoder.unclaim_bytes_read(core::mem::size_of::<T>());
-                T::decode(decoder)
+                T::decode(decoder).map_err(|e| decoder.error_at(e, PathSegment::Index(index)))
             }));
 
             // result is only None if N does not match the values of `(0..N)`, which it always should
             // So this unwrap should never occur
-            result.unwrap()
+            let result = result.unwrap()?;
+            decoder.decrease_depth();
+            Ok(result)
         }
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        if unty::type_equal::<T, u8>() {
+            return super::skip_bytes(decoder, N);
+        }
+        if FixedWidthNumber::<T>::new(decoder.config()).is_some() {
+            return super::skip_bytes(decoder, core::mem::size_of::<[T; N]>());
+        }
+        decoder.increase_depth()?;
+        for _ in 0..N {
+            T::skip(decoder)?;
+        }
+        decoder.decrease_depth();
+        Ok(())
+    }
 }
 
 impl<'de, T, const N: usize> BorrowDecode<'de> for [T; N]
@@ -509,18 +625,43 @@ where
             // translate an array of u8 into an array of T
             let res = unsafe { ptr.read() };
             Ok(res)
+        } else if let Some(number) = FixedWidthNumber::<T>::new(decoder.config()) {
+            let mut array = number.zeroed_array::<N>();
+            super::read_number_slice(decoder, number, &mut array)?;
+            Ok(array)
+        } else if let Some(array) = decode_varint_array(decoder)? {
+            Ok(array)
         } else {
-            let result = super::impl_core::collect_into_array(&mut (0..N).map(|_| {
+            decoder.increase_depth()?;
+            let result = super::impl_core::collect_into_array(&mut (0..N).map(|index| {
                 // See the documentation on `unclaim_bytes_read` as to why we're doing this here
                 decoder.unclaim_bytes_read(core::mem::size_of::<T>());
                 T::borrow_decode(decoder)
+                    .map_err(|e| decoder.error_at(e, PathSegment::Index(index)))
             }));
 
             // result is only None if N does not match the values of `(0..N)`, which it always should
             // So this unwrap should never occur
-            result.unwrap()
+            let result = result.unwrap()?;
+            decoder.decrease_depth();
+            Ok(result)
         }
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        if unty::type_equal::<T, u8>() {
+            return super::skip_bytes(decoder, N);
+        }
+        if FixedWidthNumber::<T>::new(decoder.config()).is_some() {
+            return super::skip_bytes(decoder, core::mem::size_of::<[T; N]>());
+        }
+        decoder.increase_depth()?;
+        for _ in 0..N {
+            T::borrow_skip(decoder)?;
+        }
+        decoder.decrease_depth();
+        Ok(())
+    }
 }
 
 impl Decode for () {
@@ -550,6 +691,13 @@ where
             None => Ok(None),
         }
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        match super::decode_option_variant(decoder, core::any::type_name::<Option<T>>())? {
+            Some(_) => T::skip(decoder),
+            None => Ok(()),
+        }
+    }
 }
 
 impl<'de, T> BorrowDecode<'de> for Option<T>
@@ -565,6 +713,13 @@ where
             None => Ok(None),
         }
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        match super::decode_option_variant(decoder, core::any::type_name::<Option<T>>())? {
+            Some(_) => T::borrow_skip(decoder),
+            None => Ok(()),
+        }
+    }
 }
 
 impl<T, U> Decode for Result<T, U>
@@ -590,6 +745,18 @@ where
             }),
         }
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        match u32::decode(decoder)? {
+            0 => T::skip(decoder),
+            1 => U::skip(decoder),
+            x => Err(DecodeError::UnexpectedVariant {
+                found: x,
+                allowed: &crate::error::AllowedEnumVariants::Range { max: 1, min: 0 },
+                type_name: core::any::type_name::<Result<T, U>>(),
+            }),
+        }
+    }
 }
 
 impl<'de, T, U> BorrowDecode<'de> for Result<T, U>
@@ -615,6 +782,18 @@ where
             }),
         }
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        match u32::decode(decoder)? {
+            0 => T::borrow_skip(decoder),
+            1 => U::borrow_skip(decoder),
+            x => Err(DecodeError::UnexpectedVariant {
+                found: x,
+                allowed: &crate::error::AllowedEnumVariants::Range { max: 1, min: 0 },
+                type_name: core::any::type_name::<Result<T, U>>(),
+            }),
+        }
+    }
 }
 
 impl<T> Decode for Cell<T>
@@ -625,6 +804,10 @@ where
         let t = T::decode(decoder)?;
         Ok(Cell::new(t))
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        T::skip(decoder)
+    }
 }
 
 impl<'de, T> BorrowDecode<'de> for Cell<T>
@@ -635,6 +818,10 @@ where
         let t = T::borrow_decode(decoder)?;
         Ok(Cell::new(t))
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        T::borrow_skip(decoder)
+    }
 }
 
 impl<T> Decode for RefCell<T>
@@ -645,6 +832,10 @@ where
         let t = T::decode(decoder)?;
         Ok(RefCell::new(t))
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        T::skip(decoder)
+    }
 }
 
 impl<'de, T> BorrowDecode<'de> for RefCell<T>
@@ -655,6 +846,10 @@ where
         let t = T::borrow_decode(decoder)?;
         Ok(RefCell::new(t))
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        T::borrow_skip(decoder)
+    }
 }
 
 impl Decode for Duration {
@@ -679,6 +874,11 @@ where
         let max = T::decode(decoder)?;
         Ok(min..max)
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        T::skip(decoder)?;
+        T::skip(decoder)
+    }
 }
 impl<'de, T> BorrowDecode<'de> for Range<T>
 where
@@ -689,6 +889,11 @@ where
         let max = T::borrow_decode(decoder)?;
         Ok(min..max)
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        T::borrow_skip(decoder)?;
+        T::borrow_skip(decoder)
+    }
 }
 
 impl<T> Decode for RangeInclusive<T>
@@ -700,6 +905,11 @@ where
         let max = T::decode(decoder)?;
         Ok(RangeInclusive::new(min, max))
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        T::skip(decoder)?;
+        T::skip(decoder)
+    }
 }
 
 impl<'de, T> BorrowDecode<'de> for RangeInclusive<T>
@@ -711,6 +921,11 @@ where
         let max = T::borrow_decode(decoder)?;
         Ok(RangeInclusive::new(min, max))
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        T::borrow_skip(decoder)?;
+        T::borrow_skip(decoder)
+    }
 }
 
 impl<T> Decode for Bound<T>
@@ -729,6 +944,18 @@ where
             }),
         }
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        match u32::decode(decoder)? {
+            0 => Ok(()),
+            1 | 2 => T::skip(decoder),
+            x => Err(DecodeError::UnexpectedVariant {
+                allowed: &crate::error::AllowedEnumVariants::Range { max: 2, min: 0 },
+                found: x,
+                type_name: core::any::type_name::<Bound<T>>(),
+            }),
+        }
+    }
 }
 
 impl<'de, T> BorrowDecode<'de> for Bound<T>
@@ -747,6 +974,18 @@ where
             }),
         }
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        match u32::decode(decoder)? {
+            0 => Ok(()),
+            1 | 2 => T::borrow_skip(decoder),
+            x => Err(DecodeError::UnexpectedVariant {
+                allowed: &crate::error::AllowedEnumVariants::Range { max: 2, min: 0 },
+                found: x,
+                type_name: core::any::type_name::<Bound<T>>(),
+            }),
+        }
+    }
 }
 
 const UTF8_CHAR_WIDTH: [u8; 256] = [
@@ -0,0 +1,333 @@
+use super::{
+    read::{BorrowReader, SliceReader},
+    BorrowDecode, BorrowDecoder, Decoder, DecoderImpl, DecoderState,
+};
+use crate::{
+    config::Config,
+    enc::{write::Writer, Encode, Encoder},
+    error::{DecodeError, EncodeError},
+};
+use core::{borrow::Borrow, fmt, marker::PhantomData};
+
+/// A sequence that is decoded on demand, e.g. the encoding of a `Vec<T>` or `&[T]`.
+///
+/// Decoding a `LazySeq` only finds out where the sequence ends, by skipping every item with [BorrowDecode::borrow_skip]. The items are decoded when they are iterated over with [iter]. This can only be decoded from a reader that holds all the data in memory, like the reader that is used by [borrow_decode_from_slice].
+///
+/// The items have to be decoded with the same config as the `LazySeq` was decoded with. They are decoded at the depth of the sequence, and count towards the byte limit as if they were decoded together with the value around the sequence.
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use bincode::de::LazySeq;
+///
+/// let config = bincode::config::standard();
+/// let bytes = bincode::encode_to_vec(vec![1u32, 2, 3, 4], config).unwrap();
+///
+/// let (seq, _): (LazySeq<u32>, usize) = bincode::borrow_decode_from_slice(&bytes, config).unwrap();
+/// assert_eq!(seq.len(), 4);
+/// assert_eq!(seq.get(2, config).unwrap(), Some(3));
+/// let items: Vec<u32> = seq.iter(config).collect::<Result<_, _>>().unwrap();
+/// assert_eq!(items, [1, 2, 3, 4]);
+/// # }
+/// ```
+///
+/// [iter]: #method.iter
+/// [borrow_decode_from_slice]: ../fn.borrow_decode_from_slice.html
+pub struct LazySeq<'de, T> {
+    len: usize,
+    bytes: &'de [u8],
+    /// The state of the decoder that the sequence was decoded with, to continue from when decoding the items
+    state: DecoderState,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'de, T> LazySeq<'de, T> {
+    /// The amount of items in this sequence
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if this sequence has no items
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The encoded items of this sequence, without the length in front of them
+    pub fn as_bytes(&self) -> &'de [u8] {
+        self.bytes
+    }
+
+    /// Returns an iterator that decodes the items of this sequence one by one.
+    pub fn iter<C: Config>(&self, config: C) -> LazySeqIter<'de, T, C> {
+        LazySeqIter {
+            decoder: DecoderImpl::with_state(SliceReader::new(self.bytes), config, self.state),
+            remaining: self.len,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, T: BorrowDecode<'de>> LazySeq<'de, T> {
+    /// Decodes the item at `index`. The items before it are skipped without decoding them. Returns `Ok(None)` if `index` is out of bounds.
+    pub fn get<C: Config>(&self, index: usize, config: C) -> Result<Option<T>, DecodeError> {
+        self.iter(config).nth(index).transpose()
+    }
+}
+
+impl<'de, T: BorrowDecode<'de>> BorrowDecode<'de> for LazySeq<'de, T> {
+    fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = super::decode_slice_len(decoder)?;
+        let (bytes, state) = take_items::<T, D>(decoder, len)?;
+        Ok(Self {
+            len,
+            bytes,
+            state,
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<T> Encode for LazySeq<'_, T> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        crate::enc::encode_slice_len(encoder, self.len)?;
+        encoder.writer().write(self.bytes)
+    }
+}
+
+impl<T> Clone for LazySeq<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for LazySeq<'_, T> {}
+
+impl<T> fmt::Debug for LazySeq<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazySeq")
+            .field("len", &self.len)
+            .field("bytes", &self.bytes.len())
+            .finish()
+    }
+}
+
+/// An iterator that decodes the items of a [LazySeq].
+///
+/// After an item fails to decode, the iterator returns `None`.
+pub struct LazySeqIter<'de, T, C: Config> {
+    decoder: DecoderImpl<SliceReader<'de>, C>,
+    remaining: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'de, T: BorrowDecode<'de>, C: Config> Iterator for LazySeqIter<'de, T, C> {
+    type Item = Result<T, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let result = T::borrow_decode(&mut self.decoder);
+        if result.is_err() {
+            self.remaining = 0;
+        }
+        Some(result)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        for _ in 0..n.min(self.remaining) {
+            self.remaining -= 1;
+            if let Err(e) = T::borrow_skip(&mut self.decoder) {
+                self.remaining = 0;
+                return Some(Err(e));
+            }
+        }
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'de, T: BorrowDecode<'de>, C: Config> ExactSizeIterator for LazySeqIter<'de, T, C> {}
+
+/// A map that is decoded on demand, e.g. the encoding of a `HashMap<K, V>` or `BTreeMap<K, V>`.
+///
+/// This works the same as [LazySeq], with entries of `(K, V)`, including the depth and byte limits. Entries can be looked up with [get], which decodes only the keys until the key is found.
+///
+/// ```
+/// # #[cfg(feature = "std")] {
+/// use bincode::de::LazyMap;
+/// use std::collections::BTreeMap;
+///
+/// let config = bincode::config::standard();
+/// let map = BTreeMap::from([("a", 1u32), ("b", 2)]);
+/// let bytes = bincode::encode_to_vec(&map, config).unwrap();
+///
+/// let (lazy, _): (LazyMap<&str, u32>, usize) = bincode::borrow_decode_from_slice(&bytes, config).unwrap();
+/// assert_eq!(lazy.len(), 2);
+/// assert_eq!(lazy.get("b", config).unwrap(), Some(2));
+/// assert_eq!(lazy.get("c", config).unwrap(), None);
+/// # }
+/// ```
+///
+/// [get]: #method.get
+pub struct LazyMap<'de, K, V> {
+    len: usize,
+    bytes: &'de [u8],
+    /// The state of the decoder that the map was decoded with, see [LazySeq]
+    state: DecoderState,
+    _marker: PhantomData<fn() -> (K, V)>,
+}
+
+impl<'de, K, V> LazyMap<'de, K, V> {
+    /// The amount of entries in this map
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if this map has no entries
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The encoded entries of this map, without the length in front of them
+    pub fn as_bytes(&self) -> &'de [u8] {
+        self.bytes
+    }
+
+    /// Returns an iterator that decodes the entries of this map one by one.
+    pub fn iter<C: Config>(&self, config: C) -> LazyMapIter<'de, K, V, C> {
+        LazyMapIter {
+            decoder: DecoderImpl::with_state(SliceReader::new(self.bytes), config, self.state),
+            remaining: self.len,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, K: BorrowDecode<'de>, V: BorrowDecode<'de>> LazyMap<'de, K, V> {
+    /// Decodes the value of the first entry with the given key. The values of the other entries are skipped without decoding them.
+    pub fn get<Q, C>(&self, key: &Q, config: C) -> Result<Option<V>, DecodeError>
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+        C: Config,
+    {
+        let mut decoder = DecoderImpl::with_state(SliceReader::new(self.bytes), config, self.state);
+        for _ in 0..self.len {
+            let k = K::borrow_decode(&mut decoder)?;
+            if k.borrow() == key {
+                return V::borrow_decode(&mut decoder).map(Some);
+            }
+            V::borrow_skip(&mut decoder)?;
+        }
+        Ok(None)
+    }
+}
+
+impl<'de, K: BorrowDecode<'de>, V: BorrowDecode<'de>> BorrowDecode<'de> for LazyMap<'de, K, V> {
+    fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = super::decode_slice_len(decoder)?;
+        let (bytes, state) = take_items::<(K, V), D>(decoder, len)?;
+        Ok(Self {
+            len,
+            bytes,
+            state,
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<K, V> Encode for LazyMap<'_, K, V> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        crate::enc::encode_slice_len(encoder, self.len)?;
+        encoder.writer().write(self.bytes)
+    }
+}
+
+impl<K, V> Clone for LazyMap<'_, K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V> Copy for LazyMap<'_, K, V> {}
+
+impl<K, V> fmt::Debug for LazyMap<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyMap")
+            .field("len", &self.len)
+            .field("bytes", &self.bytes.len())
+            .finish()
+    }
+}
+
+/// An iterator that decodes the entries of a [LazyMap].
+///
+/// After an entry fails to decode, the iterator returns `None`.
+pub struct LazyMapIter<'de, K, V, C: Config> {
+    decoder: DecoderImpl<SliceReader<'de>, C>,
+    remaining: usize,
+    _marker: PhantomData<fn() -> (K, V)>,
+}
+
+impl<'de, K: BorrowDecode<'de>, V: BorrowDecode<'de>, C: Config> Iterator
+    for LazyMapIter<'de, K, V, C>
+{
+    type Item = Result<(K, V), DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let result = <(K, V)>::borrow_decode(&mut self.decoder);
+        if result.is_err() {
+            self.remaining = 0;
+        }
+        Some(result)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        for _ in 0..n.min(self.remaining) {
+            self.remaining -= 1;
+            if let Err(e) = <(K, V)>::borrow_skip(&mut self.decoder) {
+                self.remaining = 0;
+                return Some(Err(e));
+            }
+        }
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'de, K: BorrowDecode<'de>, V: BorrowDecode<'de>, C: Config> ExactSizeIterator
+    for LazyMapIter<'de, K, V, C>
+{
+}
+
+/// Skips `len` items of `T` and returns the bytes that they were encoded in, and the state of the decoder to decode the items with.
+fn take_items<'de, T: BorrowDecode<'de>, D: BorrowDecoder<'de>>(
+    decoder: &mut D,
+    len: usize,
+) -> Result<(&'de [u8], DecoderState), DecodeError> {
+    let remaining = decoder.borrow_reader().peek_remaining().ok_or(DecodeError::Other(
+        "Lazy types can only be decoded from a reader that holds all its data in memory, like a slice",
+    ))?;
+    decoder.increase_depth()?;
+    // The items are at the depth inside the sequence, and their bytes are claimed by `decoder` below
+    let state = decoder.state();
+    let mut items = DecoderImpl::with_state(SliceReader::new(remaining), *decoder.config(), state);
+    for _ in 0..len {
+        T::borrow_skip(&mut items)?;
+    }
+    decoder.decrease_depth();
+    let size = remaining.len() - items.reader().slice.len();
+    decoder.claim_bytes_read(size)?;
+    Ok((decoder.borrow_reader().take_bytes(size)?, state))
+}
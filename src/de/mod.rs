@@ -4,17 +4,19 @@ mod decoder;
 mod impl_core;
 mod impl_tuples;
 mod impls;
+mod lazy;
 
-use self::read::{BorrowReader, Reader};
+use self::read::{BorrowReader, Reader, Take};
 use crate::{
-    config::{Config, InternalLimitConfig},
-    error::DecodeError,
-    utils::Sealed,
+    config::{Config, EnumTagType, InternalLimitConfig},
+    error::{DecodeError, PathSegment},
+    utils::{FixedWidthNumber, Sealed},
 };
 
 pub mod read;
 
-pub use self::decoder::DecoderImpl;
+pub use self::decoder::{DecoderImpl, DecoderState};
+pub use self::lazy::{LazyMap, LazyMapIter, LazySeq, LazySeqIter};
 
 /// Trait that makes a type able to be decoded, akin to serde's `DeserializeOwned` trait.
 ///
@@ -44,13 +46,17 @@ pub use self::decoder::DecoderImpl;
 /// #     pub x: f32,
 /// #     pub y: f32,
 /// # }
+/// use bincode::error::PathSegment;
+///
 /// impl bincode::Decode for Entity {
 ///     fn decode<D: bincode::de::Decoder>(
 ///         decoder: &mut D,
 ///     ) -> core::result::Result<Self, bincode::error::DecodeError> {
 ///         Ok(Self {
-///             x: bincode::Decode::decode(decoder)?,
-///             y: bincode::Decode::decode(decoder)?,
+///             x: bincode::Decode::decode(decoder)
+///                 .map_err(|e| decoder.error_at(e, PathSegment::field::<Self>("x")))?,
+///             y: bincode::Decode::decode(decoder)
+///                 .map_err(|e| decoder.error_at(e, PathSegment::field::<Self>("y")))?,
 ///         })
 ///     }
 /// }
@@ -59,13 +65,17 @@ pub use self::decoder::DecoderImpl;
 ///         decoder: &mut D,
 ///     ) -> core::result::Result<Self, bincode::error::DecodeError> {
 ///         Ok(Self {
-///             x: bincode::BorrowDecode::borrow_decode(decoder)?,
-///             y: bincode::BorrowDecode::borrow_decode(decoder)?,
+///             x: bincode::BorrowDecode::borrow_decode(decoder)
+///                 .map_err(|e| decoder.error_at(e, PathSegment::field::<Self>("x")))?,
+///             y: bincode::BorrowDecode::borrow_decode(decoder)
+///                 .map_err(|e| decoder.error_at(e, PathSegment::field::<Self>("y")))?,
 ///         })
 ///     }
 /// }
 /// ```
 ///
+/// The `map_err` adds the name of the field to the [path](../error/struct.ContextError.html#method.path) of the error, so that it is clear which field failed to decode. It can be left out.
+///
 /// From here you can add/remove fields, or add custom logic.
 ///
 /// To get specific integer types, you can use:
@@ -85,6 +95,13 @@ pub use self::decoder::DecoderImpl;
 pub trait Decode: Sized {
     /// Attempt to decode this type with the given [Decode].
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError>;
+
+    /// Advance the decoder over the encoding of this type, without building it.
+    ///
+    /// The default implementation decodes the value and drops it. This should be overwritten by types that allocate, so that they can be skipped without allocating. See [skip] for more information.
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        Self::decode(decoder).map(|_| ())
+    }
 }
 
 /// Trait that makes a type able to be decoded, akin to serde's `Deserialize` trait.
@@ -95,6 +112,13 @@ pub trait Decode: Sized {
 pub trait BorrowDecode<'de>: Sized {
     /// Attempt to decode this type with the given [BorrowDecode].
     fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError>;
+
+    /// Advance the decoder over the encoding of this type, without building it. This is the [BorrowDecode] version of [Decode::skip].
+    ///
+    /// The default implementation decodes the value and drops it.
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        Self::borrow_decode(decoder).map(|_| ())
+    }
 }
 
 /// Helper macro to implement `BorrowDecode` for any type that implements `Decode`.
@@ -107,6 +131,12 @@ macro_rules! impl_borrow_decode {
             ) -> core::result::Result<Self, $crate::error::DecodeError> {
                 $crate::Decode::decode(decoder)
             }
+
+            fn borrow_skip<D: $crate::de::BorrowDecoder<'de>>(
+                decoder: &mut D,
+            ) -> core::result::Result<(), $crate::error::DecodeError> {
+                <Self as $crate::Decode>::skip(decoder)
+            }
         }
     };
 }
@@ -125,14 +155,61 @@ pub trait Decoder: Sealed {
     /// Returns a reference to the config
     fn config(&self) -> &Self::C;
 
+    /// Returns the amount of bytes that were read from the reader since it was created, or `None` if the reader does not keep track of its [position](read/trait.Reader.html#method.position).
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use bincode::de::{read::SliceReader, Decode, Decoder, DecoderImpl};
+    ///
+    /// let bytes = bincode::encode_to_vec((5u8, 1000u32), bincode::config::standard()).unwrap();
+    /// let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), bincode::config::standard());
+    /// u8::decode(&mut decoder).unwrap();
+    /// assert_eq!(decoder.position(), Some(1));
+    /// u32::decode(&mut decoder).unwrap();
+    /// assert_eq!(decoder.position(), Some(4));
+    /// # }
+    /// ```
+    fn position(&self) -> Option<usize>;
+
+    /// Returns the depth and the amount of claimed bytes of this decoder.
+    ///
+    /// Types that decode a part of the input with their own decoder, like [Opaque], create that decoder with [DecoderImpl::with_state], so that the depth limit and the byte limit of the config still apply to the whole input.
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use bincode::de::{read::SliceReader, Decode, Decoder, DecoderImpl};
+    ///
+    /// let config = bincode::config::standard().with_max_depth::<1>();
+    /// let bytes = bincode::encode_to_vec(Box::new(5u32), config).unwrap();
+    /// let mut outer = DecoderImpl::new(SliceReader::new(&bytes), config);
+    /// outer.increase_depth().unwrap();
+    ///
+    /// let mut inner = DecoderImpl::with_state(SliceReader::new(&bytes), config, outer.state());
+    /// assert!(Box::<u32>::decode(&mut inner).is_err());
+    /// # }
+    /// ```
+    ///
+    /// [Opaque]: ../struct.Opaque.html
+    fn state(&self) -> DecoderState;
+
+    /// Adds `segment` to the path of `error` and returns `error`. Implementations of [Decode] call this when a field or an item inside them fails to decode, starting at the innermost value.
+    ///
+    /// The path is only kept by the decoders of the `*_with_context` functions, like [decode_from_slice_with_context], which return it in a [ContextError]. Other decoders ignore `segment`, and `error` is always returned unchanged, so that it can still be matched on.
+    ///
+    /// See the [Decode] documentation for an example.
+    ///
+    /// [decode_from_slice_with_context]: ../fn.decode_from_slice_with_context.html
+    /// [ContextError]: ../error/struct.ContextError.html
+    fn error_at(&mut self, error: DecodeError, segment: PathSegment) -> DecodeError;
+
     /// Claim that `n` bytes are going to be read from the decoder.
-    /// This can be used to validate `Configuration::Limit<N>()`.
+    /// This can be used to validate `Configuration::with_limit::<N>()` and `Configuration::with_runtime_limit(limit)`.
     fn claim_bytes_read(&mut self, n: usize) -> Result<(), DecodeError>;
 
     /// Claim that we're going to read a container which contains `len` entries of `T`.
     /// This will correctly handle overflowing if `len * size_of::<T>() > usize::max_value`
     fn claim_container_read<T>(&mut self, len: usize) -> Result<(), DecodeError> {
-        if <Self::C as InternalLimitConfig>::LIMIT.is_some() {
+        if self.config().byte_limit().is_some() {
             match len.checked_mul(core::mem::size_of::<T>()) {
                 Some(val) => self.claim_bytes_read(val),
                 None => Err(DecodeError::LimitExceeded),
@@ -202,6 +279,75 @@ pub trait Decoder: Sealed {
     /// }
     /// ```
     fn unclaim_bytes_read(&mut self, n: usize);
+
+    /// Notify the decoder that a nested value is about to be decoded.
+    /// This can be used to validate `Configuration::with_max_depth::<N>()`.
+    ///
+    /// Every call to this function should be paired with a call to [decrease_depth] once the nested value is decoded. If decoding fails, the decoder should not be used anymore, so there is no need to call `decrease_depth`.
+    ///
+    /// ```
+    /// # use bincode::de::{Decode, Decoder};
+    /// # use bincode::error::DecodeError;
+    /// enum List {
+    ///     Nil,
+    ///     Cons(u32, Box<List>),
+    /// }
+    ///
+    /// impl Decode for List {
+    ///     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+    ///         if !bool::decode(decoder)? {
+    ///             return Ok(List::Nil);
+    ///         }
+    ///         decoder.increase_depth()?;
+    ///         let value = u32::decode(decoder)?;
+    ///         let next = List::decode(decoder)?;
+    ///         decoder.decrease_depth();
+    ///         Ok(List::Cons(value, Box::new(next)))
+    ///     }
+    /// }
+    /// # bincode::impl_borrow_decode!(List);
+    /// ```
+    ///
+    /// [decrease_depth]: #tymethod.decrease_depth
+    fn increase_depth(&mut self) -> Result<(), DecodeError>;
+
+    /// Notify the decoder that a nested value, that was announced with [increase_depth], is done decoding.
+    ///
+    /// [increase_depth]: #tymethod.increase_depth
+    fn decrease_depth(&mut self);
+
+    /// Decodes a value that is prefixed with its length `len` in bytes, like a version of a versioned struct or a variant of a `length_prefixed` enum. `decode` is given a decoder that reads at most `len` bytes, and the bytes it does not read are skipped afterwards, so that values with fields that are unknown to this version of a type can still be decoded.
+    ///
+    /// Returns [DecodeError::UnexpectedEnd] if `decode` tries to read more than `len` bytes.
+    ///
+    /// ```
+    /// use bincode::de::{read::SliceReader, Decode, Decoder, DecoderImpl};
+    ///
+    /// // A length of 3, a value of 5 and 2 bytes that are skipped, then a value of 7
+    /// let bytes = [3, 5, 1, 2, 7];
+    /// let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), bincode::config::standard());
+    /// let len = usize::decode(&mut decoder).unwrap();
+    /// let value = decoder.decode_length_prefixed(len, |decoder| u8::decode(decoder));
+    /// assert_eq!(value.unwrap(), 5);
+    /// assert_eq!(u8::decode(&mut decoder).unwrap(), 7);
+    /// ```
+    fn decode_length_prefixed<T, F>(&mut self, len: usize, decode: F) -> Result<T, DecodeError>
+    where
+        F: FnOnce(&mut DecoderImpl<Take<'_, dyn Reader + '_>, Self::C>) -> Result<T, DecodeError>,
+    {
+        // Continue from the claimed bytes before `len`, so that the bytes of the value are not counted twice
+        let state = self.state();
+        self.claim_bytes_read(len)?;
+        let config = *self.config();
+        let mut inner = DecoderImpl::with_state(
+            Take::new(self.reader() as &mut dyn Reader, len),
+            config,
+            state,
+        );
+        let value = decode(&mut inner)?;
+        inner.reader().skip_remaining()?;
+        Ok(value)
+    }
 }
 
 /// Any source that can decode basic types. This type is most notably implemented for [Decoder].
@@ -213,9 +359,33 @@ pub trait BorrowDecoder<'de>: Decoder {
 
     /// Rerturns a mutable reference to the borrow reader
     fn borrow_reader(&mut self) -> &mut Self::BR;
+
+    /// The [BorrowDecoder] version of [Decoder::decode_length_prefixed].
+    fn borrow_decode_length_prefixed<T, F>(
+        &mut self,
+        len: usize,
+        decode: F,
+    ) -> Result<T, DecodeError>
+    where
+        F: FnOnce(
+            &mut DecoderImpl<Take<'_, dyn BorrowReader<'de> + '_>, Self::C>,
+        ) -> Result<T, DecodeError>,
+    {
+        let state = self.state();
+        self.claim_bytes_read(len)?;
+        let config = *self.config();
+        let mut inner = DecoderImpl::with_state(
+            Take::new(self.borrow_reader() as &mut dyn BorrowReader<'de>, len),
+            config,
+            state,
+        );
+        let value = decode(&mut inner)?;
+        inner.reader().skip_remaining()?;
+        Ok(value)
+    }
 }
 
-impl<'a, T> Decoder for &'a mut T
+impl<T> Decoder for &mut T
 where
     T: Decoder,
 {
@@ -231,6 +401,21 @@ where
         T::config(self)
     }
 
+    #[inline]
+    fn position(&self) -> Option<usize> {
+        T::position(self)
+    }
+
+    #[inline]
+    fn state(&self) -> DecoderState {
+        T::state(self)
+    }
+
+    #[inline]
+    fn error_at(&mut self, error: DecodeError, segment: PathSegment) -> DecodeError {
+        T::error_at(self, error, segment)
+    }
+
     #[inline]
     fn claim_bytes_read(&mut self, n: usize) -> Result<(), DecodeError> {
         T::claim_bytes_read(self, n)
@@ -240,9 +425,27 @@ where
     fn unclaim_bytes_read(&mut self, n: usize) {
         T::unclaim_bytes_read(self, n)
     }
+
+    #[inline]
+    fn increase_depth(&mut self) -> Result<(), DecodeError> {
+        T::increase_depth(self)
+    }
+
+    #[inline]
+    fn decrease_depth(&mut self) {
+        T::decrease_depth(self)
+    }
+
+    #[inline]
+    fn decode_length_prefixed<V, F>(&mut self, len: usize, decode: F) -> Result<V, DecodeError>
+    where
+        F: FnOnce(&mut DecoderImpl<Take<'_, dyn Reader + '_>, Self::C>) -> Result<V, DecodeError>,
+    {
+        T::decode_length_prefixed(self, len, decode)
+    }
 }
 
-impl<'a, 'de, T> BorrowDecoder<'de> for &'a mut T
+impl<'de, T> BorrowDecoder<'de> for &mut T
 where
     T: BorrowDecoder<'de>,
 {
@@ -251,6 +454,20 @@ where
     fn borrow_reader(&mut self) -> &mut Self::BR {
         T::borrow_reader(self)
     }
+
+    #[inline]
+    fn borrow_decode_length_prefixed<V, F>(
+        &mut self,
+        len: usize,
+        decode: F,
+    ) -> Result<V, DecodeError>
+    where
+        F: FnOnce(
+            &mut DecoderImpl<Take<'_, dyn BorrowReader<'de> + '_>, Self::C>,
+        ) -> Result<V, DecodeError>,
+    {
+        T::borrow_decode_length_prefixed(self, len, decode)
+    }
 }
 
 /// Decodes only the option variant from the decoder. Will not read any more data than that.
@@ -278,3 +495,181 @@ pub(crate) fn decode_slice_len<D: Decoder>(decoder: &mut D) -> Result<usize, Dec
 
     v.try_into().map_err(|_| DecodeError::OutsideUsizeRange(v))
 }
+
+/// With strict decoding, fails if a key was inserted into the map or set `M` that was already in it. `inserted` is `true` if the key was new.
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn check_duplicate_key<M, D: Decoder>(
+    decoder: &D,
+    inserted: bool,
+) -> Result<(), DecodeError> {
+    if !inserted && decoder.config().strict_decoding() {
+        return Err(DecodeError::DuplicateMapKey {
+            type_name: core::any::type_name::<M>(),
+        });
+    }
+    Ok(())
+}
+
+/// Advances the decoder over the encoding of `T`, without building a `T`.
+///
+/// Containers like `Vec<T>`, `String` and `HashMap<K, V>` are skipped without allocating, and collections of bytes are skipped in a single step. Derived types skip their fields one by one. The skipped data is not validated, e.g. the bytes of a skipped `String` are not checked to be valid UTF-8.
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use bincode::de::{Decode, DecoderImpl, read::SliceReader};
+///
+/// let config = bincode::config::standard();
+/// let bytes = bincode::encode_to_vec((vec![String::from("a long log line")], 5u32), config).unwrap();
+///
+/// let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), config);
+/// bincode::de::skip::<Vec<String>>(&mut decoder).unwrap();
+/// assert_eq!(u32::decode(&mut decoder).unwrap(), 5);
+/// # }
+/// ```
+#[inline]
+pub fn skip<T: Decode>(decoder: &mut impl Decoder) -> Result<(), DecodeError> {
+    T::skip(decoder)
+}
+
+/// Skips a sequence of `T` that is prefixed with its length, like a `Vec<T>`.
+#[cfg(feature = "alloc")]
+pub(crate) fn skip_seq<T: Decode, D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+    let len = decode_slice_len(decoder)?;
+    if unty::type_equal::<T, u8>() {
+        return skip_bytes(decoder, len);
+    }
+    if FixedWidthNumber::<T>::new(decoder.config()).is_some() {
+        return skip_bytes(decoder, fixed_width_len::<T>(len)?);
+    }
+    decoder.increase_depth()?;
+    for _ in 0..len {
+        T::skip(decoder)?;
+    }
+    decoder.decrease_depth();
+    Ok(())
+}
+
+/// Skips a sequence of `T` that is prefixed with its length with [BorrowDecode::borrow_skip].
+pub(crate) fn borrow_skip_seq<'de, T: BorrowDecode<'de>, D: BorrowDecoder<'de>>(
+    decoder: &mut D,
+) -> Result<(), DecodeError> {
+    let len = decode_slice_len(decoder)?;
+    if unty::type_equal::<T, u8>() {
+        return skip_bytes(decoder, len);
+    }
+    if FixedWidthNumber::<T>::new(decoder.config()).is_some() {
+        return skip_bytes(decoder, fixed_width_len::<T>(len)?);
+    }
+    decoder.increase_depth()?;
+    for _ in 0..len {
+        T::borrow_skip(decoder)?;
+    }
+    decoder.decrease_depth();
+    Ok(())
+}
+
+/// Skips a map of `K` and `V` that is prefixed with its length with [BorrowDecode::borrow_skip].
+#[cfg(feature = "alloc")]
+pub(crate) fn borrow_skip_map<
+    'de,
+    K: BorrowDecode<'de>,
+    V: BorrowDecode<'de>,
+    D: BorrowDecoder<'de>,
+>(
+    decoder: &mut D,
+) -> Result<(), DecodeError> {
+    let len = decode_slice_len(decoder)?;
+    decoder.increase_depth()?;
+    for _ in 0..len {
+        K::borrow_skip(decoder)?;
+        V::borrow_skip(decoder)?;
+    }
+    decoder.decrease_depth();
+    Ok(())
+}
+
+/// Skips a map of `K` and `V` that is prefixed with its length, like a `BTreeMap<K, V>`.
+#[cfg(feature = "alloc")]
+pub(crate) fn skip_map<K: Decode, V: Decode, D: Decoder>(
+    decoder: &mut D,
+) -> Result<(), DecodeError> {
+    let len = decode_slice_len(decoder)?;
+    decoder.increase_depth()?;
+    for _ in 0..len {
+        K::skip(decoder)?;
+        V::skip(decoder)?;
+    }
+    decoder.decrease_depth();
+    Ok(())
+}
+
+/// Skips `len` bytes of the decoder without decoding them.
+///
+/// This is used by `#[bincode(versioned)]` structs to skip the fields that were added by a newer version of the struct.
+#[inline]
+pub fn skip_bytes<D: Decoder>(decoder: &mut D, len: usize) -> Result<(), DecodeError> {
+    decoder.claim_bytes_read(len)?;
+    read::skip(decoder.reader(), len)
+}
+
+/// The numbers of a slice, as found by [borrow_number_slice]
+pub(crate) enum NumberSlice<'de, T> {
+    /// The numbers are stored as their native bytes, and are aligned in the input
+    Borrowed(&'de [T]),
+    /// The numbers are stored as their native bytes, but are not aligned in the input
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+    Unaligned(FixedWidthNumber<T>, &'de [u8]),
+    /// The numbers are not stored as their native bytes, and have to be decoded. Nothing has been read from the decoder.
+    Encoded,
+}
+
+/// Borrows `len` numbers of type `T` from the decoder, if the config encodes them as their native bytes.
+pub(crate) fn borrow_number_slice<'de, T, D: BorrowDecoder<'de>>(
+    decoder: &mut D,
+    len: usize,
+) -> Result<NumberSlice<'de, T>, DecodeError> {
+    let number = match FixedWidthNumber::<T>::new(decoder.config()) {
+        Some(number) if number.is_native() => number,
+        _ => return Ok(NumberSlice::Encoded),
+    };
+    let size = fixed_width_len::<T>(len)?;
+    decoder.claim_bytes_read(size)?;
+    let bytes = decoder.borrow_reader().take_bytes(size)?;
+    Ok(match number.cast_bytes(bytes) {
+        Some(slice) => NumberSlice::Borrowed(slice),
+        None => NumberSlice::Unaligned(number, bytes),
+    })
+}
+
+/// The size in bytes of `len` items of the fixed width number `T`
+fn fixed_width_len<T>(len: usize) -> Result<usize, DecodeError> {
+    len.checked_mul(core::mem::size_of::<T>())
+        .ok_or(DecodeError::OutsideUsizeRange(len as u64))
+}
+
+/// Reads `items` as one block of bytes. The caller has to claim the bytes that are read.
+pub(crate) fn read_number_slice<T, D: Decoder>(
+    decoder: &mut D,
+    number: FixedWidthNumber<T>,
+    items: &mut [T],
+) -> Result<(), DecodeError> {
+    let bytes = number.as_bytes_mut(items);
+    decoder.reader().read(bytes)?;
+    number.swap_bytes(bytes);
+    Ok(())
+}
+
+/// Decodes the variant index of a derived enum, which is encoded as the [EnumTagType] of the config.
+///
+/// This is used by `#[derive(bincode::Decode)]` on enums.
+///
+/// [EnumTagType]: ../config/enum.EnumTagType.html
+#[inline]
+pub fn decode_enum_tag<D: Decoder>(decoder: &mut D) -> Result<u32, DecodeError> {
+    match decoder.config().enum_tag_type() {
+        EnumTagType::U8 => u8::decode(decoder).map(u32::from),
+        EnumTagType::U16 => u16::decode(decoder).map(u32::from),
+        _ => u32::decode(decoder),
+    }
+}
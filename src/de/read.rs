@@ -29,6 +29,16 @@ pub trait Reader {
     /// must be provided so that subsequent reads or peek-reads do not return the same bytes
     #[inline]
     fn consume(&mut self, _: usize) {}
+
+    /// The amount of bytes that were read or consumed since this reader was created, if the reader keeps track of it. This is used by [Decoder::position], and returns `None` by default.
+    ///
+    /// Wrap a reader in a [CountingReader] to count the bytes that are read from it.
+    ///
+    /// [Decoder::position]: ../trait.Decoder.html#tymethod.position
+    #[inline]
+    fn position(&self) -> Option<usize> {
+        None
+    }
 }
 
 impl<T> Reader for &mut T
@@ -49,6 +59,27 @@ where
     fn consume(&mut self, n: usize) {
         (*self).consume(n)
     }
+
+    #[inline]
+    fn position(&self) -> Option<usize> {
+        (**self).position()
+    }
+}
+
+/// Reads and drops `len` bytes from `reader`.
+pub(crate) fn skip<R: Reader + ?Sized>(reader: &mut R, len: usize) -> Result<(), DecodeError> {
+    if reader.peek_read(len).is_some() {
+        reader.consume(len);
+        return Ok(());
+    }
+    let mut buffer = [0u8; 64];
+    let mut remaining = len;
+    while remaining > 0 {
+        let chunk = remaining.min(buffer.len());
+        reader.read(&mut buffer[..chunk])?;
+        remaining -= chunk;
+    }
+    Ok(())
 }
 
 /// A reader for borrowed data. Implementors of this must also implement the [Reader] trait. See the module documentation for more information.
@@ -57,17 +88,31 @@ pub trait BorrowReader<'storage>: Reader {
     ///
     /// *note*: Exactly `length` bytes must be returned. If less bytes are returned, bincode may panic. If more bytes are returned, the excess bytes may be discarded.
     fn take_bytes(&mut self, length: usize) -> Result<&'storage [u8], DecodeError>;
+
+    /// Returns all the bytes that are left in this reader, without consuming them.
+    ///
+    /// This is used by types like [LazySeq] that keep a reference to the encoded data. Readers that can not return the remaining bytes at once return `None`, which is the default.
+    ///
+    /// [LazySeq]: ../struct.LazySeq.html
+    #[inline]
+    fn peek_remaining(&self) -> Option<&'storage [u8]> {
+        None
+    }
 }
 
 /// A reader type for `&[u8]` slices. Implements both [Reader] and [BorrowReader], and thus can be used for borrowed data.
 pub struct SliceReader<'storage> {
     pub(crate) slice: &'storage [u8],
+    len: usize,
 }
 
 impl<'storage> SliceReader<'storage> {
     /// Constructs a slice reader
     pub const fn new(bytes: &'storage [u8]) -> SliceReader<'storage> {
-        SliceReader { slice: bytes }
+        SliceReader {
+            slice: bytes,
+            len: bytes.len(),
+        }
     }
 }
 
@@ -95,6 +140,11 @@ impl<'storage> Reader for SliceReader<'storage> {
     fn consume(&mut self, n: usize) {
         self.slice = self.slice.get(n..).unwrap_or_default();
     }
+
+    #[inline]
+    fn position(&self) -> Option<usize> {
+        Some(self.len - self.slice.len())
+    }
 }
 
 impl<'storage> BorrowReader<'storage> for SliceReader<'storage> {
@@ -109,4 +159,175 @@ impl<'storage> BorrowReader<'storage> for SliceReader<'storage> {
         self.slice = remaining;
         Ok(read_slice)
     }
+    #[inline]
+    fn peek_remaining(&self) -> Option<&'storage [u8]> {
+        Some(self.slice)
+    }
+}
+
+/// A reader that counts the bytes that are read from the inner reader, for readers that do not know their [position](trait.Reader.html#method.position). This is used by [decode_from_reader_with_count].
+///
+/// [decode_from_reader_with_count]: ../../fn.decode_from_reader_with_count.html
+pub struct CountingReader<R> {
+    reader: R,
+    position: usize,
+}
+
+impl<R> CountingReader<R> {
+    /// Wraps `reader`, starting at position 0
+    pub const fn new(reader: R) -> Self {
+        Self {
+            reader,
+            position: 0,
+        }
+    }
+
+    /// The amount of bytes that were read or consumed from the inner reader
+    pub const fn bytes_read(&self) -> usize {
+        self.position
+    }
+
+    /// Returns a reference to the inner reader
+    pub const fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Returns the inner reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Reader> Reader for CountingReader<R> {
+    #[inline(always)]
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        self.reader.read(bytes)?;
+        self.position += bytes.len();
+        Ok(())
+    }
+
+    #[inline]
+    fn peek_read(&mut self, n: usize) -> Option<&[u8]> {
+        self.reader.peek_read(n)
+    }
+
+    #[inline]
+    fn consume(&mut self, n: usize) {
+        self.reader.consume(n);
+        self.position += n;
+    }
+
+    #[inline]
+    fn position(&self) -> Option<usize> {
+        Some(self.position)
+    }
+}
+
+impl<'storage, R: BorrowReader<'storage>> BorrowReader<'storage> for CountingReader<R> {
+    #[inline(always)]
+    fn take_bytes(&mut self, length: usize) -> Result<&'storage [u8], DecodeError> {
+        let bytes = self.reader.take_bytes(length)?;
+        self.position += length;
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn peek_remaining(&self) -> Option<&'storage [u8]> {
+        self.reader.peek_remaining()
+    }
+}
+
+/// A reader that can read at most a given amount of bytes from an inner reader.
+///
+/// This is used for values that are prefixed with their length, see [Decoder::decode_length_prefixed].
+///
+/// [Decoder::decode_length_prefixed]: ../trait.Decoder.html#method.decode_length_prefixed
+pub struct Take<'a, R: ?Sized> {
+    reader: &'a mut R,
+    len: usize,
+    remaining: usize,
+}
+
+impl<'a, R: ?Sized> Take<'a, R> {
+    pub(crate) fn new(reader: &'a mut R, len: usize) -> Self {
+        Self {
+            reader,
+            len,
+            remaining: len,
+        }
+    }
+
+    /// Returns [DecodeError::TrailingBytes] if not all bytes were read
+    pub(crate) fn finish(&self) -> Result<(), DecodeError> {
+        if self.remaining == 0 {
+            Ok(())
+        } else {
+            Err(DecodeError::TrailingBytes {
+                remaining: self.remaining,
+            })
+        }
+    }
+
+    fn claim(&mut self, n: usize) -> Result<(), DecodeError> {
+        if n > self.remaining {
+            return Err(DecodeError::UnexpectedEnd {
+                additional: n - self.remaining,
+            });
+        }
+        self.remaining -= n;
+        Ok(())
+    }
+}
+
+impl<R: Reader + ?Sized> Take<'_, R> {
+    /// Skips the bytes that were not read yet
+    pub(crate) fn skip_remaining(&mut self) -> Result<(), DecodeError> {
+        skip(self, self.remaining)
+    }
+}
+
+impl<R: Reader + ?Sized> Reader for Take<'_, R> {
+    #[inline]
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        self.claim(bytes.len())?;
+        self.reader.read(bytes)
+    }
+
+    #[inline]
+    fn peek_read(&mut self, n: usize) -> Option<&[u8]> {
+        if n > self.remaining {
+            return None;
+        }
+        let remaining = self.remaining;
+        self.reader
+            .peek_read(n)
+            .map(|bytes| &bytes[..bytes.len().min(remaining)])
+    }
+
+    #[inline]
+    fn consume(&mut self, n: usize) {
+        self.remaining -= n;
+        self.reader.consume(n)
+    }
+
+    #[inline]
+    fn position(&self) -> Option<usize> {
+        Some(self.len - self.remaining)
+    }
+}
+
+impl<'de, R: BorrowReader<'de> + ?Sized> BorrowReader<'de> for Take<'_, R> {
+    #[inline]
+    fn take_bytes(&mut self, length: usize) -> Result<&'de [u8], DecodeError> {
+        self.claim(length)?;
+        self.reader.take_bytes(length)
+    }
+
+    #[inline]
+    fn peek_remaining(&self) -> Option<&'de [u8]> {
+        let remaining = self.remaining;
+        self.reader
+            .peek_remaining()
+            .map(|bytes| &bytes[..bytes.len().min(remaining)])
+    }
 }
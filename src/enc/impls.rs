@@ -2,6 +2,7 @@ use super::{write::Writer, Encode, Encoder};
 use crate::{
     config::{Endianness, IntEncoding, InternalEndianConfig, InternalIntEncodingConfig},
     error::EncodeError,
+    utils::FixedWidthNumber,
 };
 use core::cmp::Reverse;
 use core::{
@@ -51,6 +52,7 @@ impl Encode for u16 {
             IntEncoding::Variable => {
                 crate::varint::varint_encode_u16(encoder.writer(), E::C::ENDIAN, *self)
             }
+            IntEncoding::Leb128 => crate::varint::leb128_encode_u16(encoder.writer(), *self),
             IntEncoding::Fixed => match E::C::ENDIAN {
                 Endianness::Big => encoder.writer().write(&self.to_be_bytes()),
                 Endianness::Little => encoder.writer().write(&self.to_le_bytes()),
@@ -71,6 +73,7 @@ impl Encode for u32 {
             IntEncoding::Variable => {
                 crate::varint::varint_encode_u32(encoder.writer(), E::C::ENDIAN, *self)
             }
+            IntEncoding::Leb128 => crate::varint::leb128_encode_u32(encoder.writer(), *self),
             IntEncoding::Fixed => match E::C::ENDIAN {
                 Endianness::Big => encoder.writer().write(&self.to_be_bytes()),
                 Endianness::Little => encoder.writer().write(&self.to_le_bytes()),
@@ -91,6 +94,7 @@ impl Encode for u64 {
             IntEncoding::Variable => {
                 crate::varint::varint_encode_u64(encoder.writer(), E::C::ENDIAN, *self)
             }
+            IntEncoding::Leb128 => crate::varint::leb128_encode_u64(encoder.writer(), *self),
             IntEncoding::Fixed => match E::C::ENDIAN {
                 Endianness::Big => encoder.writer().write(&self.to_be_bytes()),
                 Endianness::Little => encoder.writer().write(&self.to_le_bytes()),
@@ -111,6 +115,7 @@ impl Encode for u128 {
             IntEncoding::Variable => {
                 crate::varint::varint_encode_u128(encoder.writer(), E::C::ENDIAN, *self)
             }
+            IntEncoding::Leb128 => crate::varint::leb128_encode_u128(encoder.writer(), *self),
             IntEncoding::Fixed => match E::C::ENDIAN {
                 Endianness::Big => encoder.writer().write(&self.to_be_bytes()),
                 Endianness::Little => encoder.writer().write(&self.to_le_bytes()),
@@ -131,6 +136,7 @@ impl Encode for usize {
             IntEncoding::Variable => {
                 crate::varint::varint_encode_usize(encoder.writer(), E::C::ENDIAN, *self)
             }
+            IntEncoding::Leb128 => crate::varint::leb128_encode_usize(encoder.writer(), *self),
             IntEncoding::Fixed => match E::C::ENDIAN {
                 Endianness::Big => encoder.writer().write(&(*self as u64).to_be_bytes()),
                 Endianness::Little => encoder.writer().write(&(*self as u64).to_le_bytes()),
@@ -163,6 +169,7 @@ impl Encode for i16 {
             IntEncoding::Variable => {
                 crate::varint::varint_encode_i16(encoder.writer(), E::C::ENDIAN, *self)
             }
+            IntEncoding::Leb128 => crate::varint::leb128_encode_i16(encoder.writer(), *self),
             IntEncoding::Fixed => match E::C::ENDIAN {
                 Endianness::Big => encoder.writer().write(&self.to_be_bytes()),
                 Endianness::Little => encoder.writer().write(&self.to_le_bytes()),
@@ -183,6 +190,7 @@ impl Encode for i32 {
             IntEncoding::Variable => {
                 crate::varint::varint_encode_i32(encoder.writer(), E::C::ENDIAN, *self)
             }
+            IntEncoding::Leb128 => crate::varint::leb128_encode_i32(encoder.writer(), *self),
             IntEncoding::Fixed => match E::C::ENDIAN {
                 Endianness::Big => encoder.writer().write(&self.to_be_bytes()),
                 Endianness::Little => encoder.writer().write(&self.to_le_bytes()),
@@ -203,6 +211,7 @@ impl Encode for i64 {
             IntEncoding::Variable => {
                 crate::varint::varint_encode_i64(encoder.writer(), E::C::ENDIAN, *self)
             }
+            IntEncoding::Leb128 => crate::varint::leb128_encode_i64(encoder.writer(), *self),
             IntEncoding::Fixed => match E::C::ENDIAN {
                 Endianness::Big => encoder.writer().write(&self.to_be_bytes()),
                 Endianness::Little => encoder.writer().write(&self.to_le_bytes()),
@@ -223,6 +232,7 @@ impl Encode for i128 {
             IntEncoding::Variable => {
                 crate::varint::varint_encode_i128(encoder.writer(), E::C::ENDIAN, *self)
             }
+            IntEncoding::Leb128 => crate::varint::leb128_encode_i128(encoder.writer(), *self),
             IntEncoding::Fixed => match E::C::ENDIAN {
                 Endianness::Big => encoder.writer().write(&self.to_be_bytes()),
                 Endianness::Little => encoder.writer().write(&self.to_le_bytes()),
@@ -243,6 +253,7 @@ impl Encode for isize {
             IntEncoding::Variable => {
                 crate::varint::varint_encode_isize(encoder.writer(), E::C::ENDIAN, *self)
             }
+            IntEncoding::Leb128 => crate::varint::leb128_encode_isize(encoder.writer(), *self),
             IntEncoding::Fixed => match E::C::ENDIAN {
                 Endianness::Big => encoder.writer().write(&(*self as i64).to_be_bytes()),
                 Endianness::Little => encoder.writer().write(&(*self as i64).to_le_bytes()),
@@ -306,6 +317,9 @@ where
             encoder.writer().write(t)?;
             return Ok(());
         }
+        if let Some(number) = FixedWidthNumber::<T>::new(encoder.config()) {
+            return super::write_number_slice(encoder, number, self);
+        }
 
         for item in self {
             item.encode(encoder)?;
@@ -364,6 +378,8 @@ where
             let array_slice: &[u8] =
                 unsafe { core::slice::from_raw_parts(self.as_ptr().cast(), N) };
             encoder.writer().write(array_slice)
+        } else if let Some(number) = FixedWidthNumber::<T>::new(encoder.config()) {
+            super::write_number_slice(encoder, number, self)
         } else {
             for item in self.iter() {
                 item.encode(encoder)?;
@@ -481,7 +497,7 @@ where
     }
 }
 
-impl<'a, T> Encode for &'a T
+impl<T> Encode for &T
 where
     T: Encode + ?Sized,
 {
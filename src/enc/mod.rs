@@ -5,7 +5,11 @@ mod impl_tuples;
 mod impls;
 
 use self::write::Writer;
-use crate::{config::Config, error::EncodeError, utils::Sealed};
+use crate::{
+    config::{Config, EnumTagType},
+    error::EncodeError,
+    utils::{FixedWidthNumber, Sealed},
+};
 
 pub mod write;
 
@@ -47,7 +51,6 @@ pub use self::encoder::EncoderImpl;
 /// ```
 ///
 /// From here you can add/remove fields, or add custom logic.
-
 pub trait Encode {
     /// Encode a given type.
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError>;
@@ -68,7 +71,7 @@ pub trait Encoder: Sealed {
     fn config(&self) -> &Self::C;
 }
 
-impl<'a, T> Encoder for &'a mut T
+impl<T> Encoder for &mut T
 where
     T: Encoder,
 {
@@ -102,3 +105,88 @@ pub(crate) fn encode_option_variant<E: Encoder, T>(
 pub(crate) fn encode_slice_len<E: Encoder>(encoder: &mut E, len: usize) -> Result<(), EncodeError> {
     (len as u64).encode(encoder)
 }
+
+/// Encodes the bytes written by `encode`, prefixed with their length in the same way as the length of a slice.
+///
+/// This is used by `#[bincode(versioned)]` structs for each version of their fields, by `#[bincode(length_prefixed)]` enums for the fields of each variant, and by [Opaque](crate::Opaque). `encode` is given an encoder with a `dyn Writer`, so that a type that contains itself does not create a new encoder type for each level.
+///
+/// With the `alloc` feature the bytes are written to a buffer once. Without it, `encode` is called twice: once to find the length, and once to write the bytes.
+pub fn encode_length_prefixed<E, F>(encoder: &mut E, encode: F) -> Result<(), EncodeError>
+where
+    E: Encoder,
+    F: FnMut(&mut EncoderImpl<&mut dyn Writer, E::C>) -> Result<(), EncodeError>,
+{
+    write_length_prefixed(encoder, encode)
+}
+
+#[cfg(feature = "alloc")]
+fn write_length_prefixed<E, F>(encoder: &mut E, mut encode: F) -> Result<(), EncodeError>
+where
+    E: Encoder,
+    F: FnMut(&mut EncoderImpl<&mut dyn Writer, E::C>) -> Result<(), EncodeError>,
+{
+    let mut buffer = crate::features::VecWriter::with_capacity(0);
+    encode(&mut EncoderImpl::new(
+        &mut buffer as &mut dyn Writer,
+        *encoder.config(),
+    ))?;
+    let bytes = buffer.collect();
+    encode_slice_len(encoder, bytes.len())?;
+    encoder.writer().write(&bytes)
+}
+
+#[cfg(not(feature = "alloc"))]
+fn write_length_prefixed<E, F>(encoder: &mut E, mut encode: F) -> Result<(), EncodeError>
+where
+    E: Encoder,
+    F: FnMut(&mut EncoderImpl<&mut dyn Writer, E::C>) -> Result<(), EncodeError>,
+{
+    let config = *encoder.config();
+    let mut size = write::SizeWriter::default();
+    encode(&mut EncoderImpl::new(&mut size as &mut dyn Writer, config))?;
+    encode_slice_len(encoder, size.bytes_written)?;
+    encode(&mut EncoderImpl::new(
+        encoder.writer() as &mut dyn Writer,
+        config,
+    ))
+}
+
+/// Writes `items` as one block of bytes. If the byte order of the config is not the native byte order, the bytes are reversed in chunks.
+pub(crate) fn write_number_slice<T, E: Encoder>(
+    encoder: &mut E,
+    number: FixedWidthNumber<T>,
+    items: &[T],
+) -> Result<(), EncodeError> {
+    let bytes = number.as_bytes(items);
+    if number.is_native() {
+        return encoder.writer().write(bytes);
+    }
+    // A `u128` buffer is aligned for every number type
+    let mut buffer = [0u128; 64];
+    // Safety: every bit pattern is a valid `u128`
+    let buffer: &mut [u8] =
+        unsafe { core::slice::from_raw_parts_mut(buffer.as_mut_ptr().cast(), 1024) };
+    for chunk in bytes.chunks(buffer.len()) {
+        let buffer = &mut buffer[..chunk.len()];
+        buffer.copy_from_slice(chunk);
+        number.swap_bytes(buffer);
+        encoder.writer().write(buffer)?;
+    }
+    Ok(())
+}
+
+/// Encodes the variant index of a derived enum as the [EnumTagType] of the config. Returns [EncodeError::EnumTagOverflow] if `tag` does not fit in that type.
+///
+/// This is used by `#[derive(bincode::Encode)]` on enums.
+///
+/// [EnumTagType]: ../config/enum.EnumTagType.html
+#[inline]
+pub fn encode_enum_tag<E: Encoder>(encoder: &mut E, tag: u32) -> Result<(), EncodeError> {
+    let tag_type = encoder.config().enum_tag_type();
+    let overflow = || EncodeError::EnumTagOverflow { tag, tag_type };
+    match tag_type {
+        EnumTagType::U8 => u8::try_from(tag).map_err(|_| overflow())?.encode(encoder),
+        EnumTagType::U16 => u16::try_from(tag).map_err(|_| overflow())?.encode(encoder),
+        _ => tag.encode(encoder),
+    }
+}
@@ -12,7 +12,7 @@ pub trait Writer {
     fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError>;
 }
 
-impl<T: Writer> Writer for &mut T {
+impl<T: Writer + ?Sized> Writer for &mut T {
     #[inline]
     fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
         (**self).write(bytes)
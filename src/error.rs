@@ -15,6 +15,16 @@ pub enum EncodeError {
         type_name: &'static str,
     },
 
+    /// A derived enum was encoded with a variant index that does not fit in the configured [EnumTagType].
+    ///
+    /// [EnumTagType]: ../config/enum.EnumTagType.html
+    EnumTagOverflow {
+        /// The variant index that did not fit
+        tag: u32,
+        /// The type that the variant index should have been encoded as
+        tag_type: crate::config::EnumTagType,
+    },
+
     /// An uncommon error occurred, see the inner text for more information
     Other(&'static str),
 
@@ -58,12 +68,42 @@ pub enum EncodeError {
 
 impl core::fmt::Display for EncodeError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        // TODO: Improve this?
-        write!(f, "{:?}", self)
+        match self {
+            Self::UnexpectedEnd => f.write_str("the writer ran out of storage"),
+            Self::RefCellAlreadyBorrowed { inner, type_name } => {
+                write!(f, "could not borrow the RefCell {}: {}", type_name, inner)
+            }
+            Self::EnumTagOverflow { tag, tag_type } => write!(
+                f,
+                "the variant index {} does not fit in an {} enum tag",
+                tag, tag_type
+            ),
+            Self::Other(message) => f.write_str(message),
+            #[cfg(feature = "alloc")]
+            Self::OtherString(message) => f.write_str(message),
+            #[cfg(feature = "std")]
+            Self::InvalidPathCharacters => f.write_str("the path is not valid UTF-8"),
+            #[cfg(feature = "std")]
+            Self::Io { inner, index } => {
+                write!(f, "io error after writing {} bytes: {}", index, inner)
+            }
+            #[cfg(feature = "std")]
+            Self::LockFailed { type_name } => write!(f, "could not lock the {}", type_name),
+            #[cfg(feature = "std")]
+            Self::InvalidSystemTime { inner, time } => {
+                write!(f, "invalid SystemTime {:?}: {}", time, inner)
+            }
+            #[cfg(feature = "serde")]
+            Self::Serde(inner) => write!(f, "{}", inner),
+        }
     }
 }
 
 /// Errors that can be encountered by decoding a type
+///
+/// These errors do not contain the offset in the input where decoding failed. Use the `*_with_context` functions, like [decode_from_slice_with_context], to get the offset and the path to the value that failed in a [ContextError].
+///
+/// [decode_from_slice_with_context]: ../fn.decode_from_slice_with_context.html
 #[non_exhaustive]
 #[derive(Debug)]
 pub enum DecodeError {
@@ -80,6 +120,23 @@ pub enum DecodeError {
     /// The given configuration limit was exceeded
     LimitExceeded,
 
+    /// The value was nested deeper than the maximum depth of the configuration
+    DepthLimitExceeded,
+
+    /// The value was decoded, but the input contained more bytes after it.
+    TrailingBytes {
+        /// The amount of bytes that were left after the decoded value
+        remaining: usize,
+    },
+
+    /// The fingerprint in the input does not match the fingerprint of the type that is being decoded. This means that the data was encoded with a different layout of the type.
+    FingerprintMismatch {
+        /// The fingerprint of the type that is being decoded
+        expected: u64,
+        /// The fingerprint that was found in the input
+        found: u64,
+    },
+
     /// Invalid type was found. The decoder tried to read type `expected`, but found type `found` instead.
     InvalidIntegerType {
         /// The type that was being read from the reader
@@ -134,6 +191,39 @@ pub enum DecodeError {
     /// cause this error.
     OutsideUsizeRange(u64),
 
+    /// The offset table of an [Indexed](../struct.Indexed.html) sequence contains an offset that is smaller than the offset before it, or larger than the data of the sequence.
+    InvalidIndexedOffset {
+        /// The index of the element that the offset belongs to
+        index: usize,
+        /// The offset that was found
+        offset: u64,
+    },
+
+    /// A slice of numbers like `&[u32]` could not be borrowed from the input. This happens when the numbers are not encoded as their native bytes, because the config uses `Varint` encoding or a different endianness than the target, or when the numbers are not aligned in the input.
+    ///
+    /// Decode a `Cow<[T]>` or a `Vec<T>` instead, which copy the numbers when they can not be borrowed.
+    CannotBorrowSlice {
+        /// The type of the numbers in the slice
+        type_name: &'static str,
+    },
+
+    /// The config uses the canonical encoding, but the keys of a map or set were not in ascending order.
+    ///
+    /// See [with_canonical_encoding](../config/struct.Configuration.html#method.with_canonical_encoding) for the order of each type.
+    UnsortedKeys {
+        /// The type of the map or set that was being decoded
+        type_name: &'static str,
+    },
+
+    /// The config uses strict decoding, but a map or set contained the same key more than once.
+    DuplicateMapKey {
+        /// The type of the map or set that was being decoded
+        type_name: &'static str,
+    },
+
+    /// The config uses strict decoding, but a varint was encoded with more bytes than needed for its value.
+    NonCanonicalVarint,
+
     /// Tried to decode an enum with no variants
     EmptyEnum {
         /// The type that was being decoded
@@ -178,6 +268,15 @@ pub enum DecodeError {
         additional: usize,
     },
 
+    /// A frame of the [framed](../framed/index.html) module was read completely, but the value in it needed more bytes than the frame contains.
+    ///
+    /// Unlike [UnexpectedEnd](#variant.UnexpectedEnd), the frame has been consumed and the next call to `decode` continues with the next frame.
+    #[cfg(feature = "std")]
+    FrameTooShort {
+        /// Gives an estimate of how many extra bytes the value needed.
+        additional: usize,
+    },
+
     /// An uncommon error occurred, see the inner text for more information
     Other(&'static str),
 
@@ -192,8 +291,112 @@ pub enum DecodeError {
 
 impl core::fmt::Display for DecodeError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        // TODO: Improve this?
-        write!(f, "{:?}", self)
+        match self {
+            Self::UnexpectedEnd { additional } => write!(
+                f,
+                "unexpected end of input, about {} more bytes were needed",
+                additional
+            ),
+            Self::LimitExceeded => f.write_str("the configured byte limit was exceeded"),
+            Self::DepthLimitExceeded => {
+                f.write_str("the value is nested deeper than the configured depth limit")
+            }
+            Self::TrailingBytes { remaining } => write!(
+                f,
+                "{} bytes were left in the input after the decoded value",
+                remaining
+            ),
+            Self::FingerprintMismatch { expected, found } => write!(
+                f,
+                "fingerprint mismatch, expected {:#018x}, found {:#018x}",
+                expected, found
+            ),
+            Self::InvalidIntegerType { expected, found } => {
+                write!(f, "invalid varint, expected {}, found {}", expected, found)
+            }
+            Self::NonZeroTypeIsZero { non_zero_type } => {
+                write!(f, "a non-zero {} was zero", non_zero_type)
+            }
+            Self::UnexpectedVariant {
+                type_name,
+                allowed,
+                found,
+            } => write!(
+                f,
+                "invalid variant index of {}, expected {}, found {}",
+                type_name, allowed, found
+            ),
+            Self::Utf8 { inner } => write!(f, "invalid UTF-8: {}", inner),
+            Self::InvalidCharEncoding(bytes) => write!(f, "invalid char encoding {:02x?}", bytes),
+            Self::InvalidBooleanValue(value) => {
+                write!(f, "invalid bool value, expected 0 or 1, found {}", value)
+            }
+            Self::ArrayLengthMismatch { required, found } => write!(
+                f,
+                "array length mismatch, expected {}, found {}",
+                required, found
+            ),
+            Self::OutsideUsizeRange(value) => {
+                write!(f, "the value {} does not fit in an usize", value)
+            }
+            Self::InvalidIndexedOffset { index, offset } => write!(
+                f,
+                "invalid offset {} for item {} of an indexed sequence",
+                offset, index
+            ),
+            Self::CannotBorrowSlice { type_name } => write!(
+                f,
+                "cannot borrow a slice of {} from the input, decode a Cow or a Vec instead",
+                type_name
+            ),
+            Self::UnsortedKeys { type_name } => {
+                write!(f, "the keys of the {} are not sorted", type_name)
+            }
+            Self::DuplicateMapKey { type_name } => {
+                write!(f, "the {} contains a duplicate key", type_name)
+            }
+            Self::NonCanonicalVarint => {
+                f.write_str("a varint was encoded with more bytes than needed")
+            }
+            Self::EmptyEnum { type_name } => {
+                write!(
+                    f,
+                    "cannot decode the enum {}, it has no variants",
+                    type_name
+                )
+            }
+            Self::InvalidDuration { secs, nanos } => write!(
+                f,
+                "a duration of {} seconds and {} nanoseconds overflows",
+                secs, nanos
+            ),
+            Self::InvalidSystemTime { duration } => write!(
+                f,
+                "UNIX_EPOCH plus {:?} does not fit in a SystemTime",
+                duration
+            ),
+            #[cfg(feature = "std")]
+            Self::CStringNulError { position } => {
+                write!(f, "the CString contains a nul byte at {}", position)
+            }
+            #[cfg(feature = "std")]
+            Self::Io { inner, additional } => write!(
+                f,
+                "io error while reading {} more bytes: {}",
+                additional, inner
+            ),
+            #[cfg(feature = "std")]
+            Self::FrameTooShort { additional } => write!(
+                f,
+                "the value needed about {} more bytes than its frame contains",
+                additional
+            ),
+            Self::Other(message) => f.write_str(message),
+            #[cfg(feature = "alloc")]
+            Self::OtherString(message) => f.write_str(message),
+            #[cfg(feature = "serde")]
+            Self::Serde(inner) => write!(f, "{}", inner),
+        }
     }
 }
 
@@ -212,6 +415,179 @@ impl DecodeError {
             other => other,
         }
     }
+
+    /// If the current error is `InvalidIntegerType`, change the `expected` value to `expected`. This is used by src/varint/leb128.rs, which decodes the smaller integer types as an `u64`.
+    pub(crate) fn change_expected_integer_type(self, expected: IntegerType) -> DecodeError {
+        match self {
+            Self::InvalidIntegerType { found, .. } => Self::InvalidIntegerType { expected, found },
+            other => other,
+        }
+    }
+}
+
+/// A [DecodeError] together with the path to the value that failed and the offset in the input where it failed.
+///
+/// This is returned by the `*_with_context` functions, like [decode_from_slice_with_context]. The other decode functions return the [DecodeError] on its own, because collecting the path has a small cost for every value that fails.
+///
+/// ```
+/// # #[cfg(all(feature = "alloc", feature = "derive"))] {
+/// use bincode::error::{DecodeError, PathSegment};
+///
+/// #[derive(bincode::Decode, Debug)]
+/// struct Message {
+///     id: u32,
+///     names: Vec<String>,
+/// }
+///
+/// // The second name is not valid UTF-8
+/// let bytes = [7, 2, 1, b'a', 1, 0xff];
+/// let config = bincode::config::standard();
+/// let error = bincode::decode_from_slice_with_context::<Message, _>(&bytes, config).unwrap_err();
+/// assert!(matches!(error.error(), DecodeError::Utf8 { .. }));
+/// assert!(matches!(
+///     error.path(),
+///     [PathSegment::Field { field: "names", .. }, PathSegment::Index(1)]
+/// ));
+/// assert_eq!(error.offset(), 6);
+///
+/// // The other decode functions return the same error without the context
+/// let error = bincode::decode_from_slice::<Message, _>(&bytes, config).unwrap_err();
+/// assert!(matches!(error, DecodeError::Utf8 { .. }));
+/// # }
+/// ```
+///
+/// [decode_from_slice_with_context]: ../fn.decode_from_slice_with_context.html
+#[derive(Debug)]
+pub struct ContextError {
+    error: DecodeError,
+    #[cfg(feature = "alloc")]
+    path: alloc::vec::Vec<PathSegment>,
+    offset: usize,
+}
+
+impl ContextError {
+    #[cfg(feature = "alloc")]
+    pub(crate) fn new(
+        error: DecodeError,
+        path: alloc::vec::Vec<PathSegment>,
+        offset: usize,
+    ) -> Self {
+        Self {
+            error,
+            path,
+            offset,
+        }
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub(crate) fn new(error: DecodeError, offset: usize) -> Self {
+        Self { error, offset }
+    }
+
+    /// The error that caused decoding to fail
+    pub fn error(&self) -> &DecodeError {
+        &self.error
+    }
+
+    /// The error that caused decoding to fail, see [error](#method.error)
+    pub fn into_error(self) -> DecodeError {
+        self.error
+    }
+
+    /// The path from the type that was being decoded to the value that failed, starting at the outermost value. This is empty if the error did not happen inside a field of a derived type or an item of a collection, or if the `alloc` feature is disabled.
+    #[cfg(feature = "alloc")]
+    pub fn path(&self) -> &[PathSegment] {
+        &self.path
+    }
+
+    /// The path from the type that was being decoded to the value that failed. Without the `alloc` feature there is nowhere to store the path, so this is always empty.
+    #[cfg(not(feature = "alloc"))]
+    pub fn path(&self) -> &[PathSegment] {
+        &[]
+    }
+
+    /// The amount of bytes that were read from the input when decoding failed
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+/// Shows the error, followed by the path to the value that failed and the offset. For example `invalid bool value, expected 0 or 1, found 2 at my_crate::Drawing.shapes[1]::Polygon.1, offset 12`.
+impl core::fmt::Display for ContextError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.error)?;
+        let path = self.path();
+        if let Some(first) = path.first() {
+            f.write_str(" at ")?;
+            match first {
+                PathSegment::Field { type_name, .. }
+                | PathSegment::VariantField { type_name, .. } => f.write_str(type_name)?,
+                PathSegment::Index(_) => {}
+            }
+            for segment in path {
+                write!(f, "{}", segment)?;
+            }
+        }
+        write!(f, ", offset {}", self.offset)
+    }
+}
+
+/// A step in the [path](struct.ContextError.html#method.path) of a [ContextError].
+///
+/// Implementations of [Decode] add the steps to the path with [Decoder::error_at] when a value inside them fails to decode.
+///
+/// [Decode]: ../de/trait.Decode.html
+/// [Decoder::error_at]: ../de/trait.Decoder.html#tymethod.error_at
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    /// A field of a struct
+    Field {
+        /// The type name of the struct
+        type_name: &'static str,
+        /// The name of the field, or its index for a tuple struct
+        field: &'static str,
+    },
+    /// A field of an enum variant
+    VariantField {
+        /// The type name of the enum
+        type_name: &'static str,
+        /// The name of the variant
+        variant: &'static str,
+        /// The name of the field, or its index for a tuple variant
+        field: &'static str,
+    },
+    /// An item of a collection like a `Vec` or an array
+    Index(usize),
+}
+
+impl PathSegment {
+    /// The field `field` of the struct `T`
+    pub fn field<T: ?Sized>(field: &'static str) -> Self {
+        Self::Field {
+            type_name: core::any::type_name::<T>(),
+            field,
+        }
+    }
+
+    /// The field `field` of the variant `variant` of the enum `T`
+    pub fn variant_field<T: ?Sized>(variant: &'static str, field: &'static str) -> Self {
+        Self::VariantField {
+            type_name: core::any::type_name::<T>(),
+            variant,
+            field,
+        }
+    }
+}
+
+impl core::fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Field { field, .. } => write!(f, ".{}", field),
+            Self::VariantField { variant, field, .. } => write!(f, "::{}.{}", variant, field),
+            Self::Index(index) => write!(f, "[{}]", index),
+        }
+    }
 }
 
 /// Indicates which enum variants are allowed
@@ -225,6 +601,24 @@ pub enum AllowedEnumVariants {
     Allowed(&'static [u32]),
 }
 
+impl core::fmt::Display for AllowedEnumVariants {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Range { min, max } => write!(f, "{}..={}", min, max),
+            Self::Allowed(allowed) => {
+                f.write_str("one of ")?;
+                for (i, variant) in allowed.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", variant)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
 /// Integer types. Used by [DecodeError]. These types have no purpose other than being shown in errors.
 #[non_exhaustive]
 #[derive(Debug, PartialEq, Eq)]
@@ -263,3 +657,23 @@ impl IntegerType {
         }
     }
 }
+
+impl core::fmt::Display for IntegerType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::U8 => "u8",
+            Self::U16 => "u16",
+            Self::U32 => "u32",
+            Self::U64 => "u64",
+            Self::U128 => "u128",
+            Self::Usize => "usize",
+            Self::I8 => "i8",
+            Self::I16 => "i16",
+            Self::I32 => "i32",
+            Self::I64 => "i64",
+            Self::I128 => "i128",
+            Self::Isize => "isize",
+            Self::Reserved => "a reserved discriminant",
+        })
+    }
+}
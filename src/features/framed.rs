@@ -0,0 +1,230 @@
+//! Length-delimited framing for streams of bincode messages.
+//!
+//! A [FrameEncoder] writes every value as a frame: a length prefix followed by the encoded value. The length prefix is encoded the same way bincode encodes the length of a `Vec<u8>`, so it is a varint or a `u64` depending on the [IntEncoding] of the config.
+//!
+//! A [FrameDecoder] reads these frames back one at a time. Every frame is decoded with its own decoder, so the byte limit of the config applies to each frame separately. Frames that are larger than the limit are rejected before their payload is read.
+//!
+//! ```
+//! let config = bincode::config::standard();
+//!
+//! let mut stream = Vec::new();
+//! let mut encoder = bincode::framed::FrameEncoder::new(&mut stream, config);
+//! encoder.encode(&String::from("Hello")).unwrap();
+//! encoder.encode(&String::from("world")).unwrap();
+//!
+//! let mut decoder = bincode::framed::FrameDecoder::new(stream.as_slice(), config);
+//! assert_eq!(decoder.decode::<String>().unwrap(), Some(String::from("Hello")));
+//! assert_eq!(decoder.decode::<String>().unwrap(), Some(String::from("world")));
+//! assert_eq!(decoder.decode::<String>().unwrap(), None);
+//! ```
+//!
+//! [IntEncoding]: ../config/enum.IntEncoding.html
+
+use crate::{
+    config::Config,
+    de::{read::SliceReader, Decode, Decoder, DecoderImpl},
+    enc::{write::SizeWriter, Encode, EncoderImpl},
+    error::{DecodeError, EncodeError},
+    IoWriter,
+};
+use std::vec::Vec;
+
+const MAX_FILL: usize = 64 * 1024;
+
+/// Writes values as length-delimited frames into any type that implements `std::io::Write`.
+///
+/// See the [module documentation](index.html) for more information.
+pub struct FrameEncoder<W, C> {
+    writer: W,
+    config: C,
+}
+
+impl<W: std::io::Write, C: Config> FrameEncoder<W, C> {
+    /// Create a new `FrameEncoder` that writes into the given writer.
+    pub const fn new(writer: W, config: C) -> Self {
+        Self { writer, config }
+    }
+
+    /// Encode the given value as a single frame. Returns the amount of bytes written, including the length prefix.
+    pub fn encode<E: Encode>(&mut self, val: &E) -> Result<usize, EncodeError> {
+        let len = {
+            let mut size_writer = EncoderImpl::new(SizeWriter::default(), self.config);
+            val.encode(&mut size_writer)?;
+            size_writer.into_writer().bytes_written
+        };
+
+        let mut encoder = EncoderImpl::new(IoWriter::new(&mut self.writer), self.config);
+        crate::enc::encode_slice_len(&mut encoder, len)?;
+        val.encode(&mut encoder)?;
+        Ok(encoder.into_writer().bytes_written())
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Reads length-delimited frames, as written by [FrameEncoder], from any type that implements `std::io::Read`.
+///
+/// The decoder never reads past the end of the current frame. Every call to `read` requests only the bytes that are still missing, so wrapping a slow reader in a `std::io::BufReader` is recommended.
+///
+/// If the reader returns an error or reaches its end in the middle of a frame, the bytes of that frame that were already read are kept. Calling [decode] again continues with the same frame, so no stream position is lost when e.g. a non-blocking socket returns `WouldBlock`.
+///
+/// See the [module documentation](index.html) for more information.
+///
+/// [decode]: #method.decode
+pub struct FrameDecoder<R, C> {
+    reader: R,
+    config: C,
+    buffer: Vec<u8>,
+    skip: usize,
+}
+
+impl<R: std::io::Read, C: Config> FrameDecoder<R, C> {
+    /// Create a new `FrameDecoder` that reads from the given reader.
+    pub const fn new(reader: R, config: C) -> Self {
+        Self {
+            reader,
+            config,
+            buffer: Vec::new(),
+            skip: 0,
+        }
+    }
+
+    /// Decode the next frame from the reader.
+    ///
+    /// Returns `Ok(None)` if the reader reached its end exactly at a frame boundary.
+    ///
+    /// If the reader reaches its end in the middle of a frame, [DecodeError::UnexpectedEnd] is returned. If the reader returns an error, [DecodeError::Io] is returned. In both cases the partial frame is kept and the next call to `decode` will continue reading it.
+    ///
+    /// Once a frame has been read completely it is consumed, even if its payload fails to decode. A payload that ends before the value does returns [DecodeError::FrameTooShort], and a payload with bytes left after the value returns [DecodeError::TrailingBytes].
+    ///
+    /// If the frame is larger than the limit of the config, [DecodeError::LimitExceeded] is returned and the payload of that frame will be skipped by the next call to `decode`.
+    pub fn decode<D: Decode>(&mut self) -> Result<Option<D>, DecodeError> {
+        while self.skip > 0 {
+            let mut buffer = [0u8; 1024];
+            let len = self.skip.min(buffer.len());
+            match self.read_some(&mut buffer[..len])? {
+                0 => {
+                    return Err(DecodeError::UnexpectedEnd {
+                        additional: self.skip,
+                    })
+                }
+                n => self.skip -= n,
+            }
+        }
+
+        let (prefix_len, frame_len) = loop {
+            let mut decoder = DecoderImpl::new(SliceReader::new(&self.buffer), self.config);
+            match crate::de::decode_slice_len(&mut decoder) {
+                Ok(frame_len) => {
+                    let prefix_len = self.buffer.len() - decoder.reader().slice.len();
+                    break (prefix_len, frame_len);
+                }
+                Err(DecodeError::UnexpectedEnd { additional }) => {
+                    if !self.fill(additional)? {
+                        return Ok(None);
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+        };
+
+        if matches!(self.config.limit(), Some(limit) if frame_len > limit) {
+            self.buffer.clear();
+            self.skip = frame_len;
+            return Err(DecodeError::LimitExceeded);
+        }
+
+        let total_len = prefix_len
+            .checked_add(frame_len)
+            .ok_or(DecodeError::OutsideUsizeRange(frame_len as u64))?;
+        while self.buffer.len() < total_len {
+            // Don't trust the length prefix with a huge allocation, grow the buffer as the data comes in
+            self.fill((total_len - self.buffer.len()).min(MAX_FILL))?;
+        }
+
+        let result =
+            crate::decode_from_slice_exact(&self.buffer[prefix_len..total_len], self.config);
+        self.buffer.clear();
+        match result {
+            Ok(value) => Ok(Some(value)),
+            // The frame is already consumed, so reading more bytes would not help
+            Err(DecodeError::UnexpectedEnd { additional }) => {
+                Err(DecodeError::FrameTooShort { additional })
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Returns `true` if some, but not all, bytes of the next frame have been read.
+    pub fn has_partial_frame(&self) -> bool {
+        !self.buffer.is_empty() || self.skip > 0
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Returns the underlying reader. Any bytes of a partial frame are lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Read up to `additional` bytes into the buffer. Returns `false` if the reader is at its end and no frame has been started.
+    fn fill(&mut self, additional: usize) -> Result<bool, DecodeError> {
+        let start = self.buffer.len();
+        self.buffer.resize(start + additional, 0);
+        let mut buffer = core::mem::take(&mut self.buffer);
+        let result = self.read_some(&mut buffer[start..]);
+        self.buffer = buffer;
+
+        let read = match result {
+            Ok(read) => read,
+            Err(e) => {
+                self.buffer.truncate(start);
+                return Err(e);
+            }
+        };
+        self.buffer.truncate(start + read);
+        if read > 0 {
+            Ok(true)
+        } else if start == 0 {
+            Ok(false)
+        } else {
+            Err(DecodeError::UnexpectedEnd { additional })
+        }
+    }
+
+    fn read_some(&mut self, bytes: &mut [u8]) -> Result<usize, DecodeError> {
+        loop {
+            match self.reader.read(bytes) {
+                Ok(n) => return Ok(n),
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(inner) => {
+                    return Err(DecodeError::Io {
+                        inner,
+                        additional: bytes.len(),
+                    })
+                }
+            }
+        }
+    }
+}
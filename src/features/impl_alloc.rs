@@ -1,12 +1,16 @@
 use crate::{
-    de::{read::Reader, BorrowDecoder, Decode, Decoder},
+    config::IntEncoding,
+    de::{read::Reader, BorrowDecoder, Decode, Decoder, NumberSlice},
     enc::{
         self,
         write::{SizeWriter, Writer},
         Encode, Encoder,
     },
-    error::{DecodeError, EncodeError},
-    impl_borrow_decode, BorrowDecode, Config,
+    error::{DecodeError, EncodeError, PathSegment},
+    impl_borrow_decode,
+    schema::{schema_of, Schema, Type},
+    utils::FixedWidthNumber,
+    BorrowDecode, Config,
 };
 use alloc::{
     borrow::{Cow, ToOwned},
@@ -19,6 +23,7 @@ use alloc::{
 
 #[cfg(target_has_atomic = "ptr")]
 use alloc::sync::Arc;
+use core::cmp::Ordering;
 
 #[derive(Default)]
 pub(crate) struct VecWriter {
@@ -70,6 +75,10 @@ where
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         Ok(Vec::<T>::decode(decoder)?.into())
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::skip_seq::<T, D>(decoder)
+    }
 }
 impl<'de, T> BorrowDecode<'de> for BinaryHeap<T>
 where
@@ -78,6 +87,10 @@ where
     fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
         Ok(Vec::<T>::borrow_decode(decoder)?.into())
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::borrow_skip_seq::<T, D>(decoder)
+    }
 }
 
 impl<T> Encode for BinaryHeap<T>
@@ -94,6 +107,30 @@ where
     }
 }
 
+impl<T: Schema> Schema for BinaryHeap<T> {
+    const SCHEMA: Type = Type::Seq(schema_of::<T>);
+}
+
+/// With the canonical encoding, checks that `key` comes after `last`, the last key that was decoded into the map or set `M`
+fn check_sorted_key<M, K: Ord, D: Decoder>(
+    decoder: &D,
+    last: Option<&K>,
+    key: &K,
+) -> Result<(), DecodeError> {
+    match last {
+        Some(last) if decoder.config().canonical() => match last.cmp(key) {
+            Ordering::Less => Ok(()),
+            Ordering::Equal => Err(DecodeError::DuplicateMapKey {
+                type_name: core::any::type_name::<M>(),
+            }),
+            Ordering::Greater => Err(DecodeError::UnsortedKeys {
+                type_name: core::any::type_name::<M>(),
+            }),
+        },
+        _ => Ok(()),
+    }
+}
+
 impl<K, V> Decode for BTreeMap<K, V>
 where
     K: Decode + Ord,
@@ -103,17 +140,25 @@ where
         let len = crate::de::decode_slice_len(decoder)?;
         decoder.claim_container_read::<(K, V)>(len)?;
 
+        decoder.increase_depth()?;
         let mut map = BTreeMap::new();
         for _ in 0..len {
             // See the documentation on `unclaim_bytes_read` as to why we're doing this here
             decoder.unclaim_bytes_read(core::mem::size_of::<(K, V)>());
 
             let key = K::decode(decoder)?;
+            check_sorted_key::<Self, _, D>(decoder, map.keys().next_back(), &key)?;
             let value = V::decode(decoder)?;
-            map.insert(key, value);
+            let inserted = map.insert(key, value).is_none();
+            crate::de::check_duplicate_key::<Self, D>(decoder, inserted)?;
         }
+        decoder.decrease_depth();
         Ok(map)
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::skip_map::<K, V, D>(decoder)
+    }
 }
 impl<'de, K, V> BorrowDecode<'de> for BTreeMap<K, V>
 where
@@ -124,17 +169,25 @@ where
         let len = crate::de::decode_slice_len(decoder)?;
         decoder.claim_container_read::<(K, V)>(len)?;
 
+        decoder.increase_depth()?;
         let mut map = BTreeMap::new();
         for _ in 0..len {
             // See the documentation on `unclaim_bytes_read` as to why we're doing this here
             decoder.unclaim_bytes_read(core::mem::size_of::<(K, V)>());
 
             let key = K::borrow_decode(decoder)?;
+            check_sorted_key::<Self, _, D>(decoder, map.keys().next_back(), &key)?;
             let value = V::borrow_decode(decoder)?;
-            map.insert(key, value);
+            let inserted = map.insert(key, value).is_none();
+            crate::de::check_duplicate_key::<Self, D>(decoder, inserted)?;
         }
+        decoder.decrease_depth();
         Ok(map)
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::borrow_skip_map::<K, V, D>(decoder)
+    }
 }
 
 impl<K, V> Encode for BTreeMap<K, V>
@@ -152,6 +205,13 @@ where
     }
 }
 
+impl<K: Schema, V: Schema> Schema for BTreeMap<K, V> {
+    const SCHEMA: Type = Type::Map {
+        key: schema_of::<K>,
+        value: schema_of::<V>,
+    };
+}
+
 impl<T> Decode for BTreeSet<T>
 where
     T: Decode + Ord,
@@ -160,16 +220,24 @@ where
         let len = crate::de::decode_slice_len(decoder)?;
         decoder.claim_container_read::<T>(len)?;
 
+        decoder.increase_depth()?;
         let mut map = BTreeSet::new();
         for _ in 0..len {
             // See the documentation on `unclaim_bytes_read` as to why we're doing this here
             decoder.unclaim_bytes_read(core::mem::size_of::<T>());
 
             let key = T::decode(decoder)?;
-            map.insert(key);
+            check_sorted_key::<Self, _, D>(decoder, map.iter().next_back(), &key)?;
+            let inserted = map.insert(key);
+            crate::de::check_duplicate_key::<Self, D>(decoder, inserted)?;
         }
+        decoder.decrease_depth();
         Ok(map)
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::skip_seq::<T, D>(decoder)
+    }
 }
 impl<'de, T> BorrowDecode<'de> for BTreeSet<T>
 where
@@ -179,16 +247,24 @@ where
         let len = crate::de::decode_slice_len(decoder)?;
         decoder.claim_container_read::<T>(len)?;
 
+        decoder.increase_depth()?;
         let mut map = BTreeSet::new();
         for _ in 0..len {
             // See the documentation on `unclaim_bytes_read` as to why we're doing this here
             decoder.unclaim_bytes_read(core::mem::size_of::<T>());
 
             let key = T::borrow_decode(decoder)?;
-            map.insert(key);
+            check_sorted_key::<Self, _, D>(decoder, map.iter().next_back(), &key)?;
+            let inserted = map.insert(key);
+            crate::de::check_duplicate_key::<Self, D>(decoder, inserted)?;
         }
+        decoder.decrease_depth();
         Ok(map)
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::borrow_skip_seq::<T, D>(decoder)
+    }
 }
 
 impl<T> Encode for BTreeSet<T>
@@ -204,6 +280,10 @@ where
     }
 }
 
+impl<T: Schema> Schema for BTreeSet<T> {
+    const SCHEMA: Type = Type::Seq(schema_of::<T>);
+}
+
 impl<T> Decode for VecDeque<T>
 where
     T: Decode,
@@ -211,6 +291,10 @@ where
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         Ok(Vec::<T>::decode(decoder)?.into())
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::skip_seq::<T, D>(decoder)
+    }
 }
 impl<'de, T> BorrowDecode<'de> for VecDeque<T>
 where
@@ -219,6 +303,10 @@ where
     fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
         Ok(Vec::<T>::borrow_decode(decoder)?.into())
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::borrow_skip_seq::<T, D>(decoder)
+    }
 }
 
 impl<T> Encode for VecDeque<T>
@@ -248,33 +336,88 @@ where
     }
 }
 
+impl<T: Schema> Schema for VecDeque<T> {
+    const SCHEMA: Type = Type::Seq(schema_of::<T>);
+}
+
 impl<T> Decode for Vec<T>
 where
     T: Decode,
 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         let len = crate::de::decode_slice_len(decoder)?;
+        decode_vec_items(decoder, len)
+    }
 
-        if unty::type_equal::<T, u8>() {
-            decoder.claim_container_read::<T>(len)?;
-            // optimize for reading u8 vecs
-            let mut vec = alloc::vec![0u8; len];
-            decoder.reader().read(&mut vec)?;
-            // Safety: Vec<T> is Vec<u8>
-            Ok(unsafe { core::mem::transmute(vec) })
-        } else {
-            decoder.claim_container_read::<T>(len)?;
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::skip_seq::<T, D>(decoder)
+    }
+}
 
-            let mut vec = Vec::with_capacity(len);
-            for _ in 0..len {
-                // See the documentation on `unclaim_bytes_read` as to why we're doing this here
-                decoder.unclaim_bytes_read(core::mem::size_of::<T>());
+/// Decodes the `len` items of a `Vec<T>`, after its length has been decoded
+fn decode_vec_items<T: Decode, D: Decoder>(
+    decoder: &mut D,
+    len: usize,
+) -> Result<Vec<T>, DecodeError> {
+    if unty::type_equal::<T, u8>() {
+        decoder.claim_container_read::<T>(len)?;
+        // optimize for reading u8 vecs
+        let mut vec = alloc::vec![0u8; len];
+        decoder.reader().read(&mut vec)?;
+        // Safety: Vec<T> is Vec<u8>
+        Ok(unsafe { core::mem::transmute::<Vec<u8>, Vec<T>>(vec) })
+    } else if let Some(number) = FixedWidthNumber::<T>::new(decoder.config()) {
+        decoder.claim_container_read::<T>(len)?;
+        // optimize for reading vecs of fixed width numbers
+        let mut vec = number.zeroed_vec(len);
+        crate::de::read_number_slice(decoder, number, &mut vec)?;
+        Ok(vec)
+    } else if let Some(vec) = decode_varint_vec(decoder, len)? {
+        Ok(vec)
+    } else {
+        decoder.claim_container_read::<T>(len)?;
 
-                vec.push(T::decode(decoder)?);
-            }
-            Ok(vec)
+        decoder.increase_depth()?;
+        let mut vec = Vec::with_capacity(len);
+        for index in 0..len {
+            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+            decoder.unclaim_bytes_read(core::mem::size_of::<T>());
+
+            vec.push(
+                T::decode(decoder).map_err(|e| decoder.error_at(e, PathSegment::Index(index)))?,
+            );
         }
+        decoder.decrease_depth();
+        Ok(vec)
+    }
+}
+
+/// Decodes the `len` items of a `Vec<u32>` or `Vec<u64>` with batched varint decoding. Returns `None` if `T` is another type, or if the config does not use varint encoding or uses strict decoding.
+fn decode_varint_vec<T, D: Decoder>(
+    decoder: &mut D,
+    len: usize,
+) -> Result<Option<Vec<T>>, DecodeError> {
+    macro_rules! batch {
+        ($ty:ty) => {
+            if unty::type_equal::<T, $ty>() {
+                decoder.claim_container_read::<$ty>(len)?;
+                decoder.increase_depth()?;
+                let mut vec = alloc::vec![0 as $ty; len];
+                crate::varint::varint_decode_slice(decoder, &mut vec)?;
+                decoder.decrease_depth();
+                // Safety: Vec<T> is Vec<$ty>
+                return Ok(Some(unsafe { core::mem::transmute::<Vec<$ty>, Vec<T>>(vec) }));
+            }
+        };
     }
+
+    if decoder.config().int_encoding() == IntEncoding::Variable
+        && !decoder.config().strict_decoding()
+    {
+        batch!(u32);
+        batch!(u64);
+    }
+    Ok(None)
 }
 
 impl<'de, T> BorrowDecode<'de> for Vec<T>
@@ -290,20 +433,37 @@ where
             let mut vec = alloc::vec![0u8; len];
             decoder.reader().read(&mut vec)?;
             // Safety: Vec<T> is Vec<u8>
-            Ok(unsafe { core::mem::transmute(vec) })
+            Ok(unsafe { core::mem::transmute::<Vec<u8>, Vec<T>>(vec) })
+        } else if let Some(number) = FixedWidthNumber::<T>::new(decoder.config()) {
+            decoder.claim_container_read::<T>(len)?;
+            // optimize for reading vecs of fixed width numbers
+            let mut vec = number.zeroed_vec(len);
+            crate::de::read_number_slice(decoder, number, &mut vec)?;
+            Ok(vec)
+        } else if let Some(vec) = decode_varint_vec(decoder, len)? {
+            Ok(vec)
         } else {
             decoder.claim_container_read::<T>(len)?;
 
+            decoder.increase_depth()?;
             let mut vec = Vec::with_capacity(len);
-            for _ in 0..len {
+            for index in 0..len {
                 // See the documentation on `unclaim_bytes_read` as to why we're doing this here
                 decoder.unclaim_bytes_read(core::mem::size_of::<T>());
 
-                vec.push(T::borrow_decode(decoder)?);
+                vec.push(
+                    T::borrow_decode(decoder)
+                        .map_err(|e| decoder.error_at(e, PathSegment::Index(index)))?,
+                );
             }
+            decoder.decrease_depth();
             Ok(vec)
         }
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::borrow_skip_seq::<T, D>(decoder)
+    }
 }
 
 impl<T> Encode for Vec<T>
@@ -317,6 +477,8 @@ where
             let slice: &[u8] = unsafe { core::mem::transmute(self.as_slice()) };
             encoder.writer().write(slice)?;
             Ok(())
+        } else if let Some(number) = FixedWidthNumber::<T>::new(encoder.config()) {
+            crate::enc::write_number_slice(encoder, number, self)
         } else {
             for item in self.iter() {
                 item.encode(encoder)?;
@@ -326,6 +488,10 @@ where
     }
 }
 
+impl<T: Schema> Schema for Vec<T> {
+    const SCHEMA: Type = Type::Seq(schema_of::<T>);
+}
+
 impl Decode for String {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         let bytes = Vec::<u8>::decode(decoder)?;
@@ -333,6 +499,10 @@ impl Decode for String {
             inner: e.utf8_error(),
         })
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::skip_seq::<u8, D>(decoder)
+    }
 }
 impl_borrow_decode!(String);
 
@@ -340,6 +510,10 @@ impl Decode for Box<str> {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         String::decode(decoder).map(String::into_boxed_str)
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::skip_seq::<u8, D>(decoder)
+    }
 }
 impl_borrow_decode!(Box<str>);
 
@@ -349,23 +523,45 @@ impl Encode for String {
     }
 }
 
+impl Schema for String {
+    const SCHEMA: Type = Type::Str;
+}
+
 impl<T> Decode for Box<T>
 where
     T: Decode,
 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        decoder.increase_depth()?;
         let t = T::decode(decoder)?;
+        decoder.decrease_depth();
         Ok(Box::new(t))
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        decoder.increase_depth()?;
+        T::skip(decoder)?;
+        decoder.decrease_depth();
+        Ok(())
+    }
 }
 impl<'de, T> BorrowDecode<'de> for Box<T>
 where
     T: BorrowDecode<'de>,
 {
     fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        decoder.increase_depth()?;
         let t = T::borrow_decode(decoder)?;
+        decoder.decrease_depth();
         Ok(Box::new(t))
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        decoder.increase_depth()?;
+        T::borrow_skip(decoder)?;
+        decoder.decrease_depth();
+        Ok(())
+    }
 }
 
 impl<T> Encode for Box<T>
@@ -377,6 +573,10 @@ where
     }
 }
 
+impl<T: Schema + ?Sized> Schema for Box<T> {
+    const SCHEMA: Type = T::SCHEMA;
+}
+
 impl<T> Decode for Box<[T]>
 where
     T: Decode + 'static,
@@ -385,6 +585,10 @@ where
         let vec = Vec::decode(decoder)?;
         Ok(vec.into_boxed_slice())
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::skip_seq::<T, D>(decoder)
+    }
 }
 
 impl<'de, T> BorrowDecode<'de> for Box<[T]>
@@ -395,6 +599,10 @@ where
         let vec = Vec::borrow_decode(decoder)?;
         Ok(vec.into_boxed_slice())
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::borrow_skip_seq::<T, D>(decoder)
+    }
 }
 
 impl<'cow, T> Decode for Cow<'cow, T>
@@ -406,6 +614,10 @@ where
         let t = <T as ToOwned>::Owned::decode(decoder)?;
         Ok(Cow::Owned(t))
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        <T as ToOwned>::Owned::skip(decoder)
+    }
 }
 impl<'cow, T> BorrowDecode<'cow> for Cow<'cow, T>
 where
@@ -413,11 +625,41 @@ where
     &'cow T: BorrowDecode<'cow>,
 {
     fn borrow_decode<D: BorrowDecoder<'cow>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        // Slices of numbers are borrowed when possible, and copied otherwise
+        macro_rules! number_slice {
+            ($($ty:ty),*) => {
+                $(
+                    if unty::type_equal::<T, [$ty]>() {
+                        let cow = core::mem::ManuallyDrop::new(borrow_decode_number_cow::<$ty, D>(decoder)?);
+                        // Safety: T = [$ty]
+                        return Ok(unsafe { core::mem::transmute_copy::<Cow<'cow, [$ty]>, Self>(&cow) });
+                    }
+                )*
+            };
+        }
+        number_slice!(u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
         let t = <&T>::borrow_decode(decoder)?;
         Ok(Cow::Borrowed(t))
     }
 }
 
+/// Decodes a slice of numbers, which is borrowed if the numbers are stored as their native bytes and aligned, and copied otherwise.
+fn borrow_decode_number_cow<'de, T: Decode + Clone, D: BorrowDecoder<'de>>(
+    decoder: &mut D,
+) -> Result<Cow<'de, [T]>, DecodeError> {
+    let len = crate::de::decode_slice_len(decoder)?;
+    match crate::de::borrow_number_slice::<T, D>(decoder, len)? {
+        NumberSlice::Borrowed(slice) => Ok(Cow::Borrowed(slice)),
+        NumberSlice::Unaligned(number, bytes) => {
+            let mut vec = number.zeroed_vec(len);
+            number.as_bytes_mut(&mut vec).copy_from_slice(bytes);
+            Ok(Cow::Owned(vec))
+        }
+        NumberSlice::Encoded => decode_vec_items(decoder, len).map(Cow::Owned),
+    }
+}
+
 impl<'cow, T> Encode for Cow<'cow, T>
 where
     T: ToOwned + ?Sized,
@@ -428,6 +670,13 @@ where
     }
 }
 
+impl<T> Schema for Cow<'_, T>
+where
+    T: Schema + ToOwned + ?Sized,
+{
+    const SCHEMA: Type = T::SCHEMA;
+}
+
 #[test]
 fn test_cow_round_trip() {
     let start = Cow::Borrowed("Foo");
@@ -446,9 +695,18 @@ where
     T: Decode,
 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        decoder.increase_depth()?;
         let t = T::decode(decoder)?;
+        decoder.decrease_depth();
         Ok(Rc::new(t))
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        decoder.increase_depth()?;
+        T::skip(decoder)?;
+        decoder.decrease_depth();
+        Ok(())
+    }
 }
 
 impl Decode for Rc<str> {
@@ -456,6 +714,10 @@ impl Decode for Rc<str> {
         let decoded = String::decode(decoder)?;
         Ok(decoded.into())
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::skip_seq::<u8, D>(decoder)
+    }
 }
 
 impl<'de, T> BorrowDecode<'de> for Rc<T>
@@ -463,9 +725,18 @@ where
     T: BorrowDecode<'de>,
 {
     fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        decoder.increase_depth()?;
         let t = T::borrow_decode(decoder)?;
+        decoder.decrease_depth();
         Ok(Rc::new(t))
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        decoder.increase_depth()?;
+        T::borrow_skip(decoder)?;
+        decoder.decrease_depth();
+        Ok(())
+    }
 }
 
 impl<'de> BorrowDecode<'de> for Rc<str> {
@@ -473,6 +744,10 @@ impl<'de> BorrowDecode<'de> for Rc<str> {
         let decoded = String::decode(decoder)?;
         Ok(decoded.into())
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::skip_seq::<u8, D>(decoder)
+    }
 }
 
 impl<T> Encode for Rc<T>
@@ -484,6 +759,10 @@ where
     }
 }
 
+impl<T: Schema + ?Sized> Schema for Rc<T> {
+    const SCHEMA: Type = T::SCHEMA;
+}
+
 impl<T> Decode for Rc<[T]>
 where
     T: Decode + 'static,
@@ -492,6 +771,10 @@ where
         let vec = Vec::decode(decoder)?;
         Ok(vec.into())
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::skip_seq::<T, D>(decoder)
+    }
 }
 
 impl<'de, T> BorrowDecode<'de> for Rc<[T]>
@@ -502,6 +785,10 @@ where
         let vec = Vec::borrow_decode(decoder)?;
         Ok(vec.into())
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::borrow_skip_seq::<T, D>(decoder)
+    }
 }
 
 #[cfg(target_has_atomic = "ptr")]
@@ -510,9 +797,18 @@ where
     T: Decode,
 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        decoder.increase_depth()?;
         let t = T::decode(decoder)?;
+        decoder.decrease_depth();
         Ok(Arc::new(t))
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        decoder.increase_depth()?;
+        T::skip(decoder)?;
+        decoder.decrease_depth();
+        Ok(())
+    }
 }
 
 #[cfg(target_has_atomic = "ptr")]
@@ -521,6 +817,10 @@ impl Decode for Arc<str> {
         let decoded = String::decode(decoder)?;
         Ok(decoded.into())
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::skip_seq::<u8, D>(decoder)
+    }
 }
 
 #[cfg(target_has_atomic = "ptr")]
@@ -529,9 +829,18 @@ where
     T: BorrowDecode<'de>,
 {
     fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        decoder.increase_depth()?;
         let t = T::borrow_decode(decoder)?;
+        decoder.decrease_depth();
         Ok(Arc::new(t))
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        decoder.increase_depth()?;
+        T::borrow_skip(decoder)?;
+        decoder.decrease_depth();
+        Ok(())
+    }
 }
 
 #[cfg(target_has_atomic = "ptr")]
@@ -540,6 +849,10 @@ impl<'de> BorrowDecode<'de> for Arc<str> {
         let decoded = String::decode(decoder)?;
         Ok(decoded.into())
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::skip_seq::<u8, D>(decoder)
+    }
 }
 
 #[cfg(target_has_atomic = "ptr")]
@@ -552,6 +865,11 @@ where
     }
 }
 
+#[cfg(target_has_atomic = "ptr")]
+impl<T: Schema + ?Sized> Schema for Arc<T> {
+    const SCHEMA: Type = T::SCHEMA;
+}
+
 #[cfg(target_has_atomic = "ptr")]
 impl<T> Decode for Arc<[T]>
 where
@@ -561,6 +879,10 @@ where
         let vec = Vec::decode(decoder)?;
         Ok(vec.into())
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::skip_seq::<T, D>(decoder)
+    }
 }
 
 #[cfg(target_has_atomic = "ptr")]
@@ -572,4 +894,8 @@ where
         let vec = Vec::borrow_decode(decoder)?;
         Ok(vec.into())
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::borrow_skip_seq::<T, D>(decoder)
+    }
 }
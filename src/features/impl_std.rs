@@ -1,11 +1,16 @@
 use crate::{
-    config::Config,
-    de::{read::Reader, BorrowDecode, BorrowDecoder, Decode, Decoder, DecoderImpl},
+    config::{Config, EnumTagType},
+    de::{
+        read::{BorrowReader, Reader},
+        BorrowDecode, BorrowDecoder, Decode, Decoder,
+    },
     enc::{write::Writer, Encode, Encoder, EncoderImpl},
-    error::{DecodeError, EncodeError},
+    error::{DecodeError, EncodeError, PathSegment},
     impl_borrow_decode,
+    schema::{schema_of, Field, Schema, Type, Variant},
+    utils::Sealed,
 };
-use core::time::Duration;
+use core::{cmp::Ordering, time::Duration};
 use std::{
     collections::{HashMap, HashSet},
     ffi::{CStr, CString},
@@ -15,6 +20,7 @@ use std::{
     path::{Path, PathBuf},
     sync::{Mutex, RwLock},
     time::SystemTime,
+    vec::Vec,
 };
 
 /// Decode type `D` from the given reader with the given `Config`. The reader can be any type that implements `std::io::Read`, e.g. `std::fs::File`.
@@ -27,18 +33,62 @@ pub fn decode_from_std_read<D: Decode, C: Config, R: std::io::Read>(
     src: &mut R,
     config: C,
 ) -> Result<D, DecodeError> {
-    let reader = IoReader::new(src);
-    let mut decoder = DecoderImpl::<_, C>::new(reader, config);
-    D::decode(&mut decoder)
+    decode_from_std_read_with_count(src, config).map(|(result, _)| result)
+}
+
+/// Decode type `D` from the given reader with the given `Config`, like [decode_from_std_read]. Returns the decoded output and the amount of bytes read.
+///
+/// Because [decode_from_std_read] never reads past the end of the value, the reader is left at the start of whatever comes after it. The returned count can be used to keep track of where each value starts in a stream.
+///
+/// ```
+/// let config = bincode::config::standard();
+/// let mut bytes = bincode::encode_to_vec(1000u32, config).unwrap();
+/// bytes.extend(bincode::encode_to_vec(String::from("hello"), config).unwrap());
+///
+/// let mut stream = &bytes[..];
+/// let (number, len): (u32, usize) = bincode::decode_from_std_read_with_count(&mut stream, config).unwrap();
+/// assert_eq!((number, len), (1000, 3));
+/// let (text, len): (String, usize) = bincode::decode_from_std_read_with_count(&mut stream, config).unwrap();
+/// assert_eq!((text.as_str(), len), ("hello", 6));
+/// ```
+///
+/// See the [config] module for more information about config options.
+///
+/// [decode_from_std_read]: fn.decode_from_std_read.html
+/// [config]: config/index.html
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn decode_from_std_read_with_count<D: Decode, C: Config, R: std::io::Read>(
+    src: &mut R,
+    config: C,
+) -> Result<(D, usize), DecodeError> {
+    crate::decode_from_reader_with_count(IoReader::new(src), config)
+}
+
+/// Decode type `D` from the given reader with the given `Config`, like [decode_from_std_read_with_count]. If decoding fails, the error comes with the path to the value that failed and the offset where it failed.
+///
+/// See [ContextError] for an example.
+///
+/// [decode_from_std_read_with_count]: fn.decode_from_std_read_with_count.html
+/// [ContextError]: error/struct.ContextError.html
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn decode_from_std_read_with_context<D: Decode, C: Config, R: std::io::Read>(
+    src: &mut R,
+    config: C,
+) -> Result<(D, usize), crate::error::ContextError> {
+    crate::decode_from_reader_with_context(IoReader::new(src), config)
 }
 
 pub(crate) struct IoReader<R> {
     reader: R,
+    bytes_read: usize,
 }
 
 impl<R> IoReader<R> {
     pub const fn new(reader: R) -> Self {
-        Self { reader }
+        Self {
+            reader,
+            bytes_read: 0,
+        }
     }
 }
 
@@ -53,7 +103,14 @@ where
             .map_err(|inner| DecodeError::Io {
                 inner,
                 additional: bytes.len(),
-            })
+            })?;
+        self.bytes_read += bytes.len();
+        Ok(())
+    }
+
+    #[inline]
+    fn position(&self) -> Option<usize> {
+        Some(self.bytes_read)
     }
 }
 
@@ -128,18 +185,259 @@ impl<'storage, W: std::io::Write> Writer for IoWriter<'storage, W> {
     }
 }
 
-impl<'a> Encode for &'a CStr {
+/// Decode type `D` from the given tokio [AsyncRead] with the given `Config`. The reader can be any type that implements `tokio::io::AsyncRead`, e.g. `tokio::net::TcpStream`.
+///
+/// Because [Decode] is not async, the bytes are buffered in memory until a value can be decoded. Only the bytes that are needed are read from `src`, so any data after the value is left untouched in the reader.
+///
+/// Every time more bytes are needed, the value is decoded again from the start. To decode large values, or many values one after another, use an [AsyncReadDecoder] instead.
+///
+/// If the `Config` has a limit, the buffer will never grow beyond that limit and [DecodeError::LimitExceeded] is returned instead.
+///
+/// See the [config] module for more information about config options.
+///
+/// [AsyncRead]: tokio::io::AsyncRead
+/// [config]: config/index.html
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub async fn decode_from_async_read<D: Decode, C: Config, R: tokio::io::AsyncRead + Unpin>(
+    src: &mut R,
+    config: C,
+) -> Result<D, DecodeError> {
+    use tokio::io::AsyncReadExt;
+
+    let mut buffer = std::vec::Vec::new();
+    loop {
+        let additional = match crate::decode_from_slice(&buffer, config) {
+            Ok((result, _)) => return Ok(result),
+            Err(DecodeError::UnexpectedEnd { additional }) => additional,
+            Err(e) => return Err(e),
+        };
+        let start = buffer.len();
+        let new_len = start
+            .checked_add(additional)
+            .ok_or(DecodeError::LimitExceeded)?;
+        if matches!(config.limit(), Some(limit) if new_len > limit) {
+            return Err(DecodeError::LimitExceeded);
+        }
+        buffer.resize(new_len, 0);
+        src.read_exact(&mut buffer[start..])
+            .await
+            .map_err(|inner| DecodeError::Io { inner, additional })?;
+    }
+}
+
+/// Decodes values one after another from any type that implements tokio's [AsyncRead], e.g. `tokio::net::TcpStream`.
+///
+/// The bytes are read in chunks that grow with the value that is being decoded, and decoding is only retried when the reader has no more bytes ready. Bytes that are read past the end of a value are kept, and are used by the next call to [decode]. This makes it faster than [decode_from_async_read] for large values, but means that the reader should not be used directly while it is wrapped.
+///
+/// If the `Config` has a limit, the buffer will never grow beyond that limit and [DecodeError::LimitExceeded] is returned instead.
+///
+/// ```
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let config = bincode::config::standard();
+///
+/// let mut stream = Vec::new();
+/// bincode::encode_into_async_write(String::from("Hello"), &mut stream, config).await.unwrap();
+/// bincode::encode_into_async_write(String::from("world"), &mut stream, config).await.unwrap();
+///
+/// let mut decoder = bincode::AsyncReadDecoder::new(stream.as_slice(), config);
+/// assert_eq!(decoder.decode::<String>().await.unwrap(), Some(String::from("Hello")));
+/// assert_eq!(decoder.decode::<String>().await.unwrap(), Some(String::from("world")));
+/// assert_eq!(decoder.decode::<String>().await.unwrap(), None);
+/// # });
+/// ```
+///
+/// [AsyncRead]: tokio::io::AsyncRead
+/// [decode]: #method.decode
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub struct AsyncReadDecoder<R, C> {
+    reader: R,
+    config: C,
+    buffer: Vec<u8>,
+}
+
+#[cfg(feature = "tokio")]
+impl<R: tokio::io::AsyncRead + Unpin, C: Config> AsyncReadDecoder<R, C> {
+    /// Create a new `AsyncReadDecoder` that reads from the given reader.
+    pub const fn new(reader: R, config: C) -> Self {
+        Self {
+            reader,
+            config,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Decode the next value from the reader.
+    ///
+    /// Returns `Ok(None)` if the reader reached its end before any byte of the next value.
+    ///
+    /// If the reader reaches its end in the middle of a value, [DecodeError::UnexpectedEnd] is returned. If the reader returns an error, [DecodeError::Io] is returned. In both cases the bytes that were already read are kept, and the next call to `decode` will continue with the same value.
+    pub async fn decode<D: Decode>(&mut self) -> Result<Option<D>, DecodeError> {
+        // The amount of bytes that are needed before decoding the buffer can succeed
+        let mut needed = 0;
+        loop {
+            if self.buffer.len() >= needed {
+                match crate::decode_from_slice(&self.buffer, self.config) {
+                    Ok((result, len)) => {
+                        self.buffer.drain(..len);
+                        return Ok(Some(result));
+                    }
+                    Err(DecodeError::UnexpectedEnd { additional }) => {
+                        needed = self
+                            .buffer
+                            .len()
+                            .checked_add(additional)
+                            .ok_or(DecodeError::LimitExceeded)?
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+            if matches!(self.config.limit(), Some(limit) if needed > limit) {
+                return Err(DecodeError::LimitExceeded);
+            }
+            let start = self.buffer.len();
+            match self.fill(needed).await? {
+                0 if start == 0 => return Ok(None),
+                0 => {
+                    return Err(DecodeError::UnexpectedEnd {
+                        additional: needed - start,
+                    })
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns the bytes that were read from the reader, but are not decoded yet.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Returns the underlying reader. Any bytes in the [buffer](#method.buffer) are lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Waits until at least one byte can be read, then reads the bytes that are ready without waiting, up to a chunk that is at least `needed` bytes long. Returns the amount of bytes read, which is `0` if the reader is at its end.
+    async fn fill(&mut self, needed: usize) -> Result<usize, DecodeError> {
+        use core::{pin::Pin, task::Poll};
+        use tokio::io::ReadBuf;
+
+        const MIN_CHUNK_SIZE: usize = 8 * 1024;
+
+        let start = self.buffer.len();
+        // Doubling the buffer means that a value is decoded O(log n) times when its bytes arrive faster than they are decoded
+        let mut end = needed.max(start * 2).max(MIN_CHUNK_SIZE);
+        if let Some(limit) = self.config.limit() {
+            end = end.min(limit);
+        }
+        let mut filled = start;
+        self.buffer.resize(end, 0);
+
+        let mut wait = true;
+        let result = loop {
+            if filled == end {
+                break Ok(());
+            }
+            let (reader, buffer) = (&mut self.reader, &mut self.buffer);
+            let read = core::future::poll_fn(|cx| {
+                let mut read_buf = ReadBuf::new(&mut buffer[filled..]);
+                match Pin::new(&mut *reader).poll_read(cx, &mut read_buf) {
+                    Poll::Ready(result) => {
+                        Poll::Ready(result.map(|()| Some(read_buf.filled().len())))
+                    }
+                    Poll::Pending if wait => Poll::Pending,
+                    Poll::Pending => Poll::Ready(Ok(None)),
+                }
+            })
+            .await;
+            match read {
+                Ok(Some(0)) | Ok(None) => break Ok(()),
+                Ok(Some(n)) => {
+                    filled += n;
+                    wait = false;
+                }
+                Err(inner) => {
+                    break Err(DecodeError::Io {
+                        inner,
+                        additional: needed.saturating_sub(filled).max(1),
+                    })
+                }
+            }
+        };
+        self.buffer.truncate(filled);
+        result.map(|()| filled - start)
+    }
+}
+
+/// Encode the given value into any type that implements `tokio::io::AsyncWrite`, e.g. `tokio::net::TcpStream`, with the given `Config`.
+/// See the [config] module for more information.
+/// Returns the amount of bytes written.
+///
+/// The value is encoded into an in-memory buffer first, which is then written to `dst`.
+///
+/// [config]: config/index.html
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub async fn encode_into_async_write<E: Encode, C: Config, W: tokio::io::AsyncWrite + Unpin>(
+    val: E,
+    dst: &mut W,
+    config: C,
+) -> Result<usize, EncodeError> {
+    use tokio::io::AsyncWriteExt;
+
+    let buffer = crate::encode_to_vec(val, config)?;
+    let mut index = 0;
+    while index < buffer.len() {
+        match dst.write(&buffer[index..]).await {
+            Ok(0) => {
+                return Err(EncodeError::Io {
+                    inner: std::io::ErrorKind::WriteZero.into(),
+                    index,
+                })
+            }
+            Ok(n) => index += n,
+            Err(inner) if inner.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(inner) => return Err(EncodeError::Io { inner, index }),
+        }
+    }
+    dst.flush()
+        .await
+        .map_err(|inner| EncodeError::Io { inner, index })?;
+    Ok(index)
+}
+
+impl Encode for &CStr {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         self.to_bytes().encode(encoder)
     }
 }
 
+impl Schema for CStr {
+    const SCHEMA: Type = Type::Seq(schema_of::<u8>);
+}
+
 impl Encode for CString {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         self.as_bytes().encode(encoder)
     }
 }
 
+impl Schema for CString {
+    const SCHEMA: Type = Type::Seq(schema_of::<u8>);
+}
+
 impl Decode for CString {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         let vec = std::vec::Vec::decode(decoder)?;
@@ -147,6 +445,10 @@ impl Decode for CString {
             position: inner.nul_position(),
         })
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::skip_seq::<u8, D>(decoder)
+    }
 }
 impl_borrow_decode!(CString);
 
@@ -162,6 +464,10 @@ where
     }
 }
 
+impl<T: Schema + ?Sized> Schema for Mutex<T> {
+    const SCHEMA: Type = T::SCHEMA;
+}
+
 impl<T> Decode for Mutex<T>
 where
     T: Decode,
@@ -170,6 +476,10 @@ where
         let t = T::decode(decoder)?;
         Ok(Mutex::new(t))
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        T::skip(decoder)
+    }
 }
 impl<'de, T> BorrowDecode<'de> for Mutex<T>
 where
@@ -179,6 +489,10 @@ where
         let t = T::borrow_decode(decoder)?;
         Ok(Mutex::new(t))
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        T::borrow_skip(decoder)
+    }
 }
 
 impl<T> Encode for RwLock<T>
@@ -193,6 +507,10 @@ where
     }
 }
 
+impl<T: Schema + ?Sized> Schema for RwLock<T> {
+    const SCHEMA: Type = T::SCHEMA;
+}
+
 impl<T> Decode for RwLock<T>
 where
     T: Decode,
@@ -201,6 +519,10 @@ where
         let t = T::decode(decoder)?;
         Ok(RwLock::new(t))
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        T::skip(decoder)
+    }
 }
 impl<'de, T> BorrowDecode<'de> for RwLock<T>
 where
@@ -210,6 +532,10 @@ where
         let t = T::borrow_decode(decoder)?;
         Ok(RwLock::new(t))
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        T::borrow_skip(decoder)
+    }
 }
 
 impl Encode for SystemTime {
@@ -224,6 +550,10 @@ impl Encode for SystemTime {
     }
 }
 
+impl Schema for SystemTime {
+    const SCHEMA: Type = Duration::SCHEMA;
+}
+
 impl Decode for SystemTime {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         let duration = Duration::decode(decoder)?;
@@ -244,11 +574,19 @@ impl Encode for &'_ Path {
     }
 }
 
+impl Schema for Path {
+    const SCHEMA: Type = Type::Str;
+}
+
 impl<'de> BorrowDecode<'de> for &'de Path {
     fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
         let str = <&'de str>::borrow_decode(decoder)?;
         Ok(Path::new(str))
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        <&[u8]>::borrow_skip(decoder)
+    }
 }
 
 impl Encode for PathBuf {
@@ -257,11 +595,19 @@ impl Encode for PathBuf {
     }
 }
 
+impl Schema for PathBuf {
+    const SCHEMA: Type = Type::Str;
+}
+
 impl Decode for PathBuf {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         let string = std::string::String::decode(decoder)?;
         Ok(string.into())
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::skip_seq::<u8, D>(decoder)
+    }
 }
 impl_borrow_decode!(PathBuf);
 
@@ -280,6 +626,32 @@ impl Encode for IpAddr {
     }
 }
 
+impl Schema for IpAddr {
+    const SCHEMA: Type = Type::Enum {
+        name: "IpAddr",
+        type_name: core::any::type_name::<Self>,
+        tag_type: Some(EnumTagType::U32),
+        variants: &[
+            Variant {
+                name: "V4",
+                index: 0,
+                fields: &[Field {
+                    name: None,
+                    schema: schema_of::<Ipv4Addr>,
+                }],
+            },
+            Variant {
+                name: "V6",
+                index: 1,
+                fields: &[Field {
+                    name: None,
+                    schema: schema_of::<Ipv6Addr>,
+                }],
+            },
+        ],
+    };
+}
+
 impl Decode for IpAddr {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         match u32::decode(decoder)? {
@@ -301,6 +673,10 @@ impl Encode for Ipv4Addr {
     }
 }
 
+impl Schema for Ipv4Addr {
+    const SCHEMA: Type = <[u8; 4]>::SCHEMA;
+}
+
 impl Decode for Ipv4Addr {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         let mut buff = [0u8; 4];
@@ -316,6 +692,10 @@ impl Encode for Ipv6Addr {
     }
 }
 
+impl Schema for Ipv6Addr {
+    const SCHEMA: Type = <[u8; 16]>::SCHEMA;
+}
+
 impl Decode for Ipv6Addr {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         let mut buff = [0u8; 16];
@@ -340,6 +720,32 @@ impl Encode for SocketAddr {
     }
 }
 
+impl Schema for SocketAddr {
+    const SCHEMA: Type = Type::Enum {
+        name: "SocketAddr",
+        type_name: core::any::type_name::<Self>,
+        tag_type: Some(EnumTagType::U32),
+        variants: &[
+            Variant {
+                name: "V4",
+                index: 0,
+                fields: &[Field {
+                    name: None,
+                    schema: schema_of::<SocketAddrV4>,
+                }],
+            },
+            Variant {
+                name: "V6",
+                index: 1,
+                fields: &[Field {
+                    name: None,
+                    schema: schema_of::<SocketAddrV6>,
+                }],
+            },
+        ],
+    };
+}
+
 impl Decode for SocketAddr {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         match u32::decode(decoder)? {
@@ -362,6 +768,23 @@ impl Encode for SocketAddrV4 {
     }
 }
 
+impl Schema for SocketAddrV4 {
+    const SCHEMA: Type = Type::Struct {
+        name: "SocketAddrV4",
+        type_name: core::any::type_name::<Self>,
+        fields: &[
+            Field {
+                name: Some("ip"),
+                schema: schema_of::<Ipv4Addr>,
+            },
+            Field {
+                name: Some("port"),
+                schema: schema_of::<u16>,
+            },
+        ],
+    };
+}
+
 impl Decode for SocketAddrV4 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         let ip = Ipv4Addr::decode(decoder)?;
@@ -378,6 +801,23 @@ impl Encode for SocketAddrV6 {
     }
 }
 
+impl Schema for SocketAddrV6 {
+    const SCHEMA: Type = Type::Struct {
+        name: "SocketAddrV6",
+        type_name: core::any::type_name::<Self>,
+        fields: &[
+            Field {
+                name: Some("ip"),
+                schema: schema_of::<Ipv6Addr>,
+            },
+            Field {
+                name: Some("port"),
+                schema: schema_of::<u16>,
+            },
+        ],
+    };
+}
+
 impl Decode for SocketAddrV6 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         let ip = Ipv6Addr::decode(decoder)?;
@@ -405,6 +845,11 @@ impl std::error::Error for DecodeError {
         }
     }
 }
+impl std::error::Error for crate::error::ContextError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.error())
+    }
+}
 
 impl<K, V, S> Encode for HashMap<K, V, S>
 where
@@ -413,6 +858,13 @@ where
 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         crate::enc::encode_slice_len(encoder, self.len())?;
+        if encoder.config().canonical() {
+            for (k, v) in sort_by_encoded_key(self.iter(), encoder.config())? {
+                encoder.writer().write(&k)?;
+                Encode::encode(v, encoder)?;
+            }
+            return Ok(());
+        }
         for (k, v) in self.iter() {
             Encode::encode(k, encoder)?;
             Encode::encode(v, encoder)?;
@@ -421,6 +873,13 @@ where
     }
 }
 
+impl<K: Schema, V: Schema, S> Schema for HashMap<K, V, S> {
+    const SCHEMA: Type = Type::Map {
+        key: schema_of::<K>,
+        value: schema_of::<V>,
+    };
+}
+
 impl<K, V, S> Decode for HashMap<K, V, S>
 where
     K: Decode + Eq + std::hash::Hash,
@@ -433,16 +892,28 @@ where
 
         let hash_builder: S = Default::default();
         let mut map = HashMap::with_capacity_and_hasher(len, hash_builder);
+        decoder.increase_depth()?;
+        let mut previous = None;
         for _ in 0..len {
             // See the documentation on `unclaim_bytes_read` as to why we're doing this here
             decoder.unclaim_bytes_read(core::mem::size_of::<(K, V)>());
 
-            let k = K::decode(decoder)?;
+            let k = if decoder.config().canonical() {
+                decode_sorted_key::<Self, _, _>(decoder, &mut previous, |d| K::decode(d))?
+            } else {
+                K::decode(decoder)?
+            };
             let v = V::decode(decoder)?;
-            map.insert(k, v);
+            let inserted = map.insert(k, v).is_none();
+            crate::de::check_duplicate_key::<Self, D>(decoder, inserted)?;
         }
+        decoder.decrease_depth();
         Ok(map)
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::skip_map::<K, V, D>(decoder)
+    }
 }
 impl<'de, K, V, S> BorrowDecode<'de> for HashMap<K, V, S>
 where
@@ -456,16 +927,28 @@ where
 
         let hash_builder: S = Default::default();
         let mut map = HashMap::with_capacity_and_hasher(len, hash_builder);
+        decoder.increase_depth()?;
+        let mut previous = None;
         for _ in 0..len {
             // See the documentation on `unclaim_bytes_read` as to why we're doing this here
             decoder.unclaim_bytes_read(core::mem::size_of::<(K, V)>());
 
-            let k = K::borrow_decode(decoder)?;
+            let k = if decoder.config().canonical() {
+                decode_sorted_key::<Self, _, _>(decoder, &mut previous, |d| K::borrow_decode(d))?
+            } else {
+                K::borrow_decode(decoder)?
+            };
             let v = V::borrow_decode(decoder)?;
-            map.insert(k, v);
+            let inserted = map.insert(k, v).is_none();
+            crate::de::check_duplicate_key::<Self, D>(decoder, inserted)?;
         }
+        decoder.decrease_depth();
         Ok(map)
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::borrow_skip_map::<K, V, D>(decoder)
+    }
 }
 
 impl<T, S> Decode for HashSet<T, S>
@@ -479,15 +962,27 @@ where
 
         let hash_builder: S = Default::default();
         let mut map: HashSet<T, S> = HashSet::with_capacity_and_hasher(len, hash_builder);
+        decoder.increase_depth()?;
+        let mut previous = None;
         for _ in 0..len {
             // See the documentation on `unclaim_bytes_read` as to why we're doing this here
             decoder.unclaim_bytes_read(core::mem::size_of::<T>());
 
-            let key = T::decode(decoder)?;
-            map.insert(key);
+            let key = if decoder.config().canonical() {
+                decode_sorted_key::<Self, _, _>(decoder, &mut previous, |d| T::decode(d))?
+            } else {
+                T::decode(decoder)?
+            };
+            let inserted = map.insert(key);
+            crate::de::check_duplicate_key::<Self, D>(decoder, inserted)?;
         }
+        decoder.decrease_depth();
         Ok(map)
     }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::skip_seq::<T, D>(decoder)
+    }
 }
 
 impl<'de, T, S> BorrowDecode<'de> for HashSet<T, S>
@@ -500,15 +995,27 @@ where
         decoder.claim_container_read::<T>(len)?;
 
         let mut map = HashSet::with_capacity_and_hasher(len, S::default());
+        decoder.increase_depth()?;
+        let mut previous = None;
         for _ in 0..len {
             // See the documentation on `unclaim_bytes_read` as to why we're doing this here
             decoder.unclaim_bytes_read(core::mem::size_of::<T>());
 
-            let key = T::borrow_decode(decoder)?;
-            map.insert(key);
+            let key = if decoder.config().canonical() {
+                decode_sorted_key::<Self, _, _>(decoder, &mut previous, |d| T::borrow_decode(d))?
+            } else {
+                T::borrow_decode(decoder)?
+            };
+            let inserted = map.insert(key);
+            crate::de::check_duplicate_key::<Self, D>(decoder, inserted)?;
         }
+        decoder.decrease_depth();
         Ok(map)
     }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        crate::de::borrow_skip_seq::<T, D>(decoder)
+    }
 }
 
 impl<T, S> Encode for HashSet<T, S>
@@ -517,9 +1024,153 @@ where
 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         crate::enc::encode_slice_len(encoder, self.len())?;
+        if encoder.config().canonical() {
+            for (item, ()) in
+                sort_by_encoded_key(self.iter().map(|item| (item, ())), encoder.config())?
+            {
+                encoder.writer().write(&item)?;
+            }
+            return Ok(());
+        }
         for item in self.iter() {
             item.encode(encoder)?;
         }
         Ok(())
     }
 }
+
+impl<T: Schema, S> Schema for HashSet<T, S> {
+    const SCHEMA: Type = Type::Seq(schema_of::<T>);
+}
+
+/// Encodes the keys of a `HashMap` or `HashSet` and sorts the entries by the encoded keys, for the canonical encoding.
+fn sort_by_encoded_key<'a, K: Encode + 'a, V>(
+    entries: impl Iterator<Item = (&'a K, V)>,
+    config: &impl Config,
+) -> Result<Vec<(Vec<u8>, V)>, EncodeError> {
+    let mut sorted = entries
+        .map(|(k, v)| Ok((crate::encode_to_vec(k, *config)?, v)))
+        .collect::<Result<Vec<_>, EncodeError>>()?;
+    sorted.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    Ok(sorted)
+}
+
+/// Decodes a key of the `HashMap` or `HashSet` `M` with the canonical encoding. The encoded key has to come after `previous`, the encoded key before it.
+fn decode_sorted_key<M, K, D: Decoder>(
+    decoder: &mut D,
+    previous: &mut Option<Vec<u8>>,
+    decode: impl FnOnce(&mut RecordingDecoder<'_, D>) -> Result<K, DecodeError>,
+) -> Result<K, DecodeError> {
+    let mut recorder = RecordingDecoder {
+        decoder,
+        bytes: Vec::new(),
+    };
+    let key = decode(&mut recorder)?;
+    match previous
+        .as_ref()
+        .map(|previous| previous.cmp(&recorder.bytes))
+    {
+        Some(Ordering::Equal) => {
+            return Err(DecodeError::DuplicateMapKey {
+                type_name: core::any::type_name::<M>(),
+            })
+        }
+        Some(Ordering::Greater) => {
+            return Err(DecodeError::UnsortedKeys {
+                type_name: core::any::type_name::<M>(),
+            })
+        }
+        _ => {}
+    }
+    *previous = Some(recorder.bytes);
+    Ok(key)
+}
+
+/// A decoder that keeps a copy of the bytes that are read through it. This is used to compare the encoded keys of a `HashMap` or `HashSet` with the canonical encoding.
+struct RecordingDecoder<'a, D> {
+    decoder: &'a mut D,
+    bytes: Vec<u8>,
+}
+
+impl<D> Sealed for RecordingDecoder<'_, D> {}
+
+impl<D: Decoder> Reader for RecordingDecoder<'_, D> {
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        self.decoder.reader().read(bytes)?;
+        self.bytes.extend_from_slice(bytes);
+        Ok(())
+    }
+
+    fn peek_read(&mut self, n: usize) -> Option<&[u8]> {
+        self.decoder.reader().peek_read(n)
+    }
+
+    fn consume(&mut self, n: usize) {
+        if let Some(bytes) = self.decoder.reader().peek_read(n) {
+            self.bytes.extend_from_slice(bytes);
+        }
+        self.decoder.reader().consume(n);
+    }
+
+    fn position(&self) -> Option<usize> {
+        self.decoder.position()
+    }
+}
+
+impl<'de, D: BorrowDecoder<'de>> BorrowReader<'de> for RecordingDecoder<'_, D> {
+    fn take_bytes(&mut self, length: usize) -> Result<&'de [u8], DecodeError> {
+        let bytes = self.decoder.borrow_reader().take_bytes(length)?;
+        self.bytes.extend_from_slice(bytes);
+        Ok(bytes)
+    }
+}
+
+impl<D: Decoder> Decoder for RecordingDecoder<'_, D> {
+    type R = Self;
+
+    type C = D::C;
+
+    fn reader(&mut self) -> &mut Self::R {
+        self
+    }
+
+    fn config(&self) -> &Self::C {
+        self.decoder.config()
+    }
+
+    fn position(&self) -> Option<usize> {
+        self.decoder.position()
+    }
+
+    fn state(&self) -> crate::de::DecoderState {
+        self.decoder.state()
+    }
+
+    fn error_at(&mut self, error: DecodeError, segment: PathSegment) -> DecodeError {
+        self.decoder.error_at(error, segment)
+    }
+
+    fn claim_bytes_read(&mut self, n: usize) -> Result<(), DecodeError> {
+        self.decoder.claim_bytes_read(n)
+    }
+
+    fn unclaim_bytes_read(&mut self, n: usize) {
+        self.decoder.unclaim_bytes_read(n)
+    }
+
+    fn increase_depth(&mut self) -> Result<(), DecodeError> {
+        self.decoder.increase_depth()
+    }
+
+    fn decrease_depth(&mut self) {
+        self.decoder.decrease_depth()
+    }
+}
+
+impl<'de, D: BorrowDecoder<'de>> BorrowDecoder<'de> for RecordingDecoder<'_, D> {
+    type BR = Self;
+
+    fn borrow_reader(&mut self) -> &mut Self::BR {
+        self
+    }
+}
@@ -8,6 +8,10 @@ mod impl_std;
 #[cfg(feature = "std")]
 pub use self::impl_std::*;
 
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod framed;
+
 #[cfg(feature = "derive")]
 mod derive;
 #[cfg(feature = "derive")]
@@ -9,6 +9,8 @@ use serde::de::*;
 
 /// Attempt to decode a given type `D` from the given slice. Returns the decoded output and the amount of bytes read.
 ///
+/// Bytes after the decoded value are ignored, unless the config was created with [with_reject_trailing_bytes](../config/struct.Configuration.html#method.with_reject_trailing_bytes). To always reject them, use [borrow_decode_from_slice_exact].
+///
 /// See the [config](../config/index.html) module for more information on configurations.
 pub fn borrow_decode_from_slice<'de, D, C>(
     slice: &'de [u8],
@@ -25,8 +27,35 @@ where
         pd: PhantomData,
     };
     let result = D::deserialize(serde_decoder)?;
-    let bytes_read = slice.len() - decoder.borrow_reader().slice.len();
-    Ok((result, bytes_read))
+    let remaining = decoder.borrow_reader().slice.len();
+    if C::REJECT_TRAILING_BYTES && remaining > 0 {
+        return Err(DecodeError::TrailingBytes { remaining });
+    }
+    Ok((result, slice.len() - remaining))
+}
+
+/// Attempt to decode a given type `D` from the given slice. Unlike [borrow_decode_from_slice], this fails with [DecodeError::TrailingBytes](../error/enum.DecodeError.html#variant.TrailingBytes) if `slice` contains more bytes than the decoded value.
+///
+/// See the [config](../config/index.html) module for more information on configurations.
+pub fn borrow_decode_from_slice_exact<'de, D, C>(
+    slice: &'de [u8],
+    config: C,
+) -> Result<D, DecodeError>
+where
+    D: Deserialize<'de>,
+    C: Config,
+{
+    let reader = crate::de::read::SliceReader::new(slice);
+    let mut decoder = crate::de::DecoderImpl::new(reader, config);
+    let serde_decoder = SerdeDecoder {
+        de: &mut decoder,
+        pd: PhantomData,
+    };
+    let result = D::deserialize(serde_decoder)?;
+    match decoder.borrow_reader().slice.len() {
+        0 => Ok(result),
+        remaining => Err(DecodeError::TrailingBytes { remaining }),
+    }
 }
 
 #[deprecated(note = "Use borrow_decode_from_slice instead")]
@@ -114,13 +143,11 @@ impl<'a, 'de, DE: BorrowDecoder<'de>> Deserializer<'de> for SerdeDecoder<'a, 'de
         visitor.visit_i64(Decode::decode(&mut self.de)?)
     }
 
-    serde::serde_if_integer128! {
-        fn deserialize_i128<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
-        where
-            V: serde::de::Visitor<'de>,
-        {
-            visitor.visit_i128(Decode::decode(&mut self.de)?)
-        }
+    fn deserialize_i128<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        visitor.visit_i128(Decode::decode(&mut self.de)?)
     }
 
     fn deserialize_u8<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
@@ -151,13 +178,11 @@ impl<'a, 'de, DE: BorrowDecoder<'de>> Deserializer<'de> for SerdeDecoder<'a, 'de
         visitor.visit_u64(Decode::decode(&mut self.de)?)
     }
 
-    serde::serde_if_integer128! {
-        fn deserialize_u128<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
-        where
-            V: serde::de::Visitor<'de>,
-        {
-            visitor.visit_u128(Decode::decode(&mut self.de)?)
-        }
+    fn deserialize_u128<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        visitor.visit_u128(Decode::decode(&mut self.de)?)
     }
 
     fn deserialize_f32<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
@@ -10,9 +10,13 @@ use serde::de::*;
 ///
 /// Note that this does not work with borrowed types like `&str` or `&[u8]`. For that use [borrow_decode_from_slice].
 ///
+/// Bytes after the decoded value are ignored, unless the config was created with [with_reject_trailing_bytes]. To always reject them, use [decode_from_slice_exact].
+///
 /// See the [config] module for more information on configurations.
 ///
 /// [borrow_decode_from_slice]: fn.borrow_decode_from_slice.html
+/// [decode_from_slice_exact]: fn.decode_from_slice_exact.html
+/// [with_reject_trailing_bytes]: ../config/struct.Configuration.html#method.with_reject_trailing_bytes
 /// [config]: ../config/index.html
 pub fn decode_from_slice<D, C>(slice: &[u8], config: C) -> Result<(D, usize), DecodeError>
 where
@@ -23,8 +27,36 @@ where
     let mut decoder = crate::de::DecoderImpl::new(reader, config);
     let serde_decoder = SerdeDecoder { de: &mut decoder };
     let result = D::deserialize(serde_decoder)?;
-    let bytes_read = slice.len() - decoder.reader().slice.len();
-    Ok((result, bytes_read))
+    let remaining = decoder.reader().slice.len();
+    if C::REJECT_TRAILING_BYTES && remaining > 0 {
+        return Err(DecodeError::TrailingBytes { remaining });
+    }
+    Ok((result, slice.len() - remaining))
+}
+
+/// Attempt to decode a given type `D` from the given slice. Unlike [decode_from_slice], this fails with [DecodeError::TrailingBytes] if `slice` contains more bytes than the decoded value.
+///
+/// Note that this does not work with borrowed types like `&str` or `&[u8]`. For that use [borrow_decode_from_slice_exact].
+///
+/// See the [config] module for more information on configurations.
+///
+/// [borrow_decode_from_slice_exact]: fn.borrow_decode_from_slice_exact.html
+/// [decode_from_slice]: fn.decode_from_slice.html
+/// [DecodeError::TrailingBytes]: ../error/enum.DecodeError.html#variant.TrailingBytes
+/// [config]: ../config/index.html
+pub fn decode_from_slice_exact<D, C>(slice: &[u8], config: C) -> Result<D, DecodeError>
+where
+    D: DeserializeOwned,
+    C: Config,
+{
+    let reader = crate::de::read::SliceReader::new(slice);
+    let mut decoder = crate::de::DecoderImpl::new(reader, config);
+    let serde_decoder = SerdeDecoder { de: &mut decoder };
+    let result = D::deserialize(serde_decoder)?;
+    match decoder.reader().slice.len() {
+        0 => Ok(result),
+        remaining => Err(DecodeError::TrailingBytes { remaining }),
+    }
 }
 
 /// Decode type `D` from the given reader with the given `Config`. The reader can be any type that implements `std::io::Read`, e.g. `std::fs::File`.
@@ -107,13 +139,11 @@ impl<'a, 'de, DE: Decoder> Deserializer<'de> for SerdeDecoder<'a, DE> {
         visitor.visit_i64(Decode::decode(&mut self.de)?)
     }
 
-    serde::serde_if_integer128! {
-        fn deserialize_i128<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
-        where
-            V: serde::de::Visitor<'de>,
-        {
-            visitor.visit_i128(Decode::decode(&mut self.de)?)
-        }
+    fn deserialize_i128<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        visitor.visit_i128(Decode::decode(&mut self.de)?)
     }
 
     fn deserialize_u8<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
@@ -144,13 +174,11 @@ impl<'a, 'de, DE: Decoder> Deserializer<'de> for SerdeDecoder<'a, DE> {
         visitor.visit_u64(Decode::decode(&mut self.de)?)
     }
 
-    serde::serde_if_integer128! {
-        fn deserialize_u128<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
-        where
-            V: serde::de::Visitor<'de>,
-        {
-            visitor.visit_u128(Decode::decode(&mut self.de)?)
-        }
+    fn deserialize_u128<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        visitor.visit_u128(Decode::decode(&mut self.de)?)
     }
 
     fn deserialize_f32<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
@@ -92,6 +92,29 @@ pub enum DecodeError {
     CustomError,
 }
 
+impl core::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::AnyNotSupported => f.write_str("bincode does not support deserialize_any"),
+            Self::IdentifierNotSupported => {
+                f.write_str("bincode does not support deserialize_identifier")
+            }
+            Self::IgnoredAnyNotSupported => {
+                f.write_str("bincode does not support deserialize_ignored_any")
+            }
+            Self::CannotBorrowOwnedData => f.write_str(
+                "cannot borrow data from an owned reader, use serde_decode_borrowed_from_* instead",
+            ),
+            #[cfg(not(feature = "alloc"))]
+            Self::CannotAllocate => f.write_str("cannot allocate without the alloc feature"),
+            #[cfg(not(feature = "alloc"))]
+            Self::CustomError => {
+                f.write_str("custom serde error, the message is lost without the alloc feature")
+            }
+        }
+    }
+}
+
 #[cfg(feature = "alloc")]
 impl serde::de::Error for crate::error::DecodeError {
     fn custom<T>(msg: T) -> Self
@@ -139,6 +162,20 @@ pub enum EncodeError {
     CustomError,
 }
 
+impl core::fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::SequenceMustHaveLength => f.write_str("sequences must have a known length"),
+            #[cfg(not(feature = "alloc"))]
+            Self::CannotCollectStr => f.write_str("collect_str needs the alloc feature"),
+            #[cfg(not(feature = "alloc"))]
+            Self::CustomError => {
+                f.write_str("custom serde error, the message is lost without the alloc feature")
+            }
+        }
+    }
+}
+
 #[allow(clippy::from_over_into)]
 impl Into<crate::error::EncodeError> for EncodeError {
     fn into(self) -> crate::error::EncodeError {
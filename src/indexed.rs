@@ -0,0 +1,372 @@
+#[cfg(feature = "alloc")]
+use crate::de::{
+    read::{Reader, Take},
+    Decode,
+};
+use crate::{
+    config::{Config, Endianness},
+    de::{
+        read::{BorrowReader, SliceReader},
+        BorrowDecode, BorrowDecoder, Decoder, DecoderImpl, DecoderState,
+    },
+    enc::{
+        write::{SizeWriter, Writer},
+        Encode, Encoder, EncoderImpl,
+    },
+    error::{DecodeError, EncodeError},
+};
+use core::{fmt, marker::PhantomData};
+
+/// The size of a single entry in the offset table
+const OFFSET_SIZE: usize = core::mem::size_of::<u64>();
+
+/// A sequence that is encoded with a table of offsets in front of its items, so that any item can be decoded without decoding the items before it.
+///
+/// `Indexed` can wrap a `Vec<T>` or a `&[T]`. It is encoded as:
+/// - the amount of items, encoded like the length of a slice.
+/// - for every item, the offset where the item ends. This is relative to the start of the first item, and always encoded as 8 bytes in the endianness of the config, regardless of the `IntEncoding`.
+/// - the items.
+///
+/// Use [IndexedSlice] to look up items in the encoded data without decoding the whole sequence, e.g. in a memory-mapped file.
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use bincode::{Indexed, IndexedSlice};
+///
+/// let config = bincode::config::standard();
+/// let names = vec![String::from("a"), String::from("bc"), String::from("def")];
+/// let bytes = bincode::encode_to_vec(Indexed(&names[..]), config).unwrap();
+///
+/// let (index, _): (IndexedSlice<&str>, usize) = bincode::borrow_decode_from_slice(&bytes, config).unwrap();
+/// assert_eq!(index.len(), 3);
+/// assert_eq!(index.get(2, config).unwrap(), Some("def"));
+///
+/// let (Indexed(decoded), _): (Indexed<Vec<String>>, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+/// assert_eq!(decoded, names);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Indexed<T>(pub T);
+
+impl<T: Encode> Encode for Indexed<&[T]> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        encode_indexed(self.0, encoder)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Encode> Encode for Indexed<alloc::vec::Vec<T>> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        encode_indexed(&self.0, encoder)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Decode> Decode for Indexed<alloc::vec::Vec<T>> {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let ends = decode_offsets(decoder)?;
+        decoder.claim_container_read::<T>(ends.len())?;
+        decoder.increase_depth()?;
+        let mut vec = alloc::vec::Vec::with_capacity(ends.len());
+        for size in item_sizes(&ends)? {
+            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+            decoder.unclaim_bytes_read(core::mem::size_of::<T>());
+            // The item continues at the depth of the sequence, and from the claimed bytes before `size`
+            let state = decoder.state();
+            decoder.claim_bytes_read(size)?;
+
+            let config = *decoder.config();
+            let mut item =
+                DecoderImpl::with_state(Take::new(decoder.reader(), size), config, state);
+            vec.push(T::decode(&mut item)?);
+            item.reader().finish()?;
+        }
+        decoder.decrease_depth();
+        Ok(Indexed(vec))
+    }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        skip_indexed(decoder)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de, T: BorrowDecode<'de>> BorrowDecode<'de> for Indexed<alloc::vec::Vec<T>> {
+    fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let ends = decode_offsets(decoder)?;
+        decoder.claim_container_read::<T>(ends.len())?;
+        decoder.increase_depth()?;
+        let mut vec = alloc::vec::Vec::with_capacity(ends.len());
+        for size in item_sizes(&ends)? {
+            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+            decoder.unclaim_bytes_read(core::mem::size_of::<T>());
+            // The item continues at the depth of the sequence, and from the claimed bytes before `size`
+            let state = decoder.state();
+            decoder.claim_bytes_read(size)?;
+
+            let config = *decoder.config();
+            let mut item =
+                DecoderImpl::with_state(Take::new(decoder.borrow_reader(), size), config, state);
+            vec.push(T::borrow_decode(&mut item)?);
+            item.reader().finish()?;
+        }
+        decoder.decrease_depth();
+        Ok(Indexed(vec))
+    }
+
+    fn borrow_skip<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<(), DecodeError> {
+        skip_indexed(decoder)
+    }
+}
+
+/// A borrowed view of an encoded [Indexed] sequence, that decodes a single item at a time.
+///
+/// Decoding an `IndexedSlice` only reads the length and the offset table, and borrows the encoded items. Finding an item is O(1). The items have to be decoded with the same config as the `IndexedSlice` was decoded with. They are decoded at the depth of the sequence, and count towards the byte limit as if they were decoded together with the value around the sequence.
+///
+/// See [Indexed] for an example.
+pub struct IndexedSlice<'de, T> {
+    len: usize,
+    offsets: &'de [u8],
+    items: &'de [u8],
+    /// The state of the decoder that the sequence was decoded with, to continue from when decoding the items
+    state: DecoderState,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'de, T> IndexedSlice<'de, T> {
+    /// The amount of items in this sequence
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if this sequence has no items
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the encoded bytes of the item at `index`, or `Ok(None)` if `index` is out of bounds.
+    pub fn get_bytes<C: Config>(
+        &self,
+        index: usize,
+        config: C,
+    ) -> Result<Option<&'de [u8]>, DecodeError> {
+        if index >= self.len {
+            return Ok(None);
+        }
+        let endian = config.endianness();
+        let start = match index {
+            0 => 0,
+            _ => self.offset(index - 1, &endian),
+        };
+        let end = self.offset(index, &endian);
+        if start > end || end > self.items.len() as u64 {
+            return Err(DecodeError::InvalidIndexedOffset { index, offset: end });
+        }
+        Ok(Some(&self.items[start as usize..end as usize]))
+    }
+
+    fn offset(&self, index: usize, endian: &Endianness) -> u64 {
+        let start = index * OFFSET_SIZE;
+        let mut bytes = [0u8; OFFSET_SIZE];
+        bytes.copy_from_slice(&self.offsets[start..start + OFFSET_SIZE]);
+        match endian {
+            Endianness::Little => u64::from_le_bytes(bytes),
+            Endianness::Big => u64::from_be_bytes(bytes),
+        }
+    }
+
+    /// Returns an iterator that decodes the items of this sequence one by one.
+    pub fn iter<C: Config>(&self, config: C) -> IndexedSliceIter<'de, T, C> {
+        IndexedSliceIter {
+            slice: *self,
+            index: 0,
+            config,
+        }
+    }
+}
+
+impl<'de, T: BorrowDecode<'de>> IndexedSlice<'de, T> {
+    /// Decodes the item at `index`, or returns `Ok(None)` if `index` is out of bounds.
+    pub fn get<C: Config>(&self, index: usize, config: C) -> Result<Option<T>, DecodeError> {
+        let bytes = match self.get_bytes(index, config)? {
+            Some(bytes) => bytes,
+            None => return Ok(None),
+        };
+        let mut decoder = DecoderImpl::with_state(SliceReader::new(bytes), config, self.state);
+        let item = T::borrow_decode(&mut decoder)?;
+        let remaining = decoder.reader().slice.len();
+        if remaining > 0 {
+            return Err(DecodeError::TrailingBytes { remaining });
+        }
+        Ok(Some(item))
+    }
+}
+
+impl<'de, T> BorrowDecode<'de> for IndexedSlice<'de, T> {
+    fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = crate::de::decode_slice_len(decoder)?;
+        let offsets_len = offset_table_len(len)?;
+        decoder.claim_bytes_read(offsets_len)?;
+        let offsets = decoder.borrow_reader().take_bytes(offsets_len)?;
+        decoder.increase_depth()?;
+        let mut result = Self {
+            len,
+            offsets,
+            items: &[],
+            state: decoder.state(),
+            _marker: PhantomData,
+        };
+        decoder.decrease_depth();
+        let items_len = match len {
+            0 => 0,
+            _ => result.offset(len - 1, &decoder.config().endianness()),
+        };
+        let items_len =
+            usize::try_from(items_len).map_err(|_| DecodeError::InvalidIndexedOffset {
+                index: len - 1,
+                offset: items_len,
+            })?;
+        decoder.claim_bytes_read(items_len)?;
+        result.items = decoder.borrow_reader().take_bytes(items_len)?;
+        Ok(result)
+    }
+}
+
+impl<T> Encode for IndexedSlice<'_, T> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        crate::enc::encode_slice_len(encoder, self.len)?;
+        encoder.writer().write(self.offsets)?;
+        encoder.writer().write(self.items)
+    }
+}
+
+impl<T> Clone for IndexedSlice<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for IndexedSlice<'_, T> {}
+
+impl<T> fmt::Debug for IndexedSlice<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IndexedSlice")
+            .field("len", &self.len)
+            .field("bytes", &self.items.len())
+            .finish()
+    }
+}
+
+/// An iterator that decodes the items of an [IndexedSlice].
+///
+/// After an item fails to decode, the iterator returns `None`.
+pub struct IndexedSliceIter<'de, T, C: Config> {
+    slice: IndexedSlice<'de, T>,
+    index: usize,
+    config: C,
+}
+
+impl<'de, T: BorrowDecode<'de>, C: Config> Iterator for IndexedSliceIter<'de, T, C> {
+    type Item = Result<T, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = self.slice.get(self.index, self.config).transpose()?;
+        self.index = match result {
+            Ok(_) => self.index + 1,
+            Err(_) => self.slice.len,
+        };
+        Some(result)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.index = self.index.saturating_add(n).min(self.slice.len);
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.slice.len - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'de, T: BorrowDecode<'de>, C: Config> ExactSizeIterator for IndexedSliceIter<'de, T, C> {}
+
+fn encode_indexed<T: Encode, E: Encoder>(items: &[T], encoder: &mut E) -> Result<(), EncodeError> {
+    crate::enc::encode_slice_len(encoder, items.len())?;
+    let endian = encoder.config().endianness();
+    let mut size = EncoderImpl::new(SizeWriter::default(), *encoder.config());
+    for item in items {
+        item.encode(&mut size)?;
+        let end = size.writer().bytes_written as u64;
+        let bytes = match endian {
+            Endianness::Little => end.to_le_bytes(),
+            Endianness::Big => end.to_be_bytes(),
+        };
+        encoder.writer().write(&bytes)?;
+    }
+    for item in items {
+        item.encode(encoder)?;
+    }
+    Ok(())
+}
+
+/// The size of the offset table of `len` items
+fn offset_table_len(len: usize) -> Result<usize, DecodeError> {
+    len.checked_mul(OFFSET_SIZE)
+        .ok_or(DecodeError::OutsideUsizeRange(len as u64))
+}
+
+/// Reads the length and the offset table of an [Indexed] sequence
+#[cfg(feature = "alloc")]
+fn decode_offsets<D: Decoder>(decoder: &mut D) -> Result<alloc::vec::Vec<u64>, DecodeError> {
+    let len = crate::de::decode_slice_len(decoder)?;
+    decoder.claim_bytes_read(offset_table_len(len)?)?;
+    let mut ends = alloc::vec::Vec::with_capacity(len);
+    for _ in 0..len {
+        ends.push(read_offset(decoder)?);
+    }
+    Ok(ends)
+}
+
+/// Returns the size of every item, based on the offsets where they end
+#[cfg(feature = "alloc")]
+fn item_sizes(ends: &[u64]) -> Result<impl Iterator<Item = usize> + '_, DecodeError> {
+    let mut start = 0;
+    for (index, &end) in ends.iter().enumerate() {
+        if end < start || usize::try_from(end).is_err() {
+            return Err(DecodeError::InvalidIndexedOffset { index, offset: end });
+        }
+        start = end;
+    }
+    Ok(core::iter::once(0)
+        .chain(ends.iter().copied())
+        .zip(ends)
+        .map(|(start, end)| (end - start) as usize))
+}
+
+#[cfg(feature = "alloc")]
+fn read_offset<D: Decoder>(decoder: &mut D) -> Result<u64, DecodeError> {
+    let mut bytes = [0u8; OFFSET_SIZE];
+    decoder.reader().read(&mut bytes)?;
+    Ok(match decoder.config().endianness() {
+        Endianness::Little => u64::from_le_bytes(bytes),
+        Endianness::Big => u64::from_be_bytes(bytes),
+    })
+}
+
+/// Skips an [Indexed] sequence by only reading the last offset
+#[cfg(feature = "alloc")]
+fn skip_indexed<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+    let len = crate::de::decode_slice_len(decoder)?;
+    if len == 0 {
+        return Ok(());
+    }
+    crate::de::skip_bytes(decoder, offset_table_len(len - 1)?)?;
+    decoder.claim_bytes_read(OFFSET_SIZE)?;
+    let end = read_offset(decoder)?;
+    let end = usize::try_from(end).map_err(|_| DecodeError::InvalidIndexedOffset {
+        index: len - 1,
+        offset: end,
+    })?;
+    crate::de::skip_bytes(decoder, end)
+}
@@ -17,10 +17,11 @@
 //!
 //! |Name  |Default?|Supported types for Encode/Decode|Enabled methods                                                  |Other|
 //! |------|--------|-----------------------------------------|-----------------------------------------------------------------|-----|
-//! |std   | Yes    |`HashMap` and `HashSet`|`decode_from_std_read` and `encode_into_std_write`|
+//! |std   | Yes    |`HashMap` and `HashSet`|`decode_from_std_read` and `encode_into_std_write`, length-delimited streams in the `framed` module|
 //! |alloc | Yes    |All common containers in alloc, like `Vec`, `String`, `Box`|`encode_to_vec`|
 //! |atomic| Yes    |All `Atomic*` integer types, e.g. `AtomicUsize`, and `AtomicBool`||
-//! |derive| Yes    |||Enables the `BorrowDecode`, `Decode` and `Encode` derive macros|
+//! |derive| Yes    |||Enables the `BorrowDecode`, `Decode`, `Encode` and `Schema` derive macros|
+//! |tokio | No     ||`decode_from_async_read`, `encode_into_async_write` and `AsyncReadDecoder`|
 //! |serde | No     |`Compat` and `BorrowCompat`, which will work for all types that implement serde's traits|serde-specific encode/decode functions in the [serde] module|Note: There are several [known issues](serde/index.html#known-issues) when using serde and bincode|
 //!
 //! # Which functions to use
@@ -31,8 +32,11 @@
 //! |---|---|---
 //! |You're working with [`fs::File`] or [`net::TcpStream`]|[`encode_into_std_write`]|[`decode_from_std_read`]|
 //! |you're working with in-memory buffers|[`encode_to_vec`]|[`decode_from_slice`]|
+//! |You're working with in-memory buffers that should contain exactly one value|[`encode_to_vec`]|[`decode_from_slice_exact`]|
+//! |You're working with `tokio`'s `AsyncRead` and `AsyncWrite`, e.g. `tokio::net::TcpStream`|`encode_into_async_write`|`decode_from_async_read`, or `AsyncReadDecoder` for a stream of values|
 //! |You want to use a custom [Reader] and [Writer]|[`encode_into_writer`]|[`decode_from_reader`]|
 //! |You're working with pre-allocated buffers or on embedded targets|[`encode_into_slice`]|[`decode_from_slice`]|
+//! |You want to detect when data was encoded with a different layout of the type|[`encode_with_fingerprint`]|[`decode_with_fingerprint`]|
 //!
 //! **Note:** If you're using `serde`, use `bincode::serde::...` instead of `bincode::...`
 //!
@@ -82,11 +86,13 @@ extern crate std;
 
 mod atomic;
 mod features;
+mod indexed;
+mod opaque;
 pub(crate) mod utils;
 pub(crate) mod varint;
 
 use de::{read::Reader, Decoder};
-use enc::write::Writer;
+use enc::{write::Writer, Encoder};
 
 #[cfg(any(
     feature = "alloc",
@@ -101,9 +107,13 @@ pub mod config;
 pub mod de;
 pub mod enc;
 pub mod error;
+pub mod schema;
 
 pub use de::{BorrowDecode, Decode};
 pub use enc::Encode;
+pub use indexed::{Indexed, IndexedSlice, IndexedSliceIter};
+pub use opaque::Opaque;
+pub use schema::Schema;
 
 use config::Config;
 
@@ -138,12 +148,64 @@ pub fn encode_into_writer<E: enc::Encode, W: Writer, C: Config>(
     Ok(())
 }
 
+/// Encode the given value into the given slice, prefixed with the [fingerprint] of `E`. Returns the amount of bytes that have been written.
+///
+/// The fingerprint is always written as 8 little endian bytes, regardless of the config. Use [decode_with_fingerprint] to decode the value, which will fail if the layout of the type has changed in the meantime.
+///
+/// See the [config] module for more information on configurations.
+///
+/// ```
+/// # #[cfg(all(feature = "alloc", feature = "derive"))]
+/// # {
+/// #[derive(bincode::Encode, bincode::Decode, bincode::Schema, PartialEq, Debug)]
+/// struct Message {
+///     id: u32,
+///     text: String,
+/// }
+///
+/// let config = bincode::config::standard();
+/// let message = Message { id: 5, text: String::from("Hello") };
+///
+/// let mut buffer = [0u8; 32];
+/// let len = bincode::encode_with_fingerprint(&message, &mut buffer, config).unwrap();
+/// let (decoded, _): (Message, usize) =
+///     bincode::decode_with_fingerprint(&buffer[..len], config).unwrap();
+/// assert_eq!(decoded, message);
+///
+/// // A different type is rejected
+/// let result = bincode::decode_with_fingerprint::<(u32, String), _>(&buffer[..len], config);
+/// assert!(matches!(
+///     result,
+///     Err(bincode::error::DecodeError::FingerprintMismatch { .. })
+/// ));
+/// # }
+/// ```
+///
+/// [fingerprint]: schema/fn.fingerprint.html
+/// [config]: config/index.html
+pub fn encode_with_fingerprint<E: enc::Encode + schema::Schema, C: Config>(
+    val: E,
+    dst: &mut [u8],
+    config: C,
+) -> Result<usize, error::EncodeError> {
+    let writer = enc::write::SliceWriter::new(dst);
+    let mut encoder = enc::EncoderImpl::<_, C>::new(writer, config);
+    encoder
+        .writer()
+        .write(&schema::fingerprint::<E>().to_le_bytes())?;
+    val.encode(&mut encoder)?;
+    Ok(encoder.into_writer().bytes_written())
+}
+
 /// Attempt to decode a given type `D` from the given slice. Returns the decoded output and the amount of bytes read.
 ///
 /// Note that this does not work with borrowed types like `&str` or `&[u8]`. For that use [borrow_decode_from_slice].
 ///
+/// Bytes after the decoded value are ignored, unless the config was created with [with_reject_trailing_bytes]. To always reject them, use [decode_from_slice_exact].
+///
 /// See the [config] module for more information on configurations.
 ///
+/// [with_reject_trailing_bytes]: config/struct.Configuration.html#method.with_reject_trailing_bytes
 /// [config]: config/index.html
 pub fn decode_from_slice<D: de::Decode, C: Config>(
     src: &[u8],
@@ -152,14 +214,73 @@ pub fn decode_from_slice<D: de::Decode, C: Config>(
     let reader = de::read::SliceReader::new(src);
     let mut decoder = de::DecoderImpl::<_, C>::new(reader, config);
     let result = D::decode(&mut decoder)?;
-    let bytes_read = src.len() - decoder.reader().slice.len();
-    Ok((result, bytes_read))
+    let remaining = decoder.reader().slice.len();
+    if C::REJECT_TRAILING_BYTES && remaining > 0 {
+        return Err(error::DecodeError::TrailingBytes { remaining });
+    }
+    Ok((result, src.len() - remaining))
+}
+
+/// Attempt to decode a given type `D` from the given slice, like [decode_from_slice]. If decoding fails, the error comes with the path to the value that failed and the offset where it failed.
+///
+/// See [ContextError] for an example.
+///
+/// [ContextError]: error/struct.ContextError.html
+pub fn decode_from_slice_with_context<D: de::Decode, C: Config>(
+    src: &[u8],
+    config: C,
+) -> Result<(D, usize), error::ContextError> {
+    let reader = de::read::SliceReader::new(src);
+    let mut decoder = de::DecoderImpl::<_, C>::new(reader, config).with_error_path();
+    let result = D::decode(&mut decoder).map_err(|e| {
+        let offset = src.len() - decoder.reader().slice.len();
+        decoder.context_error(e, offset)
+    })?;
+    let remaining = decoder.reader().slice.len();
+    if C::REJECT_TRAILING_BYTES && remaining > 0 {
+        let offset = src.len() - remaining;
+        return Err(decoder.context_error(error::DecodeError::TrailingBytes { remaining }, offset));
+    }
+    Ok((result, src.len() - remaining))
+}
+
+/// Attempt to decode a given type `D` from the given slice. Unlike [decode_from_slice], this fails with [DecodeError::TrailingBytes] if `src` contains more bytes than the decoded value.
+///
+/// Note that this does not work with borrowed types like `&str` or `&[u8]`. For that use [borrow_decode_from_slice_exact].
+///
+/// See the [config] module for more information on configurations.
+///
+/// ```
+/// let config = bincode::config::standard();
+/// let value: u32 = bincode::decode_from_slice_exact(&[5], config).unwrap();
+/// assert_eq!(value, 5);
+///
+/// let result = bincode::decode_from_slice_exact::<u32, _>(&[5, 0], config);
+/// assert!(matches!(result, Err(bincode::error::DecodeError::TrailingBytes { remaining: 1 })));
+/// ```
+///
+/// [DecodeError::TrailingBytes]: error/enum.DecodeError.html#variant.TrailingBytes
+/// [config]: config/index.html
+pub fn decode_from_slice_exact<D: de::Decode, C: Config>(
+    src: &[u8],
+    config: C,
+) -> Result<D, error::DecodeError> {
+    let reader = de::read::SliceReader::new(src);
+    let mut decoder = de::DecoderImpl::<_, C>::new(reader, config);
+    let result = D::decode(&mut decoder)?;
+    match decoder.reader().slice.len() {
+        0 => Ok(result),
+        remaining => Err(error::DecodeError::TrailingBytes { remaining }),
+    }
 }
 
 /// Attempt to decode a given type `D` from the given slice. Returns the decoded output and the amount of bytes read.
 ///
+/// Bytes after the decoded value are ignored, unless the config was created with [with_reject_trailing_bytes]. To always reject them, use [borrow_decode_from_slice_exact].
+///
 /// See the [config] module for more information on configurations.
 ///
+/// [with_reject_trailing_bytes]: config/struct.Configuration.html#method.with_reject_trailing_bytes
 /// [config]: config/index.html
 pub fn borrow_decode_from_slice<'a, D: de::BorrowDecode<'a>, C: Config>(
     src: &'a [u8],
@@ -168,8 +289,84 @@ pub fn borrow_decode_from_slice<'a, D: de::BorrowDecode<'a>, C: Config>(
     let reader = de::read::SliceReader::new(src);
     let mut decoder = de::DecoderImpl::<_, C>::new(reader, config);
     let result = D::borrow_decode(&mut decoder)?;
-    let bytes_read = src.len() - decoder.reader().slice.len();
-    Ok((result, bytes_read))
+    let remaining = decoder.reader().slice.len();
+    if C::REJECT_TRAILING_BYTES && remaining > 0 {
+        return Err(error::DecodeError::TrailingBytes { remaining });
+    }
+    Ok((result, src.len() - remaining))
+}
+
+/// Attempt to decode a given type `D` from the given slice, like [borrow_decode_from_slice]. If decoding fails, the error comes with the path to the value that failed and the offset where it failed.
+///
+/// See [ContextError] for an example.
+///
+/// [ContextError]: error/struct.ContextError.html
+pub fn borrow_decode_from_slice_with_context<'a, D: de::BorrowDecode<'a>, C: Config>(
+    src: &'a [u8],
+    config: C,
+) -> Result<(D, usize), error::ContextError> {
+    let reader = de::read::SliceReader::new(src);
+    let mut decoder = de::DecoderImpl::<_, C>::new(reader, config).with_error_path();
+    let result = D::borrow_decode(&mut decoder).map_err(|e| {
+        let offset = src.len() - decoder.reader().slice.len();
+        decoder.context_error(e, offset)
+    })?;
+    let remaining = decoder.reader().slice.len();
+    if C::REJECT_TRAILING_BYTES && remaining > 0 {
+        let offset = src.len() - remaining;
+        return Err(decoder.context_error(error::DecodeError::TrailingBytes { remaining }, offset));
+    }
+    Ok((result, src.len() - remaining))
+}
+
+/// Attempt to decode a given type `D` from the given slice. Unlike [borrow_decode_from_slice], this fails with [DecodeError::TrailingBytes] if `src` contains more bytes than the decoded value.
+///
+/// See the [config] module for more information on configurations.
+///
+/// [DecodeError::TrailingBytes]: error/enum.DecodeError.html#variant.TrailingBytes
+/// [config]: config/index.html
+pub fn borrow_decode_from_slice_exact<'a, D: de::BorrowDecode<'a>, C: Config>(
+    src: &'a [u8],
+    config: C,
+) -> Result<D, error::DecodeError> {
+    let reader = de::read::SliceReader::new(src);
+    let mut decoder = de::DecoderImpl::<_, C>::new(reader, config);
+    let result = D::borrow_decode(&mut decoder)?;
+    match decoder.reader().slice.len() {
+        0 => Ok(result),
+        remaining => Err(error::DecodeError::TrailingBytes { remaining }),
+    }
+}
+
+/// Attempt to decode a given type `D` from the given slice, which should be prefixed with the [fingerprint] of `D`. Returns the decoded output and the amount of bytes read, including the fingerprint.
+///
+/// If the fingerprint in `src` does not match the fingerprint of `D`, [DecodeError::FingerprintMismatch] is returned. See [encode_with_fingerprint] for an example.
+///
+/// See the [config] module for more information on configurations.
+///
+/// [fingerprint]: schema/fn.fingerprint.html
+/// [DecodeError::FingerprintMismatch]: error/enum.DecodeError.html#variant.FingerprintMismatch
+/// [config]: config/index.html
+pub fn decode_with_fingerprint<D: de::Decode + schema::Schema, C: Config>(
+    src: &[u8],
+    config: C,
+) -> Result<(D, usize), error::DecodeError> {
+    let reader = de::read::SliceReader::new(src);
+    let mut decoder = de::DecoderImpl::<_, C>::new(reader, config);
+    decoder.claim_bytes_read(8)?;
+    let mut found = [0u8; 8];
+    decoder.reader().read(&mut found)?;
+    let found = u64::from_le_bytes(found);
+    let expected = schema::fingerprint::<D>();
+    if found != expected {
+        return Err(error::DecodeError::FingerprintMismatch { expected, found });
+    }
+    let result = D::decode(&mut decoder)?;
+    let remaining = decoder.reader().slice.len();
+    if C::REJECT_TRAILING_BYTES && remaining > 0 {
+        return Err(error::DecodeError::TrailingBytes { remaining });
+    }
+    Ok((result, src.len() - remaining))
 }
 
 /// Attempt to decode a given type `D` from the given [Reader].
@@ -181,8 +378,42 @@ pub fn decode_from_reader<D: de::Decode, R: Reader, C: Config>(
     reader: R,
     config: C,
 ) -> Result<D, error::DecodeError> {
-    let mut decoder = de::DecoderImpl::<_, C>::new(reader, config);
-    D::decode(&mut decoder)
+    decode_from_reader_with_count(reader, config).map(|(result, _)| result)
+}
+
+/// Attempt to decode a given type `D` from the given [Reader]. Returns the decoded output and the amount of bytes read, like [decode_from_slice].
+///
+/// Bytes after the decoded value are never read, so [with_reject_trailing_bytes] does not apply.
+///
+/// See the [config] module for more information on configurations.
+///
+/// [with_reject_trailing_bytes]: config/struct.Configuration.html#method.with_reject_trailing_bytes
+/// [config]: config/index.html
+pub fn decode_from_reader_with_count<D: de::Decode, R: Reader, C: Config>(
+    reader: R,
+    config: C,
+) -> Result<(D, usize), error::DecodeError> {
+    let mut decoder = de::DecoderImpl::<_, C>::new(de::read::CountingReader::new(reader), config);
+    let result = D::decode(&mut decoder)?;
+    Ok((result, decoder.reader().bytes_read()))
+}
+
+/// Attempt to decode a given type `D` from the given [Reader], like [decode_from_reader_with_count]. If decoding fails, the error comes with the path to the value that failed and the offset where it failed.
+///
+/// See [ContextError] for an example.
+///
+/// [ContextError]: error/struct.ContextError.html
+pub fn decode_from_reader_with_context<D: de::Decode, R: Reader, C: Config>(
+    reader: R,
+    config: C,
+) -> Result<(D, usize), error::ContextError> {
+    let reader = de::read::CountingReader::new(reader);
+    let mut decoder = de::DecoderImpl::<_, C>::new(reader, config).with_error_path();
+    let result = D::decode(&mut decoder).map_err(|e| {
+        let offset = decoder.reader().bytes_read();
+        decoder.context_error(e, offset)
+    })?;
+    Ok((result, decoder.reader().bytes_read()))
 }
 
 // TODO: Currently our doctests fail when trying to include the specs because the specs depend on `derive` and `alloc`.
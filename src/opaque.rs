@@ -0,0 +1,72 @@
+use crate::{
+    de::{read::Take, BorrowDecode, BorrowDecoder, Decode, Decoder, DecoderImpl},
+    enc::{Encode, Encoder},
+    error::{DecodeError, EncodeError},
+};
+
+/// A value that is encoded with its length in bytes in front of it.
+///
+/// The length is encoded the same way as the length of a slice, so an `Opaque<T>` has the same encoding as a `Vec<u8>` that contains the encoded `T`. This means that a decoder can skip the value without knowing its type, e.g. with `bincode::de::skip::<Vec<u8>>`, or keep the encoded `T` as raw bytes by decoding a `&[u8]` or `Vec<u8>`.
+///
+/// Skipping an `Opaque<T>` with [skip](de/fn.skip.html) never looks at the contents of `T`.
+///
+/// When decoding, `T` must use exactly the amount of bytes that was encoded in the length. Reading past the end of the value results in [DecodeError::UnexpectedEnd], and bytes that are left over result in [DecodeError::TrailingBytes].
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use bincode::Opaque;
+///
+/// let config = bincode::config::standard();
+/// let bytes = bincode::encode_to_vec((Opaque(String::from("a long log line")), 5u32), config).unwrap();
+///
+/// let ((payload, number), _): ((Vec<u8>, u32), usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+/// assert_eq!(payload.len(), 16);
+/// assert_eq!(number, 5);
+///
+/// let ((Opaque(line), number), _): ((Opaque<String>, u32), usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+/// assert_eq!(line, "a long log line");
+/// assert_eq!(number, 5);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Opaque<T>(pub T);
+
+impl<T: Encode> Encode for Opaque<T> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        crate::enc::encode_length_prefixed(encoder, |encoder| self.0.encode(encoder))
+    }
+}
+
+impl<T: Decode> Decode for Opaque<T> {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = crate::de::decode_slice_len(decoder)?;
+        // Continue at the depth of the outer decoder, and from its claimed bytes before `len`, so that the bytes of the value are not counted twice
+        let state = decoder.state();
+        decoder.claim_bytes_read(len)?;
+        let config = *decoder.config();
+        let mut inner = DecoderImpl::with_state(Take::new(decoder.reader(), len), config, state);
+        let value = T::decode(&mut inner)?;
+        inner.reader().finish()?;
+        Ok(Opaque(value))
+    }
+
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        let len = crate::de::decode_slice_len(decoder)?;
+        crate::de::skip_bytes(decoder, len)
+    }
+}
+
+impl<'de, T: BorrowDecode<'de>> BorrowDecode<'de> for Opaque<T> {
+    fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = crate::de::decode_slice_len(decoder)?;
+        // Continue at the depth of the outer decoder, and from its claimed bytes before `len`, so that the bytes of the value are not counted twice
+        let state = decoder.state();
+        decoder.claim_bytes_read(len)?;
+        let config = *decoder.config();
+        let mut inner =
+            DecoderImpl::with_state(Take::new(decoder.borrow_reader(), len), config, state);
+        let value = T::borrow_decode(&mut inner)?;
+        inner.reader().finish()?;
+        Ok(Opaque(value))
+    }
+}
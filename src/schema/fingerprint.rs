@@ -0,0 +1,169 @@
+use super::{Field, Schema, Type};
+use crate::config::EnumTagType;
+
+/// Returns a stable 64-bit fingerprint of the layout of `T`.
+///
+/// The fingerprint is computed from the [Schema] of `T`, so it changes whenever the encoded layout changes, e.g. when a field is added, removed or reordered, when the type of a field changes, or when an enum variant is added or gets a different index. Names of types, fields and variants are not part of the fingerprint, as renaming them does not change the encoded data.
+///
+/// The fingerprint is the same for every build and platform, so it can be stored alongside encoded data. See [encode_with_fingerprint] and [decode_with_fingerprint].
+///
+/// Recursive types are detected by the `type_name` of the struct or enum. Note that the fingerprint does not depend on the config, so it won't detect e.g. a different endianness, or a different enum tag type for enums without `#[bincode(tag_type)]`.
+///
+/// [encode_with_fingerprint]: ../fn.encode_with_fingerprint.html
+/// [decode_with_fingerprint]: ../fn.decode_with_fingerprint.html
+pub fn fingerprint<T: Schema + ?Sized>() -> u64 {
+    let mut hasher = Hasher::new();
+    hasher.write_type(T::SCHEMA, None);
+    hasher.finish()
+}
+
+/// The structs and enums that are currently being hashed, used to detect recursive types.
+struct Parent<'a> {
+    type_name: &'static str,
+    parent: Option<&'a Parent<'a>>,
+}
+
+/// A FNV-1a hasher. `core::hash::Hasher` implementations are not guaranteed to be stable between releases, so we use our own.
+struct Hasher {
+    state: u64,
+}
+
+impl Hasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    const fn new() -> Self {
+        Self {
+            state: Self::OFFSET_BASIS,
+        }
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= u64::from(*byte);
+            self.state = self.state.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn write_u8(&mut self, value: u8) {
+        self.write(&[value]);
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_type(&mut self, ty: Type, parent: Option<&Parent<'_>>) {
+        match ty {
+            Type::Unit => self.write_u8(0),
+            Type::Bool => self.write_u8(1),
+            Type::U8 => self.write_u8(2),
+            Type::U16 => self.write_u8(3),
+            Type::U32 => self.write_u8(4),
+            Type::U64 => self.write_u8(5),
+            Type::U128 => self.write_u8(6),
+            Type::Usize => self.write_u8(7),
+            Type::I8 => self.write_u8(8),
+            Type::I16 => self.write_u8(9),
+            Type::I32 => self.write_u8(10),
+            Type::I64 => self.write_u8(11),
+            Type::I128 => self.write_u8(12),
+            Type::Isize => self.write_u8(13),
+            Type::F32 => self.write_u8(14),
+            Type::F64 => self.write_u8(15),
+            Type::Char => self.write_u8(16),
+            Type::Str => self.write_u8(17),
+            Type::Option(inner) => {
+                self.write_u8(18);
+                self.write_type(inner(), parent);
+            }
+            Type::Seq(inner) => {
+                self.write_u8(19);
+                self.write_type(inner(), parent);
+            }
+            Type::Array { element, len } => {
+                self.write_u8(20);
+                self.write_u64(len as u64);
+                self.write_type(element(), parent);
+            }
+            Type::Map { key, value } => {
+                self.write_u8(21);
+                self.write_type(key(), parent);
+                self.write_type(value(), parent);
+            }
+            Type::Tuple(elements) => {
+                self.write_u8(22);
+                self.write_u64(elements.len() as u64);
+                for element in elements {
+                    self.write_type(element(), parent);
+                }
+            }
+            Type::Struct {
+                type_name, fields, ..
+            } => {
+                let type_name = type_name();
+                if self.write_recursion(type_name, parent) {
+                    return;
+                }
+                let parent = Parent { type_name, parent };
+                self.write_u8(23);
+                self.write_fields(fields, &parent);
+            }
+            Type::Enum {
+                type_name,
+                tag_type,
+                variants,
+                ..
+            } => {
+                let type_name = type_name();
+                if self.write_recursion(type_name, parent) {
+                    return;
+                }
+                let parent = Parent { type_name, parent };
+                self.write_u8(24);
+                self.write_u8(match tag_type {
+                    None => 0,
+                    Some(EnumTagType::U8) => 1,
+                    Some(EnumTagType::U16) => 2,
+                    Some(EnumTagType::U32) => 3,
+                });
+                self.write_u64(variants.len() as u64);
+                for variant in variants {
+                    self.write_u64(u64::from(variant.index));
+                    self.write_fields(variant.fields, &parent);
+                }
+            }
+            Type::Opaque => self.write_u8(25),
+        }
+    }
+
+    fn write_fields(&mut self, fields: &[Field], parent: &Parent<'_>) {
+        self.write_u64(fields.len() as u64);
+        for field in fields {
+            self.write_type((field.schema)(), Some(parent));
+        }
+    }
+
+    /// If `type_name` is already being hashed, write a reference to it and return `true`.
+    fn write_recursion(
+        &mut self,
+        type_name: &'static str,
+        mut parent: Option<&Parent<'_>>,
+    ) -> bool {
+        let mut distance = 0u64;
+        while let Some(p) = parent {
+            if p.type_name == type_name {
+                self.write_u8(26);
+                self.write_u64(distance);
+                return true;
+            }
+            distance += 1;
+            parent = p.parent;
+        }
+        false
+    }
+
+    const fn finish(&self) -> u64 {
+        self.state
+    }
+}
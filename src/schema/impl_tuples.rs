@@ -0,0 +1,36 @@
+use super::{schema_of, Schema, Type};
+
+macro_rules! impl_tuple {
+    () => {};
+    ($first:ident $(, $extra:ident)*) => {
+        impl<$first $(, $extra)*> Schema for ($first, $($extra, )*)
+        where
+            $first: Schema,
+        $(
+            $extra : Schema,
+        )*
+        {
+            const SCHEMA: Type = Type::Tuple(&[
+                schema_of::<$first>,
+                $(schema_of::<$extra>, )*
+            ]);
+        }
+    }
+}
+
+impl_tuple!(A);
+impl_tuple!(A, B);
+impl_tuple!(A, B, C);
+impl_tuple!(A, B, C, D);
+impl_tuple!(A, B, C, D, E);
+impl_tuple!(A, B, C, D, E, F);
+impl_tuple!(A, B, C, D, E, F, G);
+impl_tuple!(A, B, C, D, E, F, G, H);
+impl_tuple!(A, B, C, D, E, F, G, H, I);
+impl_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+impl_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M);
+impl_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N);
+impl_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
+impl_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
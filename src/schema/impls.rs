@@ -0,0 +1,203 @@
+use super::{schema_of, Field, Schema, Type, Variant};
+use crate::config::EnumTagType;
+use core::{
+    cell::{Cell, RefCell},
+    cmp::Reverse,
+    marker::PhantomData,
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
+    },
+    ops::{Bound, Range, RangeInclusive},
+    time::Duration,
+};
+
+macro_rules! impl_schema {
+    ($($ty:ty => $schema:expr),* $(,)?) => {
+        $(
+            impl Schema for $ty {
+                const SCHEMA: Type = $schema;
+            }
+        )*
+    };
+}
+
+impl_schema! {
+    () => Type::Unit,
+    bool => Type::Bool,
+    u8 => Type::U8,
+    NonZeroU8 => Type::U8,
+    u16 => Type::U16,
+    NonZeroU16 => Type::U16,
+    u32 => Type::U32,
+    NonZeroU32 => Type::U32,
+    u64 => Type::U64,
+    NonZeroU64 => Type::U64,
+    u128 => Type::U128,
+    NonZeroU128 => Type::U128,
+    usize => Type::Usize,
+    NonZeroUsize => Type::Usize,
+    i8 => Type::I8,
+    NonZeroI8 => Type::I8,
+    i16 => Type::I16,
+    NonZeroI16 => Type::I16,
+    i32 => Type::I32,
+    NonZeroI32 => Type::I32,
+    i64 => Type::I64,
+    NonZeroI64 => Type::I64,
+    i128 => Type::I128,
+    NonZeroI128 => Type::I128,
+    isize => Type::Isize,
+    NonZeroIsize => Type::Isize,
+    f32 => Type::F32,
+    f64 => Type::F64,
+    char => Type::Char,
+    str => Type::Str,
+}
+
+impl<T: ?Sized> Schema for PhantomData<T> {
+    const SCHEMA: Type = Type::Unit;
+}
+
+impl<T: Schema> Schema for Wrapping<T> {
+    const SCHEMA: Type = T::SCHEMA;
+}
+
+impl<T: Schema> Schema for Reverse<T> {
+    const SCHEMA: Type = T::SCHEMA;
+}
+
+impl<T: Schema> Schema for [T] {
+    const SCHEMA: Type = Type::Seq(schema_of::<T>);
+}
+
+impl<T: Schema, const N: usize> Schema for [T; N] {
+    const SCHEMA: Type = Type::Array {
+        element: schema_of::<T>,
+        len: N,
+    };
+}
+
+impl<T: Schema> Schema for Option<T> {
+    const SCHEMA: Type = Type::Option(schema_of::<T>);
+}
+
+impl<T: Schema, U: Schema> Schema for Result<T, U> {
+    const SCHEMA: Type = Type::Enum {
+        name: "Result",
+        type_name: core::any::type_name::<Self>,
+        tag_type: Some(EnumTagType::U32),
+        variants: &[
+            Variant {
+                name: "Ok",
+                index: 0,
+                fields: &[Field {
+                    name: None,
+                    schema: schema_of::<T>,
+                }],
+            },
+            Variant {
+                name: "Err",
+                index: 1,
+                fields: &[Field {
+                    name: None,
+                    schema: schema_of::<U>,
+                }],
+            },
+        ],
+    };
+}
+
+impl<T: Schema> Schema for Cell<T> {
+    const SCHEMA: Type = T::SCHEMA;
+}
+
+impl<T: Schema + ?Sized> Schema for RefCell<T> {
+    const SCHEMA: Type = T::SCHEMA;
+}
+
+impl Schema for Duration {
+    const SCHEMA: Type = Type::Struct {
+        name: "Duration",
+        type_name: core::any::type_name::<Self>,
+        fields: &[
+            Field {
+                name: Some("secs"),
+                schema: schema_of::<u64>,
+            },
+            Field {
+                name: Some("nanos"),
+                schema: schema_of::<u32>,
+            },
+        ],
+    };
+}
+
+impl<T: Schema> Schema for Range<T> {
+    const SCHEMA: Type = Type::Struct {
+        name: "Range",
+        type_name: core::any::type_name::<Self>,
+        fields: &[
+            Field {
+                name: Some("start"),
+                schema: schema_of::<T>,
+            },
+            Field {
+                name: Some("end"),
+                schema: schema_of::<T>,
+            },
+        ],
+    };
+}
+
+impl<T: Schema> Schema for RangeInclusive<T> {
+    const SCHEMA: Type = Type::Struct {
+        name: "RangeInclusive",
+        type_name: core::any::type_name::<Self>,
+        fields: &[
+            Field {
+                name: Some("start"),
+                schema: schema_of::<T>,
+            },
+            Field {
+                name: Some("end"),
+                schema: schema_of::<T>,
+            },
+        ],
+    };
+}
+
+impl<T: Schema> Schema for Bound<T> {
+    const SCHEMA: Type = Type::Enum {
+        name: "Bound",
+        type_name: core::any::type_name::<Self>,
+        tag_type: Some(EnumTagType::U32),
+        variants: &[
+            Variant {
+                name: "Unbounded",
+                index: 0,
+                fields: &[],
+            },
+            Variant {
+                name: "Included",
+                index: 1,
+                fields: &[Field {
+                    name: None,
+                    schema: schema_of::<T>,
+                }],
+            },
+            Variant {
+                name: "Excluded",
+                index: 2,
+                fields: &[Field {
+                    name: None,
+                    schema: schema_of::<T>,
+                }],
+            },
+        ],
+    };
+}
+
+impl<T: Schema + ?Sized> Schema for &T {
+    const SCHEMA: Type = T::SCHEMA;
+}
@@ -0,0 +1,198 @@
+//! Type descriptions that document how a type is laid out on the wire.
+//!
+//! Bincode does not write any metadata, so the only way to know how a message is laid out is by looking at the types that were used to encode it. The [Schema] trait exposes that information as a tree of [Type]s. This can be used to e.g. generate documentation like the [specification](../spec/index.html) for your own types, or to check that two types are laid out the same.
+//!
+//! This trait will be automatically implemented if you enable the `derive` feature and add `#[derive(bincode::Schema)]` to your type.
+//!
+//! ```
+//! # #[cfg(feature = "derive")]
+//! # {
+//! use bincode::schema::{Schema, Type};
+//!
+//! #[derive(bincode::Schema)]
+//! enum Shape {
+//!     Point,
+//!     Circle { radius: f32 },
+//! }
+//!
+//! match Shape::SCHEMA {
+//!     Type::Enum { name, variants, .. } => {
+//!         assert_eq!(name, "Shape");
+//!         assert_eq!(variants[1].name, "Circle");
+//!         assert_eq!(variants[1].index, 1);
+//!         assert_eq!(variants[1].fields[0].name, Some("radius"));
+//!         assert!(matches!((variants[1].fields[0].schema)(), Type::F32));
+//!     }
+//!     _ => unreachable!(),
+//! }
+//! # }
+//! ```
+
+use crate::config::EnumTagType;
+
+mod fingerprint;
+mod impl_tuples;
+mod impls;
+
+pub use self::fingerprint::fingerprint;
+
+/// Describes how a type is laid out when it is encoded with bincode.
+///
+/// This trait will be automatically implemented if you enable the `derive` feature and add `#[derive(bincode::Schema)]` to your type.
+///
+/// # Implementing this trait manually
+///
+/// The schema should describe the values in the same order as they are written by the [Encode] implementation of the type. For this struct:
+///
+/// ```
+/// struct Entity {
+///     pub x: f32,
+///     pub y: f32,
+/// }
+/// ```
+///
+/// It will look something like:
+///
+/// ```
+/// # struct Entity {
+/// #     pub x: f32,
+/// #     pub y: f32,
+/// # }
+/// use bincode::schema::{schema_of, Field, Schema, Type};
+///
+/// impl Schema for Entity {
+///     const SCHEMA: Type = Type::Struct {
+///         name: "Entity",
+///         type_name: core::any::type_name::<Self>,
+///         fields: &[
+///             Field {
+///                 name: Some("x"),
+///                 schema: schema_of::<f32>,
+///             },
+///             Field {
+///                 name: Some("y"),
+///                 schema: schema_of::<f32>,
+///             },
+///         ],
+///     };
+/// }
+/// ```
+///
+/// Nested types are referenced by a function that returns their schema instead of by their schema directly. This allows recursive types, like a tree, to implement `Schema`. When walking a schema, keep track of the structs and enums that you've already seen to avoid an infinite loop. Compare them by their `type_name`, as different types can have the same `name`.
+///
+/// [Encode]: ../enc/trait.Encode.html
+pub trait Schema {
+    /// The description of this type.
+    const SCHEMA: Type;
+}
+
+/// Returns the [Schema] of `T`. This function can be used to reference a nested type in a [Type].
+pub fn schema_of<T: Schema + ?Sized>() -> Type {
+    T::SCHEMA
+}
+
+/// The description of a type. See [Schema] for more information.
+///
+/// How integers and lengths are encoded depends on the config that is used. See the [config](../config/index.html) module for more information.
+#[derive(Debug, Copy, Clone)]
+#[non_exhaustive]
+pub enum Type {
+    /// A value that is not written at all, e.g. `()` or `PhantomData<T>`.
+    Unit,
+    /// A `bool`, encoded as a single byte.
+    Bool,
+    /// An `u8`, always encoded as a single byte.
+    U8,
+    /// An `u16`.
+    U16,
+    /// An `u32`.
+    U32,
+    /// An `u64`.
+    U64,
+    /// An `u128`.
+    U128,
+    /// An `usize`, encoded as an `u64`.
+    Usize,
+    /// An `i8`, always encoded as a single byte.
+    I8,
+    /// An `i16`.
+    I16,
+    /// An `i32`.
+    I32,
+    /// An `i64`.
+    I64,
+    /// An `i128`.
+    I128,
+    /// An `isize`, encoded as an `i64`.
+    Isize,
+    /// An `f32`.
+    F32,
+    /// An `f64`.
+    F64,
+    /// A `char`, encoded as UTF-8.
+    Char,
+    /// A string, encoded as a length followed by the UTF-8 bytes.
+    Str,
+    /// An optional value, encoded as a single byte that is `0` for `None` or `1` for `Some`, followed by the value if it is `Some`.
+    Option(fn() -> Type),
+    /// A sequence of values, encoded as a length followed by every value.
+    Seq(fn() -> Type),
+    /// An array of a fixed length, encoded as every value without a length.
+    Array {
+        /// The type of the values
+        element: fn() -> Type,
+        /// The amount of values
+        len: usize,
+    },
+    /// A map, encoded as a length followed by every key and value.
+    Map {
+        /// The type of the keys
+        key: fn() -> Type,
+        /// The type of the values
+        value: fn() -> Type,
+    },
+    /// A tuple, encoded as every value in order.
+    Tuple(&'static [fn() -> Type]),
+    /// A struct, encoded as every field in order.
+    Struct {
+        /// The name of the struct
+        name: &'static str,
+        /// Returns the full name of the struct, including its module path and generic arguments, usually `core::any::type_name::<Self>`. This tells different structs with the same `name` apart.
+        type_name: fn() -> &'static str,
+        /// The fields of the struct, in the order they are encoded
+        fields: &'static [Field],
+    },
+    /// An enum, encoded as the index of the variant, followed by the fields of the variant.
+    Enum {
+        /// The name of the enum
+        name: &'static str,
+        /// Returns the full name of the enum, including its module path and generic arguments, usually `core::any::type_name::<Self>`. This tells different enums with the same `name` apart.
+        type_name: fn() -> &'static str,
+        /// The type that the index of the variant is encoded as, or `None` if it is the [enum_tag_type](crate::config::Config::enum_tag_type) of the config
+        tag_type: Option<EnumTagType>,
+        /// The variants of the enum
+        variants: &'static [Variant],
+    },
+    /// A value of which the layout is not known to bincode, e.g. a field that is encoded with serde.
+    Opaque,
+}
+
+/// A field of a [Type::Struct] or a [Variant].
+#[derive(Debug, Copy, Clone)]
+pub struct Field {
+    /// The name of the field, or `None` if this is a field of a tuple struct or tuple variant.
+    pub name: Option<&'static str>,
+    /// The type of the field
+    pub schema: fn() -> Type,
+}
+
+/// A variant of a [Type::Enum].
+#[derive(Debug, Copy, Clone)]
+pub struct Variant {
+    /// The name of the variant
+    pub name: &'static str,
+    /// The index of the variant, which is encoded before the fields of the variant
+    pub index: u32,
+    /// The fields of the variant, in the order they are encoded
+    pub fields: &'static [Field],
+}
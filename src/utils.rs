@@ -1,3 +1,134 @@
 pub trait Sealed {}
 
-impl<'a, T> Sealed for &'a mut T where T: Sealed {}
+impl<T> Sealed for &mut T where T: Sealed {}
+
+/// Proof that `T` is a primitive number type, like `u32` or `f64`, that has a fixed width with the config that this was created with. Every bit pattern is a valid `T`, so a slice of `T` can be read and written as its memory.
+pub(crate) struct FixedWidthNumber<T> {
+    swap_bytes: bool,
+    _marker: core::marker::PhantomData<T>,
+}
+
+impl<T> Clone for FixedWidthNumber<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for FixedWidthNumber<T> {}
+
+impl<T> FixedWidthNumber<T> {
+    /// Returns `None` if `T` is not a primitive number type, or if `T` is encoded as a varint with `config`.
+    pub(crate) fn new<C: crate::config::Config>(config: &C) -> Option<Self> {
+        use crate::config::{Endianness, IntEncoding};
+
+        macro_rules! numbers {
+            ($($ty:ty => $varint:expr),* $(,)?) => {
+                false $(|| (unty::type_equal::<T, $ty>() && (!$varint || config.int_encoding() == IntEncoding::Fixed)))*
+            };
+        }
+        let fixed_width = numbers! {
+            u16 => true,
+            u32 => true,
+            u64 => true,
+            u128 => true,
+            i8 => false,
+            i16 => true,
+            i32 => true,
+            i64 => true,
+            i128 => true,
+            f32 => false,
+            f64 => false,
+        };
+        if !fixed_width {
+            return None;
+        }
+        let native_endian = match config.endianness() {
+            Endianness::Little => cfg!(target_endian = "little"),
+            Endianness::Big => cfg!(target_endian = "big"),
+        };
+        Some(Self {
+            swap_bytes: !native_endian && core::mem::size_of::<T>() > 1,
+            _marker: core::marker::PhantomData,
+        })
+    }
+
+    /// Whether the items are encoded as their memory, without reversing their bytes
+    pub(crate) fn is_native(self) -> bool {
+        !self.swap_bytes
+    }
+
+    /// The memory of `items`
+    pub(crate) fn as_bytes(self, items: &[T]) -> &[u8] {
+        // Safety: `T` is a primitive number type, so `items` is `size_of_val(items)` initialized bytes
+        unsafe { core::slice::from_raw_parts(items.as_ptr().cast(), core::mem::size_of_val(items)) }
+    }
+
+    /// The memory of `items`
+    pub(crate) fn as_bytes_mut(self, items: &mut [T]) -> &mut [u8] {
+        // Safety: `T` is a primitive number type, so every bit pattern that is written to the bytes is a valid `T`
+        unsafe {
+            core::slice::from_raw_parts_mut(
+                items.as_mut_ptr().cast(),
+                core::mem::size_of_val(items),
+            )
+        }
+    }
+
+    /// Reinterprets `bytes` as a slice of `T`, or returns `None` if `bytes` is not aligned for `T`
+    pub(crate) fn cast_bytes(self, bytes: &[u8]) -> Option<&[T]> {
+        if bytes.as_ptr().align_offset(core::mem::align_of::<T>()) != 0 {
+            return None;
+        }
+        let len = bytes.len() / core::mem::size_of::<T>();
+        // Safety: `bytes` is aligned for `T`, and every bit pattern is a valid `T`
+        Some(unsafe { core::slice::from_raw_parts(bytes.as_ptr().cast(), len) })
+    }
+
+    /// An array of zeroes
+    pub(crate) fn zeroed_array<const N: usize>(self) -> [T; N] {
+        // Safety: `T` is a primitive number type, so zeroed memory is a valid `T`
+        unsafe { core::mem::zeroed() }
+    }
+
+    /// A vec of `len` zeroes
+    #[cfg(feature = "alloc")]
+    pub(crate) fn zeroed_vec(self, len: usize) -> alloc::vec::Vec<T> {
+        let mut vec = alloc::vec::Vec::with_capacity(len);
+        // Safety: `T` is a primitive number type, so zeroed memory is a valid `T`
+        unsafe {
+            core::ptr::write_bytes(vec.as_mut_ptr(), 0, len);
+            vec.set_len(len);
+        }
+        vec
+    }
+
+    /// Converts the memory of items between the native byte order and the byte order of the config, by reversing the bytes of every item if they differ. `bytes` has to be aligned for `T`.
+    pub(crate) fn swap_bytes(self, bytes: &mut [u8]) {
+        macro_rules! swap {
+            ($ty:ty) => {{
+                assert_eq!(bytes.as_ptr().align_offset(core::mem::align_of::<T>()), 0);
+                // Safety: `bytes` is aligned for `T`, which has the same size and alignment as `$ty`
+                let items: &mut [$ty] = unsafe {
+                    core::slice::from_raw_parts_mut(
+                        bytes.as_mut_ptr().cast(),
+                        bytes.len() / core::mem::size_of::<$ty>(),
+                    )
+                };
+                for item in items {
+                    *item = item.swap_bytes();
+                }
+            }};
+        }
+
+        if !self.swap_bytes {
+            return;
+        }
+        match core::mem::size_of::<T>() {
+            2 => swap!(u16),
+            4 => swap!(u32),
+            8 => swap!(u64),
+            16 => swap!(u128),
+            _ => unreachable!(),
+        }
+    }
+}
@@ -4,8 +4,12 @@ use crate::{
     error::{DecodeError, IntegerType},
 };
 
-pub fn varint_decode_i16<R: Reader>(read: &mut R, endian: Endianness) -> Result<i16, DecodeError> {
-    let n = super::varint_decode_u16(read, endian)
+pub fn varint_decode_i16<R: Reader>(
+    read: &mut R,
+    endian: Endianness,
+    strict: bool,
+) -> Result<i16, DecodeError> {
+    let n = super::varint_decode_u16(read, endian, strict)
         .map_err(DecodeError::change_integer_type_to_signed)?;
     Ok(if n % 2 == 0 {
         // positive number
@@ -21,8 +25,12 @@ pub fn varint_decode_i16<R: Reader>(read: &mut R, endian: Endianness) -> Result<
     })
 }
 
-pub fn varint_decode_i32<R: Reader>(read: &mut R, endian: Endianness) -> Result<i32, DecodeError> {
-    let n = super::varint_decode_u32(read, endian)
+pub fn varint_decode_i32<R: Reader>(
+    read: &mut R,
+    endian: Endianness,
+    strict: bool,
+) -> Result<i32, DecodeError> {
+    let n = super::varint_decode_u32(read, endian, strict)
         .map_err(DecodeError::change_integer_type_to_signed)?;
     Ok(if n % 2 == 0 {
         // positive number
@@ -38,8 +46,12 @@ pub fn varint_decode_i32<R: Reader>(read: &mut R, endian: Endianness) -> Result<
     })
 }
 
-pub fn varint_decode_i64<R: Reader>(read: &mut R, endian: Endianness) -> Result<i64, DecodeError> {
-    let n = super::varint_decode_u64(read, endian)
+pub fn varint_decode_i64<R: Reader>(
+    read: &mut R,
+    endian: Endianness,
+    strict: bool,
+) -> Result<i64, DecodeError> {
+    let n = super::varint_decode_u64(read, endian, strict)
         .map_err(DecodeError::change_integer_type_to_signed)?;
     Ok(if n % 2 == 0 {
         // positive number
@@ -58,8 +70,9 @@ pub fn varint_decode_i64<R: Reader>(read: &mut R, endian: Endianness) -> Result<
 pub fn varint_decode_i128<R: Reader>(
     read: &mut R,
     endian: Endianness,
+    strict: bool,
 ) -> Result<i128, DecodeError> {
-    let n = super::varint_decode_u128(read, endian)
+    let n = super::varint_decode_u128(read, endian, strict)
         .map_err(DecodeError::change_integer_type_to_signed)?;
     Ok(if n % 2 == 0 {
         // positive number
@@ -78,8 +91,9 @@ pub fn varint_decode_i128<R: Reader>(
 pub fn varint_decode_isize<R: Reader>(
     read: &mut R,
     endian: Endianness,
+    strict: bool,
 ) -> Result<isize, DecodeError> {
-    match varint_decode_i64(read, endian) {
+    match varint_decode_i64(read, endian, strict) {
         Ok(val) => Ok(val as isize),
         Err(DecodeError::InvalidIntegerType { found, .. }) => {
             Err(DecodeError::InvalidIntegerType {
@@ -1,4 +1,4 @@
-use core::{convert::TryInto, u32};
+use core::convert::TryInto;
 
 use super::{SINGLE_BYTE_MAX, U128_BYTE, U16_BYTE, U32_BYTE, U64_BYTE};
 use crate::{
@@ -9,13 +9,17 @@ use crate::{
 
 #[inline(never)]
 #[cold]
-fn deserialize_varint_cold_u16<R>(read: &mut R, endian: Endianness) -> Result<u16, DecodeError>
+fn deserialize_varint_cold_u16<R>(
+    read: &mut R,
+    endian: Endianness,
+    strict: bool,
+) -> Result<u16, DecodeError>
 where
     R: Reader,
 {
     let mut bytes = [0u8; 1];
     read.read(&mut bytes)?;
-    match bytes[0] {
+    let val = match bytes[0] {
         byte @ 0..=SINGLE_BYTE_MAX => Ok(byte as u16),
         U16_BYTE => {
             let mut bytes = [0u8; 2];
@@ -29,18 +33,24 @@ where
         U64_BYTE => invalid_varint_discriminant(IntegerType::U16, IntegerType::U64),
         U128_BYTE => invalid_varint_discriminant(IntegerType::U16, IntegerType::U128),
         _ => invalid_varint_discriminant(IntegerType::U16, IntegerType::Reserved),
-    }
+    }?;
+    check_canonical(strict, bytes[0], val as u64)?;
+    Ok(val)
 }
 
 #[inline(never)]
 #[cold]
-fn deserialize_varint_cold_u32<R>(read: &mut R, endian: Endianness) -> Result<u32, DecodeError>
+fn deserialize_varint_cold_u32<R>(
+    read: &mut R,
+    endian: Endianness,
+    strict: bool,
+) -> Result<u32, DecodeError>
 where
     R: Reader,
 {
     let mut bytes = [0u8; 1];
     read.read(&mut bytes)?;
-    match bytes[0] {
+    let val = match bytes[0] {
         byte @ 0..=SINGLE_BYTE_MAX => Ok(byte as u32),
         U16_BYTE => {
             let mut bytes = [0u8; 2];
@@ -61,18 +71,24 @@ where
         U64_BYTE => invalid_varint_discriminant(IntegerType::U32, IntegerType::U64),
         U128_BYTE => invalid_varint_discriminant(IntegerType::U32, IntegerType::U128),
         _ => invalid_varint_discriminant(IntegerType::U32, IntegerType::Reserved),
-    }
+    }?;
+    check_canonical(strict, bytes[0], val as u64)?;
+    Ok(val)
 }
 
 #[inline(never)]
 #[cold]
-fn deserialize_varint_cold_u64<R>(read: &mut R, endian: Endianness) -> Result<u64, DecodeError>
+fn deserialize_varint_cold_u64<R>(
+    read: &mut R,
+    endian: Endianness,
+    strict: bool,
+) -> Result<u64, DecodeError>
 where
     R: Reader,
 {
     let mut bytes = [0u8; 1];
     read.read(&mut bytes)?;
-    match bytes[0] {
+    let val = match bytes[0] {
         byte @ 0..=SINGLE_BYTE_MAX => Ok(byte as u64),
         U16_BYTE => {
             let mut bytes = [0u8; 2];
@@ -100,18 +116,24 @@ where
         }
         U128_BYTE => invalid_varint_discriminant(IntegerType::U64, IntegerType::U128),
         _ => invalid_varint_discriminant(IntegerType::U64, IntegerType::Reserved),
-    }
+    }?;
+    check_canonical(strict, bytes[0], val)?;
+    Ok(val)
 }
 
 #[inline(never)]
 #[cold]
-fn deserialize_varint_cold_usize<R>(read: &mut R, endian: Endianness) -> Result<usize, DecodeError>
+fn deserialize_varint_cold_usize<R>(
+    read: &mut R,
+    endian: Endianness,
+    strict: bool,
+) -> Result<usize, DecodeError>
 where
     R: Reader,
 {
     let mut bytes = [0u8; 1];
     read.read(&mut bytes)?;
-    match bytes[0] {
+    let val = match bytes[0] {
         byte @ 0..=SINGLE_BYTE_MAX => Ok(byte as usize),
         U16_BYTE => {
             let mut bytes = [0u8; 2];
@@ -139,18 +161,24 @@ where
         }
         U128_BYTE => invalid_varint_discriminant(IntegerType::Usize, IntegerType::U128),
         _ => invalid_varint_discriminant(IntegerType::Usize, IntegerType::Reserved),
-    }
+    }?;
+    check_canonical(strict, bytes[0], val as u64)?;
+    Ok(val)
 }
 
 #[inline(never)]
 #[cold]
-fn deserialize_varint_cold_u128<R>(read: &mut R, endian: Endianness) -> Result<u128, DecodeError>
+fn deserialize_varint_cold_u128<R>(
+    read: &mut R,
+    endian: Endianness,
+    strict: bool,
+) -> Result<u128, DecodeError>
 where
     R: Reader,
 {
     let mut bytes = [0u8; 1];
     read.read(&mut bytes)?;
-    match bytes[0] {
+    let val = match bytes[0] {
         byte @ 0..=SINGLE_BYTE_MAX => Ok(byte as u128),
         U16_BYTE => {
             let mut bytes = [0u8; 2];
@@ -185,6 +213,37 @@ where
             })
         }
         _ => invalid_varint_discriminant(IntegerType::U128, IntegerType::Reserved),
+    }?;
+    check_canonical_u128(strict, bytes[0], val)?;
+    Ok(val)
+}
+
+/// With strict decoding, rejects a varint that could have been encoded with a smaller discriminant than `discriminant`
+#[inline]
+fn check_canonical(strict: bool, discriminant: u8, val: u64) -> Result<(), DecodeError> {
+    let min = match discriminant {
+        U16_BYTE => SINGLE_BYTE_MAX as u64 + 1,
+        U32_BYTE => u16::MAX as u64 + 1,
+        U64_BYTE => u32::MAX as u64 + 1,
+        _ => 0,
+    };
+    if strict && val < min {
+        Err(DecodeError::NonCanonicalVarint)
+    } else {
+        Ok(())
+    }
+}
+
+/// The `u128` version of [check_canonical]
+#[inline]
+fn check_canonical_u128(strict: bool, discriminant: u8, val: u128) -> Result<(), DecodeError> {
+    if discriminant == U128_BYTE {
+        if strict && val <= u64::MAX as u128 {
+            return Err(DecodeError::NonCanonicalVarint);
+        }
+        Ok(())
+    } else {
+        check_canonical(strict, discriminant, val as u64)
     }
 }
 
@@ -197,7 +256,11 @@ const fn invalid_varint_discriminant<T>(
     Err(DecodeError::InvalidIntegerType { expected, found })
 }
 
-pub fn varint_decode_u16<R: Reader>(read: &mut R, endian: Endianness) -> Result<u16, DecodeError> {
+pub fn varint_decode_u16<R: Reader>(
+    read: &mut R,
+    endian: Endianness,
+    strict: bool,
+) -> Result<u16, DecodeError> {
     if let Some(bytes) = read.peek_read(3) {
         let (discriminant, bytes) = bytes.split_at(1);
         let (out, used) = match discriminant[0] {
@@ -215,15 +278,20 @@ pub fn varint_decode_u16<R: Reader>(read: &mut R, endian: Endianness) -> Result<
             U128_BYTE => return invalid_varint_discriminant(IntegerType::U16, IntegerType::U128),
             _ => return invalid_varint_discriminant(IntegerType::U16, IntegerType::Reserved),
         };
+        check_canonical(strict, discriminant[0], out as u64)?;
 
         read.consume(used);
         Ok(out)
     } else {
-        deserialize_varint_cold_u16(read, endian)
+        deserialize_varint_cold_u16(read, endian, strict)
     }
 }
 
-pub fn varint_decode_u32<R: Reader>(read: &mut R, endian: Endianness) -> Result<u32, DecodeError> {
+pub fn varint_decode_u32<R: Reader>(
+    read: &mut R,
+    endian: Endianness,
+    strict: bool,
+) -> Result<u32, DecodeError> {
     if let Some(bytes) = read.peek_read(5) {
         let (discriminant, bytes) = bytes.split_at(1);
         let (out, used) = match discriminant[0] {
@@ -248,15 +316,20 @@ pub fn varint_decode_u32<R: Reader>(read: &mut R, endian: Endianness) -> Result<
             U128_BYTE => return invalid_varint_discriminant(IntegerType::U32, IntegerType::U128),
             _ => return invalid_varint_discriminant(IntegerType::U32, IntegerType::Reserved),
         };
+        check_canonical(strict, discriminant[0], out as u64)?;
 
         read.consume(used);
         Ok(out)
     } else {
-        deserialize_varint_cold_u32(read, endian)
+        deserialize_varint_cold_u32(read, endian, strict)
     }
 }
 
-pub fn varint_decode_u64<R: Reader>(read: &mut R, endian: Endianness) -> Result<u64, DecodeError> {
+pub fn varint_decode_u64<R: Reader>(
+    read: &mut R,
+    endian: Endianness,
+    strict: bool,
+) -> Result<u64, DecodeError> {
     if let Some(bytes) = read.peek_read(9) {
         let (discriminant, bytes) = bytes.split_at(1);
         let (out, used) = match discriminant[0] {
@@ -288,17 +361,19 @@ pub fn varint_decode_u64<R: Reader>(read: &mut R, endian: Endianness) -> Result<
             U128_BYTE => return invalid_varint_discriminant(IntegerType::U32, IntegerType::U128),
             _ => return invalid_varint_discriminant(IntegerType::U32, IntegerType::Reserved),
         };
+        check_canonical(strict, discriminant[0], out)?;
 
         read.consume(used);
         Ok(out)
     } else {
-        deserialize_varint_cold_u64(read, endian)
+        deserialize_varint_cold_u64(read, endian, strict)
     }
 }
 
 pub fn varint_decode_usize<R: Reader>(
     read: &mut R,
     endian: Endianness,
+    strict: bool,
 ) -> Result<usize, DecodeError> {
     if let Some(bytes) = read.peek_read(9) {
         let (discriminant, bytes) = bytes.split_at(1);
@@ -331,17 +406,19 @@ pub fn varint_decode_usize<R: Reader>(
             U128_BYTE => return invalid_varint_discriminant(IntegerType::Usize, IntegerType::U128),
             _ => return invalid_varint_discriminant(IntegerType::Usize, IntegerType::Reserved),
         };
+        check_canonical(strict, discriminant[0], out as u64)?;
 
         read.consume(used);
         Ok(out)
     } else {
-        deserialize_varint_cold_usize(read, endian)
+        deserialize_varint_cold_usize(read, endian, strict)
     }
 }
 
 pub fn varint_decode_u128<R: Reader>(
     read: &mut R,
     endian: Endianness,
+    strict: bool,
 ) -> Result<u128, DecodeError> {
     if let Some(bytes) = read.peek_read(17) {
         let (discriminant, bytes) = bytes.split_at(1);
@@ -381,14 +458,104 @@ pub fn varint_decode_u128<R: Reader>(
             }
             _ => return invalid_varint_discriminant(IntegerType::Usize, IntegerType::Reserved),
         };
+        check_canonical_u128(strict, discriminant[0], out)?;
 
         read.consume(used);
         Ok(out)
     } else {
-        deserialize_varint_cold_u128(read, endian)
+        deserialize_varint_cold_u128(read, endian, strict)
     }
 }
 
+/// Decodes varints from the start of `bytes` into `out`. Returns the amount of values that were decoded and the amount of bytes that they used.
+///
+/// This stops when `out` is full or when less than 9 bytes are left in `bytes`, so the last few varints of the input have to be decoded with [varint_decode_u32].
+pub fn varint_decode_u32_batch(
+    bytes: &[u8],
+    endian: Endianness,
+    out: &mut [u32],
+) -> Result<(usize, usize), DecodeError> {
+    decode_batch(bytes, endian, out, IntegerType::U32, |_| {
+        invalid_varint_discriminant(IntegerType::U32, IntegerType::U64)
+    })
+}
+
+/// Decodes varints from the start of `bytes` into `out`. Returns the amount of values that were decoded and the amount of bytes that they used.
+///
+/// This stops when `out` is full or when less than 9 bytes are left in `bytes`, so the last few varints of the input have to be decoded with [varint_decode_u64].
+pub fn varint_decode_u64_batch(
+    bytes: &[u8],
+    endian: Endianness,
+    out: &mut [u64],
+) -> Result<(usize, usize), DecodeError> {
+    decode_batch(bytes, endian, out, IntegerType::U64, Ok)
+}
+
+/// The loop of the batch decoders. Every varint is decoded from a window of 9 bytes, so only the window has to be bounds checked. Runs of single byte varints are decoded 8 at a time.
+#[inline(always)]
+fn decode_batch<T: From<u8> + From<u16> + From<u32>>(
+    bytes: &[u8],
+    endian: Endianness,
+    out: &mut [T],
+    integer_type: IntegerType,
+    from_u64: impl Fn(u64) -> Result<T, DecodeError>,
+) -> Result<(usize, usize), DecodeError> {
+    const WINDOW: usize = 9;
+    const CHUNK: usize = 8;
+
+    let mut count = 0;
+    let mut used = 0;
+    while count < out.len() && bytes.len() - used >= WINDOW {
+        let window: &[u8; WINDOW] = bytes[used..used + WINDOW].try_into().unwrap();
+        let (value, size) = match window[0] {
+            byte @ 0..=SINGLE_BYTE_MAX => {
+                if let Some(out) = out.get_mut(count..count + CHUNK) {
+                    let chunk = &window[..CHUNK];
+                    if chunk.iter().all(|&byte| byte <= SINGLE_BYTE_MAX) {
+                        for (out, &byte) in out.iter_mut().zip(chunk) {
+                            *out = T::from(byte);
+                        }
+                        count += CHUNK;
+                        used += CHUNK;
+                        continue;
+                    }
+                }
+                (T::from(byte), 1)
+            }
+            U16_BYTE => {
+                let bytes = window[1..3].try_into().unwrap();
+                let val = match endian {
+                    Endianness::Big => u16::from_be_bytes(bytes),
+                    Endianness::Little => u16::from_le_bytes(bytes),
+                };
+                (T::from(val), 3)
+            }
+            U32_BYTE => {
+                let bytes = window[1..5].try_into().unwrap();
+                let val = match endian {
+                    Endianness::Big => u32::from_be_bytes(bytes),
+                    Endianness::Little => u32::from_le_bytes(bytes),
+                };
+                (T::from(val), 5)
+            }
+            U64_BYTE => {
+                let bytes = window[1..9].try_into().unwrap();
+                let val = match endian {
+                    Endianness::Big => u64::from_be_bytes(bytes),
+                    Endianness::Little => u64::from_le_bytes(bytes),
+                };
+                (from_u64(val)?, 9)
+            }
+            U128_BYTE => return invalid_varint_discriminant(integer_type, IntegerType::U128),
+            _ => return invalid_varint_discriminant(integer_type, IntegerType::Reserved),
+        };
+        out[count] = value;
+        count += 1;
+        used += size;
+    }
+    Ok((count, used))
+}
+
 #[test]
 fn test_decode_u16() {
     let cases: &[(&[u8], u16, u16)] = &[
@@ -398,11 +565,11 @@ fn test_decode_u16() {
     ];
     for &(slice, expected_le, expected_be) in cases {
         let mut reader = crate::de::read::SliceReader::new(slice);
-        let found = varint_decode_u16(&mut reader, Endianness::Little).unwrap();
+        let found = varint_decode_u16(&mut reader, Endianness::Little, false).unwrap();
         assert_eq!(expected_le, found);
 
         let mut reader = crate::de::read::SliceReader::new(slice);
-        let found = varint_decode_u16(&mut reader, Endianness::Big).unwrap();
+        let found = varint_decode_u16(&mut reader, Endianness::Big, false).unwrap();
         assert_eq!(expected_be, found);
     }
 
@@ -434,7 +601,7 @@ fn test_decode_u16() {
 
     for (slice, expected) in errors {
         let mut reader = crate::de::read::SliceReader::new(slice);
-        let found = varint_decode_u16(&mut reader, Endianness::Little).unwrap_err();
+        let found = varint_decode_u16(&mut reader, Endianness::Little, false).unwrap_err();
         assert_eq!(std::format!("{:?}", expected), std::format!("{:?}", found));
     }
 }
@@ -449,11 +616,11 @@ fn test_decode_u32() {
     ];
     for &(slice, expected_le, expected_be) in cases {
         let mut reader = crate::de::read::SliceReader::new(slice);
-        let found = varint_decode_u32(&mut reader, Endianness::Little).unwrap();
+        let found = varint_decode_u32(&mut reader, Endianness::Little, false).unwrap();
         assert_eq!(expected_le, found);
 
         let mut reader = crate::de::read::SliceReader::new(slice);
-        let found = varint_decode_u32(&mut reader, Endianness::Big).unwrap();
+        let found = varint_decode_u32(&mut reader, Endianness::Big, false).unwrap();
         assert_eq!(expected_be, found);
     }
 
@@ -488,7 +655,7 @@ fn test_decode_u32() {
 
     for (slice, expected) in errors {
         let mut reader = crate::de::read::SliceReader::new(slice);
-        let found = varint_decode_u32(&mut reader, Endianness::Little).unwrap_err();
+        let found = varint_decode_u32(&mut reader, Endianness::Little, false).unwrap_err();
         assert_eq!(std::format!("{:?}", expected), std::format!("{:?}", found));
     }
 }
@@ -508,11 +675,11 @@ fn test_decode_u64() {
     ];
     for &(slice, expected_le, expected_be) in cases {
         let mut reader = crate::de::read::SliceReader::new(slice);
-        let found = varint_decode_u64(&mut reader, Endianness::Little).unwrap();
+        let found = varint_decode_u64(&mut reader, Endianness::Little, false).unwrap();
         assert_eq!(expected_le, found);
 
         let mut reader = crate::de::read::SliceReader::new(slice);
-        let found = varint_decode_u64(&mut reader, Endianness::Big).unwrap();
+        let found = varint_decode_u64(&mut reader, Endianness::Big, false).unwrap();
         assert_eq!(expected_be, found);
     }
 
@@ -566,7 +733,7 @@ fn test_decode_u64() {
 
     for (slice, expected) in errors {
         let mut reader = crate::de::read::SliceReader::new(slice);
-        let found = varint_decode_u64(&mut reader, Endianness::Little).unwrap_err();
+        let found = varint_decode_u64(&mut reader, Endianness::Little, false).unwrap_err();
         assert_eq!(std::format!("{:?}", expected), std::format!("{:?}", found));
     }
 }
@@ -591,11 +758,11 @@ fn test_decode_u128() {
     ];
     for &(slice, expected_le, expected_be) in cases {
         let mut reader = crate::de::read::SliceReader::new(slice);
-        let found = varint_decode_u128(&mut reader, Endianness::Little).unwrap();
+        let found = varint_decode_u128(&mut reader, Endianness::Little, false).unwrap();
         assert_eq!(expected_le, found);
 
         let mut reader = crate::de::read::SliceReader::new(slice);
-        let found = varint_decode_u128(&mut reader, Endianness::Big).unwrap();
+        let found = varint_decode_u128(&mut reader, Endianness::Big, false).unwrap();
         assert_eq!(expected_be, found);
     }
 
@@ -703,8 +870,110 @@ fn test_decode_u128() {
 
     for (slice, expected) in errors {
         let mut reader = crate::de::read::SliceReader::new(slice);
-        let found = varint_decode_u128(&mut reader, Endianness::Little).unwrap_err();
+        let found = varint_decode_u128(&mut reader, Endianness::Little, false).unwrap_err();
         std::dbg!(slice);
         assert_eq!(std::format!("{:?}", expected), std::format!("{:?}", found));
     }
 }
+
+#[test]
+fn test_decode_batch() {
+    use crate::enc::write::Writer;
+
+    struct VecWriter(std::vec::Vec<u8>);
+    impl Writer for VecWriter {
+        fn write(&mut self, bytes: &[u8]) -> Result<(), crate::error::EncodeError> {
+            self.0.extend_from_slice(bytes);
+            Ok(())
+        }
+    }
+
+    let values: std::vec::Vec<u64> = (0..40)
+        .map(|i| match i % 7 {
+            0 => u64::MAX - i,
+            1 => u32::MAX as u64 + i,
+            2 => 1000 + i,
+            _ => i,
+        })
+        .chain(0..20)
+        .collect();
+
+    for endian in [Endianness::Little, Endianness::Big] {
+        let mut writer = VecWriter(std::vec::Vec::new());
+        for &value in &values {
+            super::varint_encode_u64(&mut writer, endian, value).unwrap();
+        }
+        let bytes = writer.0;
+
+        // Every prefix of the bytes decodes to the same values as the single value decoder, and only the last 8 bytes can be left over
+        let mut reader = crate::de::read::SliceReader::new(&bytes);
+        let mut offsets = std::vec![0];
+        let mut expected = std::vec::Vec::new();
+        for _ in &values {
+            expected.push(varint_decode_u64(&mut reader, endian, false).unwrap());
+            offsets.push(bytes.len() - reader.slice.len());
+        }
+        assert_eq!(expected, values);
+        for end in 0..=bytes.len() {
+            let mut out = std::vec![0u64; values.len()];
+            let (count, used) = varint_decode_u64_batch(&bytes[..end], endian, &mut out).unwrap();
+            assert_eq!(used, offsets[count]);
+            assert!(end - used < 9);
+            assert_eq!(out[..count], expected[..count]);
+        }
+
+        // `out` limits the amount of values
+        let mut out = [0u64; 3];
+        let (count, used) = varint_decode_u64_batch(&bytes, endian, &mut out).unwrap();
+        assert_eq!((count, used), (3, offsets[3]));
+
+        // Values that fit in a u32
+        let small: std::vec::Vec<u32> = values.iter().map(|&v| v as u32).collect();
+        let mut writer = VecWriter(std::vec::Vec::new());
+        for &value in &small {
+            super::varint_encode_u32(&mut writer, endian, value).unwrap();
+        }
+        let bytes = writer.0;
+        let mut reader = crate::de::read::SliceReader::new(&bytes);
+        let expected: std::vec::Vec<u32> = small
+            .iter()
+            .map(|_| varint_decode_u32(&mut reader, endian, false).unwrap())
+            .collect();
+        let mut out = std::vec![0u32; small.len()];
+        let (count, used) = varint_decode_u32_batch(&bytes, endian, &mut out).unwrap();
+        assert!(bytes.len() - used < 9);
+        assert_eq!(out[..count], expected[..count]);
+        let mut reader = crate::de::read::SliceReader::new(&bytes[used..]);
+        for expected in &expected[count..] {
+            assert_eq!(
+                varint_decode_u32(&mut reader, endian, false).unwrap(),
+                *expected
+            );
+        }
+    }
+
+    let mut out = [0u32; 4];
+    assert!(matches!(
+        varint_decode_u32_batch(
+            &[1, 2, U64_BYTE, 0, 0, 0, 0, 0, 0, 0, 0],
+            Endianness::Little,
+            &mut out
+        ),
+        Err(DecodeError::InvalidIntegerType {
+            expected: IntegerType::U32,
+            found: IntegerType::U64,
+        })
+    ));
+    let mut out = [0u64; 4];
+    assert!(matches!(
+        varint_decode_u64_batch(
+            &[1, 255, 0, 0, 0, 0, 0, 0, 0, 0],
+            Endianness::Little,
+            &mut out
+        ),
+        Err(DecodeError::InvalidIntegerType {
+            expected: IntegerType::U64,
+            found: IntegerType::Reserved,
+        })
+    ));
+}
@@ -0,0 +1,349 @@
+//! Unsigned LEB128, as used by protobuf, DWARF and WebAssembly. Every byte holds 7 bits of the value, starting with the least significant bits, and has its high bit set if more bytes follow. Signed integers are zigzag encoded first, so that small negative numbers are small as well.
+//!
+//! Decoding accepts encodings that are longer than they need to be, like `[0x80, 0x00]` for `0`, as long as the value fits in the type and the encoding is not longer than the longest encoding of the type. With strict decoding, these fail with [DecodeError::NonCanonicalVarint].
+
+use crate::{
+    de::read::Reader,
+    enc::write::Writer,
+    error::{DecodeError, EncodeError, IntegerType},
+};
+
+/// The maximum length of a `u64`, `ceil(64 / 7)`
+const MAX_LEN_U64: usize = 10;
+/// The maximum length of a `u128`, `ceil(128 / 7)`
+const MAX_LEN_U128: usize = 19;
+
+const CONTINUE_BIT: u8 = 0x80;
+const VALUE_BITS: u8 = 0x7f;
+
+pub fn leb128_encode_u16<W: Writer>(writer: &mut W, val: u16) -> Result<(), EncodeError> {
+    leb128_encode_u64(writer, val as u64)
+}
+
+pub fn leb128_encode_u32<W: Writer>(writer: &mut W, val: u32) -> Result<(), EncodeError> {
+    leb128_encode_u64(writer, val as u64)
+}
+
+pub fn leb128_encode_u64<W: Writer>(writer: &mut W, mut val: u64) -> Result<(), EncodeError> {
+    if val < CONTINUE_BIT as u64 {
+        return writer.write(&[val as u8]);
+    }
+    let mut buf = [0u8; MAX_LEN_U64];
+    let mut len = 0;
+    while val >= CONTINUE_BIT as u64 {
+        buf[len] = val as u8 | CONTINUE_BIT;
+        val >>= 7;
+        len += 1;
+    }
+    buf[len] = val as u8;
+    writer.write(&buf[..=len])
+}
+
+pub fn leb128_encode_u128<W: Writer>(writer: &mut W, mut val: u128) -> Result<(), EncodeError> {
+    let mut buf = [0u8; MAX_LEN_U128];
+    let mut len = 0;
+    while val >= CONTINUE_BIT as u128 {
+        buf[len] = val as u8 | CONTINUE_BIT;
+        val >>= 7;
+        len += 1;
+    }
+    buf[len] = val as u8;
+    writer.write(&buf[..=len])
+}
+
+pub fn leb128_encode_usize<W: Writer>(writer: &mut W, val: usize) -> Result<(), EncodeError> {
+    leb128_encode_u64(writer, val as u64)
+}
+
+pub fn leb128_encode_i16<W: Writer>(writer: &mut W, val: i16) -> Result<(), EncodeError> {
+    leb128_encode_u16(writer, ((val << 1) ^ (val >> 15)) as u16)
+}
+
+pub fn leb128_encode_i32<W: Writer>(writer: &mut W, val: i32) -> Result<(), EncodeError> {
+    leb128_encode_u32(writer, ((val << 1) ^ (val >> 31)) as u32)
+}
+
+pub fn leb128_encode_i64<W: Writer>(writer: &mut W, val: i64) -> Result<(), EncodeError> {
+    leb128_encode_u64(writer, ((val << 1) ^ (val >> 63)) as u64)
+}
+
+pub fn leb128_encode_i128<W: Writer>(writer: &mut W, val: i128) -> Result<(), EncodeError> {
+    leb128_encode_u128(writer, ((val << 1) ^ (val >> 127)) as u128)
+}
+
+pub fn leb128_encode_isize<W: Writer>(writer: &mut W, val: isize) -> Result<(), EncodeError> {
+    leb128_encode_i64(writer, val as i64)
+}
+
+/// The smallest unsigned integer type that `val` fits in, for the `found` type of [DecodeError::InvalidIntegerType]
+const fn integer_type_of(val: u64) -> IntegerType {
+    if val <= u16::MAX as u64 {
+        IntegerType::U16
+    } else if val <= u32::MAX as u64 {
+        IntegerType::U32
+    } else {
+        IntegerType::U64
+    }
+}
+
+/// With strict decoding, rejects a last byte of `0` after other bytes, as the value could have been encoded without it
+#[inline]
+fn check_canonical(strict: bool, index: usize, last_byte: u8) -> Result<(), DecodeError> {
+    if strict && index > 0 && last_byte == 0 {
+        Err(DecodeError::NonCanonicalVarint)
+    } else {
+        Ok(())
+    }
+}
+
+/// Decodes the bytes that are returned by `next` into a `u64`. Values that do not fit in a `u64` are reported as a `U128`.
+#[inline(always)]
+fn decode_u64_with(
+    strict: bool,
+    mut next: impl FnMut() -> Result<u8, DecodeError>,
+) -> Result<u64, DecodeError> {
+    let mut val = 0;
+    for i in 0..MAX_LEN_U64 {
+        let byte = next()?;
+        // The last byte only has room for the highest bit of the value
+        if i == MAX_LEN_U64 - 1 && byte > 1 {
+            break;
+        }
+        val |= ((byte & VALUE_BITS) as u64) << (i * 7);
+        if byte & CONTINUE_BIT == 0 {
+            return check_canonical(strict, i, byte).map(|_| val);
+        }
+    }
+    Err(DecodeError::InvalidIntegerType {
+        expected: IntegerType::U64,
+        found: IntegerType::U128,
+    })
+}
+
+pub fn leb128_decode_u64<R: Reader>(read: &mut R, strict: bool) -> Result<u64, DecodeError> {
+    match read.peek_read(1) {
+        Some(&[byte]) if byte < CONTINUE_BIT => {
+            read.consume(1);
+            return Ok(byte as u64);
+        }
+        _ => {}
+    }
+    if let Some(bytes) = read.peek_read(MAX_LEN_U64) {
+        let mut used = 0;
+        let val = decode_u64_with(strict, || {
+            used += 1;
+            Ok(bytes[used - 1])
+        })?;
+        read.consume(used);
+        Ok(val)
+    } else {
+        decode_u64_with(strict, || {
+            let mut byte = [0u8];
+            read.read(&mut byte)?;
+            Ok(byte[0])
+        })
+    }
+}
+
+pub fn leb128_decode_u16<R: Reader>(read: &mut R, strict: bool) -> Result<u16, DecodeError> {
+    let val = leb128_decode_u64(read, strict)
+        .map_err(|e| e.change_expected_integer_type(IntegerType::U16))?;
+    u16::try_from(val).map_err(|_| DecodeError::InvalidIntegerType {
+        expected: IntegerType::U16,
+        found: integer_type_of(val),
+    })
+}
+
+pub fn leb128_decode_u32<R: Reader>(read: &mut R, strict: bool) -> Result<u32, DecodeError> {
+    let val = leb128_decode_u64(read, strict)
+        .map_err(|e| e.change_expected_integer_type(IntegerType::U32))?;
+    u32::try_from(val).map_err(|_| DecodeError::InvalidIntegerType {
+        expected: IntegerType::U32,
+        found: integer_type_of(val),
+    })
+}
+
+pub fn leb128_decode_u128<R: Reader>(read: &mut R, strict: bool) -> Result<u128, DecodeError> {
+    let mut val = 0;
+    for i in 0..MAX_LEN_U128 {
+        let mut byte = [0u8];
+        read.read(&mut byte)?;
+        let byte = byte[0];
+        // The last byte only has room for the two highest bits of the value
+        if i == MAX_LEN_U128 - 1 && byte > 3 {
+            break;
+        }
+        val |= ((byte & VALUE_BITS) as u128) << (i * 7);
+        if byte & CONTINUE_BIT == 0 {
+            return check_canonical(strict, i, byte).map(|_| val);
+        }
+    }
+    Err(DecodeError::InvalidIntegerType {
+        expected: IntegerType::U128,
+        found: IntegerType::Reserved,
+    })
+}
+
+pub fn leb128_decode_usize<R: Reader>(read: &mut R, strict: bool) -> Result<usize, DecodeError> {
+    let val = leb128_decode_u64(read, strict)
+        .map_err(|e| e.change_expected_integer_type(IntegerType::Usize))?;
+    usize::try_from(val).map_err(|_| DecodeError::OutsideUsizeRange(val))
+}
+
+pub fn leb128_decode_i16<R: Reader>(read: &mut R, strict: bool) -> Result<i16, DecodeError> {
+    let n = leb128_decode_u16(read, strict).map_err(DecodeError::change_integer_type_to_signed)?;
+    Ok((n >> 1) as i16 ^ -((n & 1) as i16))
+}
+
+pub fn leb128_decode_i32<R: Reader>(read: &mut R, strict: bool) -> Result<i32, DecodeError> {
+    let n = leb128_decode_u32(read, strict).map_err(DecodeError::change_integer_type_to_signed)?;
+    Ok((n >> 1) as i32 ^ -((n & 1) as i32))
+}
+
+pub fn leb128_decode_i64<R: Reader>(read: &mut R, strict: bool) -> Result<i64, DecodeError> {
+    let n = leb128_decode_u64(read, strict).map_err(DecodeError::change_integer_type_to_signed)?;
+    Ok((n >> 1) as i64 ^ -((n & 1) as i64))
+}
+
+pub fn leb128_decode_i128<R: Reader>(read: &mut R, strict: bool) -> Result<i128, DecodeError> {
+    let n = leb128_decode_u128(read, strict).map_err(DecodeError::change_integer_type_to_signed)?;
+    Ok((n >> 1) as i128 ^ -((n & 1) as i128))
+}
+
+pub fn leb128_decode_isize<R: Reader>(read: &mut R, strict: bool) -> Result<isize, DecodeError> {
+    let n =
+        leb128_decode_usize(read, strict).map_err(DecodeError::change_integer_type_to_signed)?;
+    Ok((n >> 1) as isize ^ -((n & 1) as isize))
+}
+
+#[test]
+fn test_leb128_known_values() {
+    use crate::de::read::SliceReader;
+
+    struct ArrayWriter {
+        buf: [u8; MAX_LEN_U128],
+        len: usize,
+    }
+    impl Writer for ArrayWriter {
+        fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+            self.buf[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+            self.len += bytes.len();
+            Ok(())
+        }
+    }
+
+    // The examples of the protobuf and the LEB128 wikipedia documentation
+    let cases: &[(u64, &[u8])] = &[
+        (0, &[0x00]),
+        (1, &[0x01]),
+        (127, &[0x7f]),
+        (128, &[0x80, 0x01]),
+        (150, &[0x96, 0x01]),
+        (624_485, &[0xe5, 0x8e, 0x26]),
+        (
+            u64::MAX,
+            &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01],
+        ),
+    ];
+    for &(val, bytes) in cases {
+        let mut writer = ArrayWriter {
+            buf: [0; MAX_LEN_U128],
+            len: 0,
+        };
+        leb128_encode_u64(&mut writer, val).unwrap();
+        assert_eq!(&writer.buf[..writer.len], bytes);
+        assert_eq!(
+            leb128_decode_u64(&mut SliceReader::new(bytes), false).unwrap(),
+            val
+        );
+        assert_eq!(
+            leb128_decode_u128(&mut SliceReader::new(bytes), false).unwrap(),
+            val as u128
+        );
+    }
+
+    let zigzag: &[(i64, u8)] = &[(0, 0), (-1, 1), (1, 2), (-2, 3), (63, 126), (-64, 127)];
+    for &(val, byte) in zigzag {
+        let mut writer = ArrayWriter {
+            buf: [0; MAX_LEN_U128],
+            len: 0,
+        };
+        leb128_encode_i64(&mut writer, val).unwrap();
+        assert_eq!(&writer.buf[..writer.len], [byte]);
+        leb128_encode_i16(&mut writer, val as i16).unwrap();
+        leb128_encode_i128(&mut writer, val as i128).unwrap();
+        assert_eq!(&writer.buf[..writer.len], [byte; 3]);
+    }
+
+    let mut writer = ArrayWriter {
+        buf: [0; MAX_LEN_U128],
+        len: 0,
+    };
+    leb128_encode_u128(&mut writer, u128::MAX).unwrap();
+    assert_eq!(writer.len, MAX_LEN_U128);
+    assert_eq!(writer.buf[MAX_LEN_U128 - 1], 0x03);
+    let mut reader = SliceReader::new(&writer.buf);
+    assert_eq!(leb128_decode_u128(&mut reader, false).unwrap(), u128::MAX);
+    let mut reader = SliceReader::new(&writer.buf);
+    assert_eq!(leb128_decode_i128(&mut reader, false).unwrap(), i128::MIN);
+
+    // Longer encodings than needed are accepted
+    let mut reader = SliceReader::new(&[0x80, 0x80, 0x00]);
+    assert_eq!(leb128_decode_u16(&mut reader, false).unwrap(), 0);
+}
+
+#[test]
+fn test_leb128_decode_errors() {
+    use crate::de::read::SliceReader;
+
+    let mut reader = SliceReader::new(&[0x80, 0x80, 0x04]);
+    assert!(matches!(
+        leb128_decode_u16(&mut reader, false),
+        Err(DecodeError::InvalidIntegerType {
+            expected: IntegerType::U16,
+            found: IntegerType::U32,
+        })
+    ));
+    let mut reader = SliceReader::new(&[0x80, 0x80, 0x04]);
+    assert!(matches!(
+        leb128_decode_i16(&mut reader, false),
+        Err(DecodeError::InvalidIntegerType {
+            expected: IntegerType::I16,
+            found: IntegerType::I32,
+        })
+    ));
+
+    // An 11th byte, or a 10th byte with more than the highest bit
+    let too_long = [0x80; 11];
+    let mut reader = SliceReader::new(&too_long);
+    assert!(matches!(
+        leb128_decode_u32(&mut reader, false),
+        Err(DecodeError::InvalidIntegerType {
+            expected: IntegerType::U32,
+            found: IntegerType::U128,
+        })
+    ));
+    let mut reader =
+        SliceReader::new(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02]);
+    assert!(matches!(
+        leb128_decode_u64(&mut reader, false),
+        Err(DecodeError::InvalidIntegerType {
+            expected: IntegerType::U64,
+            found: IntegerType::U128,
+        })
+    ));
+    let mut reader = SliceReader::new(&[0x80; 20]);
+    assert!(matches!(
+        leb128_decode_u128(&mut reader, false),
+        Err(DecodeError::InvalidIntegerType {
+            expected: IntegerType::U128,
+            found: IntegerType::Reserved,
+        })
+    ));
+
+    let mut reader = SliceReader::new(&[0x80, 0x80]);
+    assert!(matches!(
+        leb128_decode_u64(&mut reader, false),
+        Err(DecodeError::UnexpectedEnd { .. })
+    ));
+}
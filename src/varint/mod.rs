@@ -1,7 +1,14 @@
+use crate::{
+    config::{Endianness, InternalEndianConfig},
+    de::{read::Reader, Decoder},
+    error::DecodeError,
+};
+
 mod decode_signed;
 mod decode_unsigned;
 mod encode_signed;
 mod encode_unsigned;
+mod leb128;
 
 pub use self::{
     decode_signed::{
@@ -9,8 +16,8 @@ pub use self::{
         varint_decode_isize,
     },
     decode_unsigned::{
-        varint_decode_u128, varint_decode_u16, varint_decode_u32, varint_decode_u64,
-        varint_decode_usize,
+        varint_decode_u128, varint_decode_u16, varint_decode_u32, varint_decode_u32_batch,
+        varint_decode_u64, varint_decode_u64_batch, varint_decode_usize,
     },
     encode_signed::{
         varint_encode_i128, varint_encode_i16, varint_encode_i32, varint_encode_i64,
@@ -20,6 +27,13 @@ pub use self::{
         varint_encode_u128, varint_encode_u16, varint_encode_u32, varint_encode_u64,
         varint_encode_usize,
     },
+    leb128::{
+        leb128_decode_i128, leb128_decode_i16, leb128_decode_i32, leb128_decode_i64,
+        leb128_decode_isize, leb128_decode_u128, leb128_decode_u16, leb128_decode_u32,
+        leb128_decode_u64, leb128_decode_usize, leb128_encode_i128, leb128_encode_i16,
+        leb128_encode_i32, leb128_encode_i64, leb128_encode_isize, leb128_encode_u128,
+        leb128_encode_u16, leb128_encode_u32, leb128_encode_u64, leb128_encode_usize,
+    },
 };
 
 const SINGLE_BYTE_MAX: u8 = 250;
@@ -27,3 +41,74 @@ const U16_BYTE: u8 = 251;
 const U32_BYTE: u8 = 252;
 const U64_BYTE: u8 = 253;
 const U128_BYTE: u8 = 254;
+
+/// An unsigned integer type that can decode its varints in batches
+pub(crate) trait BatchVarint: Sized {
+    fn decode_batch(
+        bytes: &[u8],
+        endian: Endianness,
+        out: &mut [Self],
+    ) -> Result<(usize, usize), DecodeError>;
+
+    fn decode_one<R: Reader>(read: &mut R, endian: Endianness) -> Result<Self, DecodeError>;
+}
+
+impl BatchVarint for u32 {
+    fn decode_batch(
+        bytes: &[u8],
+        endian: Endianness,
+        out: &mut [Self],
+    ) -> Result<(usize, usize), DecodeError> {
+        varint_decode_u32_batch(bytes, endian, out)
+    }
+
+    fn decode_one<R: Reader>(read: &mut R, endian: Endianness) -> Result<Self, DecodeError> {
+        varint_decode_u32(read, endian, false)
+    }
+}
+
+impl BatchVarint for u64 {
+    fn decode_batch(
+        bytes: &[u8],
+        endian: Endianness,
+        out: &mut [Self],
+    ) -> Result<(usize, usize), DecodeError> {
+        varint_decode_u64_batch(bytes, endian, out)
+    }
+
+    fn decode_one<R: Reader>(read: &mut R, endian: Endianness) -> Result<Self, DecodeError> {
+        varint_decode_u64(read, endian, false)
+    }
+}
+
+/// Decodes `out.len()` varints with the endianness of the config of the decoder. If the reader can peek into its buffer, the varints are decoded in batches. Otherwise, and for the last few varints in the peeked bytes, they are decoded one by one.
+///
+/// The batches do not check for non-canonical varints, so this should not be used with strict decoding.
+pub(crate) fn varint_decode_slice<T: BatchVarint, D: Decoder>(
+    decoder: &mut D,
+    out: &mut [T],
+) -> Result<(), DecodeError> {
+    // Near the end of the input there may be less than MAX_PEEK bytes. Every varint is at least 1 byte, so valid input still has as many bytes as there are varints left
+    const MAX_PEEK: usize = 256;
+
+    let mut decoded = 0;
+    while decoded < out.len() {
+        let reader = decoder.reader();
+        let peeked = match reader.peek_read(MAX_PEEK) {
+            Some(bytes) => Some(bytes),
+            None => reader.peek_read((out.len() - decoded).min(MAX_PEEK)),
+        };
+        let (count, used) = match peeked {
+            Some(bytes) => T::decode_batch(bytes, D::C::ENDIAN, &mut out[decoded..])?,
+            None => (0, 0),
+        };
+        if count == 0 {
+            out[decoded] = T::decode_one(decoder.reader(), D::C::ENDIAN)?;
+            decoded += 1;
+        } else {
+            decoder.reader().consume(used);
+            decoded += count;
+        }
+    }
+    Ok(())
+}
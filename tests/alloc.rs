@@ -136,9 +136,9 @@ fn test_container_limits() {
 
     // for this test we'll create a malformed package of a lot of bytes
     let test_cases = &[
-        // u64::max_value(), should overflow
+        // u64::MAX, should overflow
         #[cfg(target_pointer_width = "64")]
-        bincode::encode_to_vec(u64::max_value(), bincode::config::standard()).unwrap(),
+        bincode::encode_to_vec(u64::MAX, bincode::config::standard()).unwrap(),
         #[cfg(target_pointer_width = "32")]
         bincode::encode_to_vec(u32::max_value(), bincode::config::standard()).unwrap(),
         // A high value which doesn't overflow, but exceeds the decode limit
@@ -158,6 +158,17 @@ fn test_container_limits() {
             Err(DecodeError::LimitExceeded) => {},
             Err(e) => panic!("Expected OutsideUsizeRange (on 32 bit platforms) or LimitExceeded whilst decoding {}, got {:?}", name, e),
         }
+
+        let result = bincode::decode_from_slice::<T, _>(
+            slice,
+            bincode::config::standard().with_runtime_limit(DECODE_LIMIT),
+        );
+        match result {
+            Ok(_) => panic!("Decoding {} with a runtime limit should fail, it instead succeeded", name),
+            Err(DecodeError::OutsideUsizeRange(_)) if cfg!(target_pointer_width = "32") => {},
+            Err(DecodeError::LimitExceeded) => {},
+            Err(e) => panic!("Expected OutsideUsizeRange (on 32 bit platforms) or LimitExceeded whilst decoding {} with a runtime limit, got {:?}", name, e),
+        }
     }
 
     for slice in test_cases {
@@ -175,6 +186,67 @@ fn test_container_limits() {
     }
 }
 
+#[test]
+fn test_runtime_limit() {
+    use bincode::{config::Config, error::DecodeError};
+
+    let value: Vec<u32> = vec![1, 2, 3, 4, 5];
+    let bytes = bincode::encode_to_vec(&value, bincode::config::standard()).unwrap();
+
+    // The length is claimed as a u64, followed by 4 bytes for every u32
+    let limit = 8 + 4 * value.len();
+    let config = bincode::config::standard().with_runtime_limit(limit);
+    assert_eq!(config.limit(), Some(limit));
+    let decoded: Vec<u32> = bincode::decode_from_slice(&bytes, config).unwrap().0;
+    assert_eq!(decoded, value);
+
+    let config = bincode::config::standard().with_runtime_limit(limit - 1);
+    let result = bincode::decode_from_slice::<Vec<u32>, _>(&bytes, config);
+    assert!(matches!(result, Err(DecodeError::LimitExceeded)));
+
+    // The limit is kept when changing other options, and can be cleared again
+    let config = config.with_big_endian().with_fixed_int_encoding();
+    assert_eq!(config.limit(), Some(limit - 1));
+    assert_eq!(config.with_no_limit().limit(), None);
+}
+
+#[test]
+fn test_max_depth() {
+    use bincode::error::DecodeError;
+
+    let config = bincode::config::standard().with_max_depth::<3>();
+
+    let value: Vec<Vec<Box<u32>>> = vec![vec![Box::new(5)]];
+    let bytes = bincode::encode_to_vec(&value, config).unwrap();
+    let decoded: Vec<Vec<Box<u32>>> = bincode::decode_from_slice(&bytes, config).unwrap().0;
+    assert_eq!(decoded, value);
+    let decoded: Vec<Vec<Box<u32>>> = bincode::borrow_decode_from_slice(&bytes, config).unwrap().0;
+    assert_eq!(decoded, value);
+
+    // Siblings do not add up, only the nesting counts
+    let value: Vec<Box<u32>> = vec![Box::new(1), Box::new(2), Box::new(3), Box::new(4)];
+    let bytes = bincode::encode_to_vec(&value, config).unwrap();
+    let decoded: Vec<Box<u32>> = bincode::decode_from_slice(&bytes, config).unwrap().0;
+    assert_eq!(decoded, value);
+
+    let value: Vec<Vec<Box<Box<u32>>>> = vec![vec![Box::new(Box::new(5))]];
+    let bytes = bincode::encode_to_vec(&value, config).unwrap();
+    let result = bincode::decode_from_slice::<Vec<Vec<Box<Box<u32>>>>, _>(&bytes, config);
+    assert!(matches!(result, Err(DecodeError::DepthLimitExceeded)));
+    let result = bincode::decode_from_slice::<BTreeMap<u32, Vec<Vec<Vec<u32>>>>, _>(
+        &bincode::encode_to_vec(BTreeMap::from([(1u32, vec![vec![vec![1u32]]])]), config).unwrap(),
+        config,
+    );
+    assert!(matches!(result, Err(DecodeError::DepthLimitExceeded)));
+
+    // Without a max depth the same value decodes fine
+    let decoded: Vec<Vec<Box<Box<u32>>>> =
+        bincode::decode_from_slice(&bytes, bincode::config::standard())
+            .unwrap()
+            .0;
+    assert_eq!(decoded, value);
+}
+
 #[cfg(target_has_atomic = "ptr")]
 #[test]
 fn test_arc_str() {
@@ -308,3 +308,81 @@ fn test_duration_wrapping() {
 
     assert_eq!(result.subsec_nanos(), 294967295);
 }
+
+#[test]
+fn test_trailing_bytes() {
+    let config = bincode::config::standard();
+    // A `&[u8]` of length 1, followed by 1 trailing byte
+    let input = [1u8, 10, 15];
+
+    let result: (u8, usize) = bincode::decode_from_slice(&input, config).unwrap();
+    assert_eq!(result, (1, 1));
+    let result: (&[u8], usize) = bincode::borrow_decode_from_slice(&input, config).unwrap();
+    assert_eq!(result, (&[10u8][..], 2));
+
+    let result = bincode::decode_from_slice_exact::<u8, _>(&input, config);
+    assert!(matches!(
+        result,
+        Err(DecodeError::TrailingBytes { remaining: 2 })
+    ));
+    let result = bincode::borrow_decode_from_slice_exact::<&[u8], _>(&input, config);
+    assert!(matches!(
+        result,
+        Err(DecodeError::TrailingBytes { remaining: 1 })
+    ));
+    let result: (u8, u8, u8) = bincode::decode_from_slice_exact(&input, config).unwrap();
+    assert_eq!(result, (1, 10, 15));
+    let result: &[u8] = bincode::borrow_decode_from_slice_exact(&input[..2], config).unwrap();
+    assert_eq!(result, &[10u8][..]);
+
+    let config = config.with_reject_trailing_bytes();
+    let result = bincode::decode_from_slice::<u8, _>(&input, config);
+    assert!(matches!(
+        result,
+        Err(DecodeError::TrailingBytes { remaining: 2 })
+    ));
+    let result = bincode::borrow_decode_from_slice::<&[u8], _>(&input, config);
+    assert!(matches!(
+        result,
+        Err(DecodeError::TrailingBytes { remaining: 1 })
+    ));
+    let result: ((u8, u8, u8), usize) = bincode::decode_from_slice(&input, config).unwrap();
+    assert_eq!(result, ((1, 10, 15), 3));
+
+    let config = config.with_allow_trailing_bytes();
+    let result: (u8, usize) = bincode::decode_from_slice(&input, config).unwrap();
+    assert_eq!(result, (1, 1));
+}
+
+#[test]
+fn test_leb128_int_encoding() {
+    let config = bincode::config::standard().with_leb128_int_encoding();
+    let mut buffer = [0u8; 256];
+
+    // The same bytes as protobuf's `uint32` and `sint64`
+    let len = bincode::encode_into_slice(300u32, &mut buffer, config).unwrap();
+    assert_eq!(buffer[..len], [0xac, 0x02]);
+    let len = bincode::encode_into_slice(-1i32, &mut buffer, config).unwrap();
+    assert_eq!(buffer[..len], [0x01]);
+    let len = bincode::encode_into_slice(-150i64, &mut buffer, config).unwrap();
+    assert_eq!(buffer[..len], [0xab, 0x02]);
+    let len = bincode::encode_into_slice(u64::MAX, &mut buffer, config).unwrap();
+    assert_eq!(len, 10);
+
+    // Lengths use the same encoding, and the endianness does not matter
+    let len =
+        bincode::encode_into_slice(&[7u8; 200][..], &mut buffer, config.with_big_endian()).unwrap();
+    assert_eq!(len, 202);
+    assert_eq!(buffer[..3], [0xc8, 0x01, 7]);
+
+    let result: (u16, usize) = bincode::decode_from_slice(&[0xff, 0xff, 0x03], config).unwrap();
+    assert_eq!(result, (u16::MAX, 3));
+    let result = bincode::decode_from_slice::<u16, _>(&[0xff, 0xff, 0x04], config);
+    assert!(matches!(
+        result,
+        Err(DecodeError::InvalidIntegerType {
+            expected: bincode::error::IntegerType::U16,
+            found: bincode::error::IntegerType::U32,
+        })
+    ));
+}
@@ -0,0 +1,235 @@
+#![cfg(feature = "std")]
+
+use bincode::error::DecodeError;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+#[test]
+fn test_canonical_hash_map() {
+    let config = bincode::config::standard().with_canonical_encoding();
+
+    let forward: HashMap<u8, String> = (0..200).map(|i| (i, i.to_string())).collect();
+    let backward: HashMap<u8, String> = (0..200).rev().map(|i| (i, i.to_string())).collect();
+    let sorted: BTreeMap<u8, String> = (0..200).map(|i| (i, i.to_string())).collect();
+
+    // Keys that are encoded as one byte are sorted the same way as their values
+    let bytes = bincode::encode_to_vec(&forward, config).unwrap();
+    assert_eq!(bytes, bincode::encode_to_vec(&backward, config).unwrap());
+    assert_eq!(bytes, bincode::encode_to_vec(&sorted, config).unwrap());
+
+    let (decoded, len): (HashMap<u8, String>, usize) =
+        bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, forward);
+    assert_eq!(len, bytes.len());
+    let (decoded, _): (HashMap<u8, &str>, usize) =
+        bincode::borrow_decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded.len(), 200);
+
+    // Other keys are sorted by their encoded bytes
+    let set: HashSet<u32> = [251, 256, 300, 5].into_iter().collect();
+    let bytes = bincode::encode_to_vec(&set, config).unwrap();
+    assert_eq!(
+        bytes,
+        [4, 5, 251, 0, 1, 251, 44, 1, 251, 251, 0],
+        "expected 5, 256, 300, 251"
+    );
+    let (decoded, _): (HashSet<u32>, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, set);
+
+    // Without the canonical encoding the same bytes are accepted as well
+    let (decoded, _): (HashSet<u32>, usize) =
+        bincode::decode_from_slice(&bytes, bincode::config::standard()).unwrap();
+    assert_eq!(decoded, set);
+}
+
+#[test]
+fn test_canonical_unsorted_keys() {
+    let config = bincode::config::standard().with_canonical_encoding();
+
+    // A map of 2 entries, 2 => 0 and 1 => 0
+    let unsorted = [2, 2, 0, 1, 0];
+    assert!(matches!(
+        bincode::decode_from_slice::<HashMap<u8, u8>, _>(&unsorted, config),
+        Err(DecodeError::UnsortedKeys { .. })
+    ));
+    assert!(matches!(
+        bincode::decode_from_slice::<BTreeMap<u8, u8>, _>(&unsorted, config),
+        Err(DecodeError::UnsortedKeys { .. })
+    ));
+    assert!(matches!(
+        bincode::borrow_decode_from_slice::<HashMap<u8, u8>, _>(&unsorted, config),
+        Err(DecodeError::UnsortedKeys { .. })
+    ));
+    assert!(bincode::decode_from_slice::<HashMap<u8, u8>, _>(
+        &unsorted,
+        bincode::config::standard()
+    )
+    .is_ok());
+
+    let duplicate = [2, 1, 0, 1, 0];
+    assert!(matches!(
+        bincode::decode_from_slice::<HashMap<u8, u8>, _>(&duplicate, config),
+        Err(DecodeError::DuplicateMapKey { .. })
+    ));
+    assert!(matches!(
+        bincode::decode_from_slice::<BTreeMap<u8, u8>, _>(&duplicate, config),
+        Err(DecodeError::DuplicateMapKey { .. })
+    ));
+
+    let bytes = [2, 2, 1];
+    assert!(matches!(
+        bincode::decode_from_slice::<HashSet<u8>, _>(&bytes, config),
+        Err(DecodeError::UnsortedKeys { .. })
+    ));
+    assert!(matches!(
+        bincode::decode_from_slice::<BTreeSet<u8>, _>(&bytes, config),
+        Err(DecodeError::UnsortedKeys { .. })
+    ));
+
+    // Two empty keys are duplicates as well
+    let bytes = bincode::encode_to_vec([((), 1u8), ((), 2u8)], config).unwrap();
+    let mut bytes_with_len = vec![2];
+    bytes_with_len.extend_from_slice(&bytes);
+    assert!(matches!(
+        bincode::decode_from_slice::<HashMap<(), u8>, _>(&bytes_with_len, config),
+        Err(DecodeError::DuplicateMapKey { .. })
+    ));
+}
+
+#[test]
+fn test_canonical_varint() {
+    let config = bincode::config::standard().with_canonical_encoding();
+
+    // 5 encoded with 3 bytes
+    let bytes = [251, 5, 0];
+    assert_eq!(
+        bincode::decode_from_slice::<u32, _>(&bytes, bincode::config::standard())
+            .unwrap()
+            .0,
+        5
+    );
+    assert!(matches!(
+        bincode::decode_from_slice::<u32, _>(&bytes, config),
+        Err(DecodeError::NonCanonicalVarint)
+    ));
+    assert!(matches!(
+        bincode::decode_from_slice::<Vec<u64>, _>(&[1, 251, 5, 0], config),
+        Err(DecodeError::NonCanonicalVarint)
+    ));
+    assert!(matches!(
+        bincode::decode_from_slice::<[u64; 1], _>(&bytes, config),
+        Err(DecodeError::NonCanonicalVarint)
+    ));
+    assert!(matches!(
+        bincode::decode_from_slice::<u64, _>(&[253, 1, 0, 0, 0, 0, 0, 0, 0], config),
+        Err(DecodeError::NonCanonicalVarint)
+    ));
+
+    // The shortest encoding is still accepted
+    for value in [0u64, 250, 251, 65_535, 65_536, u32::MAX as u64, u64::MAX] {
+        let bytes = bincode::encode_to_vec(value, config).unwrap();
+        let (decoded, _): (u64, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+        assert_eq!(decoded, value);
+    }
+
+    // Leb128 values with a trailing zero byte
+    let config = config.with_leb128_int_encoding();
+    assert_eq!(
+        bincode::decode_from_slice::<u32, _>(
+            &[0x80, 0x00],
+            bincode::config::standard().with_leb128_int_encoding()
+        )
+        .unwrap()
+        .0,
+        0
+    );
+    assert!(matches!(
+        bincode::decode_from_slice::<u32, _>(&[0x80, 0x00], config),
+        Err(DecodeError::NonCanonicalVarint)
+    ));
+    assert!(matches!(
+        bincode::decode_from_slice::<i64, _>(&[0x81, 0x80, 0x00], config),
+        Err(DecodeError::NonCanonicalVarint)
+    ));
+    assert_eq!(
+        bincode::decode_from_slice::<u32, _>(&[0x00], config)
+            .unwrap()
+            .0,
+        0
+    );
+    assert_eq!(
+        bincode::decode_from_slice::<u32, _>(&[0x80, 0x01], config)
+            .unwrap()
+            .0,
+        128
+    );
+}
+
+#[test]
+fn test_strict_decoding() {
+    let config = bincode::config::standard().with_strict_decoding();
+
+    // Unsorted keys are fine, duplicate keys are not
+    let unsorted = [2, 2, 0, 1, 0];
+    let (map, _): (HashMap<u8, u8>, usize) = bincode::decode_from_slice(&unsorted, config).unwrap();
+    assert_eq!(map.len(), 2);
+    let duplicate = [2, 1, 0, 1, 5];
+    assert!(matches!(
+        bincode::decode_from_slice::<HashMap<u8, u8>, _>(&duplicate, config),
+        Err(DecodeError::DuplicateMapKey { .. })
+    ));
+    assert!(matches!(
+        bincode::borrow_decode_from_slice::<BTreeMap<u8, u8>, _>(&duplicate, config),
+        Err(DecodeError::DuplicateMapKey { .. })
+    ));
+    assert!(matches!(
+        bincode::decode_from_slice::<HashSet<u8>, _>(&[2, 1, 1], config),
+        Err(DecodeError::DuplicateMapKey { .. })
+    ));
+    assert!(matches!(
+        bincode::borrow_decode_from_slice::<BTreeSet<u8>, _>(&[2, 1, 1], config),
+        Err(DecodeError::DuplicateMapKey { .. })
+    ));
+
+    // Without strict decoding the last value wins
+    let (map, _): (HashMap<u8, u8>, usize) =
+        bincode::decode_from_slice(&duplicate, bincode::config::standard()).unwrap();
+    assert_eq!(map[&1], 5);
+
+    // 1 encoded with 9 bytes
+    let bytes = [253, 1, 0, 0, 0, 0, 0, 0, 0];
+    assert!(matches!(
+        bincode::decode_from_slice::<u64, _>(&bytes, config),
+        Err(DecodeError::NonCanonicalVarint)
+    ));
+    assert!(matches!(
+        bincode::decode_from_slice::<i64, _>(&bytes, config),
+        Err(DecodeError::NonCanonicalVarint)
+    ));
+    // Lengths are varints as well
+    assert!(matches!(
+        bincode::decode_from_slice::<Vec<u8>, _>(&[251, 1, 0, 7], config),
+        Err(DecodeError::NonCanonicalVarint)
+    ));
+    assert!(matches!(
+        bincode::decode_from_slice::<u32, _>(&[0x80, 0x00], config.with_leb128_int_encoding()),
+        Err(DecodeError::NonCanonicalVarint)
+    ));
+
+    let values = vec![0u64, 250, 251, 70_000, u64::MAX];
+    let bytes = bincode::encode_to_vec(&values, config).unwrap();
+    let (decoded, _): (Vec<u64>, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, values);
+
+    // Strict decoding can be turned off again, except for the canonical encoding
+    let overlong = [253, 1, 0, 0, 0, 0, 0, 0, 0];
+    let config = config.with_lenient_decoding();
+    assert_eq!(
+        bincode::decode_from_slice::<u64, _>(&overlong, config).unwrap(),
+        (1, 9)
+    );
+    let config = config.with_canonical_encoding().with_lenient_decoding();
+    assert!(matches!(
+        bincode::decode_from_slice::<u64, _>(&overlong, config),
+        Err(DecodeError::NonCanonicalVarint)
+    ));
+}
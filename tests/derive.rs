@@ -255,10 +255,12 @@ fn test_decode_borrowed_enum_tuple_variant() {
 
 #[derive(bincode::Decode, bincode::Encode, PartialEq, Eq, Debug)]
 enum CStyleEnum {
+    // Negative discriminants are encoded as `u32::MAX`, `u32::MAX - 1`, etc. unless they have an explicit tag
+    #[bincode(tag = 0)]
     A = -1,
     B = 2,
     C,
-    D = 5,
+    D = 0x05,
     E,
 }
 
@@ -273,10 +275,10 @@ fn test_c_style_enum() {
     }
 
     assert_eq!(ser(CStyleEnum::A), 0);
-    assert_eq!(ser(CStyleEnum::B), 1);
-    assert_eq!(ser(CStyleEnum::C), 2);
-    assert_eq!(ser(CStyleEnum::D), 3);
-    assert_eq!(ser(CStyleEnum::E), 4);
+    assert_eq!(ser(CStyleEnum::B), 2);
+    assert_eq!(ser(CStyleEnum::C), 3);
+    assert_eq!(ser(CStyleEnum::D), 5);
+    assert_eq!(ser(CStyleEnum::E), 6);
 
     fn assert_de_successfully(num: u8, expected: CStyleEnum) {
         match bincode::decode_from_slice::<CStyleEnum, _>(&[num], bincode::config::standard()) {
@@ -295,7 +297,7 @@ fn test_c_style_enum() {
             }
             Err(DecodeError::UnexpectedVariant {
                 type_name: "CStyleEnum",
-                allowed: &bincode::error::AllowedEnumVariants::Allowed(&[0, 1, 2, 3, 4]),
+                allowed: &bincode::error::AllowedEnumVariants::Allowed(&[0, 2, 3, 5, 6]),
                 found,
             }) if found == num as u32 => {}
             Err(e) => panic!("Expected DecodeError::UnexpectedVariant, got {e:?}"),
@@ -303,11 +305,273 @@ fn test_c_style_enum() {
     }
 
     assert_de_successfully(0, CStyleEnum::A);
-    assert_de_successfully(1, CStyleEnum::B);
-    assert_de_successfully(2, CStyleEnum::C);
-    assert_de_successfully(3, CStyleEnum::D);
-    assert_de_successfully(4, CStyleEnum::E);
-    assert_de_fails(5);
+    assert_de_successfully(2, CStyleEnum::B);
+    assert_de_successfully(3, CStyleEnum::C);
+    assert_de_successfully(5, CStyleEnum::D);
+    assert_de_successfully(6, CStyleEnum::E);
+    assert_de_fails(1);
+    assert_de_fails(4);
+    assert_de_fails(7);
+}
+
+#[derive(bincode::Decode, bincode::Encode, PartialEq, Debug)]
+enum TaggedEnum {
+    #[bincode(tag = 17)]
+    Ping,
+    #[bincode(tag = 3)]
+    Data(u32),
+    // One more than the previous tag
+    Text {
+        len: u8,
+    },
+}
+
+#[test]
+fn test_enum_tag() {
+    let config = bincode::config::standard();
+    let mut slice = [0u8; 10];
+
+    for (value, expected) in [
+        (TaggedEnum::Ping, &[17][..]),
+        (TaggedEnum::Data(5), &[3, 5]),
+        (TaggedEnum::Text { len: 1 }, &[4, 1]),
+    ] {
+        let len = bincode::encode_into_slice(&value, &mut slice, config).unwrap();
+        assert_eq!(&slice[..len], expected);
+        let (decoded, _): (TaggedEnum, usize) =
+            bincode::decode_from_slice(&slice[..len], config).unwrap();
+        assert_eq!(decoded, value);
+    }
+
+    let result = bincode::decode_from_slice::<TaggedEnum, _>(&[0], config);
+    assert!(matches!(
+        result,
+        Err(DecodeError::UnexpectedVariant {
+            found: 0,
+            allowed: &bincode::error::AllowedEnumVariants::Allowed(&[17, 3, 4]),
+            ..
+        })
+    ));
+}
+
+#[derive(bincode::Decode, bincode::Encode, PartialEq, Debug)]
+#[bincode(tag_type = "u8")]
+enum SmallTagEnum {
+    A,
+    B(u32),
+    #[bincode(tag = 255)]
+    C,
+}
+
+#[derive(bincode::Decode, bincode::Encode, PartialEq, Debug)]
+enum LargeTagEnum {
+    A,
+    #[bincode(tag = 300)]
+    B,
+}
+
+#[test]
+fn test_enum_tag_type() {
+    let mut slice = [0u8; 10];
+
+    // The attribute overrides the config
+    let config = bincode::config::legacy();
+    let len = bincode::encode_into_slice(SmallTagEnum::B(5), &mut slice, config).unwrap();
+    assert_eq!(&slice[..len], &[1, 5, 0, 0, 0]);
+    let (decoded, _): (SmallTagEnum, usize) =
+        bincode::decode_from_slice(&slice[..len], config).unwrap();
+    assert_eq!(decoded, SmallTagEnum::B(5));
+    let len = bincode::encode_into_slice(SmallTagEnum::C, &mut slice, config).unwrap();
+    assert_eq!(&slice[..len], &[255]);
+
+    let config = bincode::config::legacy().with_u8_enum_tags();
+    let len = bincode::encode_into_slice(TaggedEnum::Data(5), &mut slice, config).unwrap();
+    assert_eq!(&slice[..len], &[3, 5, 0, 0, 0]);
+    let (decoded, _): (TaggedEnum, usize) =
+        bincode::decode_from_slice(&slice[..len], config).unwrap();
+    assert_eq!(decoded, TaggedEnum::Data(5));
+    // Built-in enums are not affected
+    let len = bincode::encode_into_slice(Ok::<u8, u8>(1), &mut slice, config).unwrap();
+    assert_eq!(&slice[..len], &[0, 0, 0, 0, 1]);
+
+    let result = bincode::encode_into_slice(LargeTagEnum::B, &mut slice, config);
+    assert!(matches!(
+        result,
+        Err(bincode::error::EncodeError::EnumTagOverflow {
+            tag: 300,
+            tag_type: bincode::config::EnumTagType::U8,
+        })
+    ));
+
+    let config = bincode::config::legacy().with_u16_enum_tags();
+    let len = bincode::encode_into_slice(LargeTagEnum::B, &mut slice, config).unwrap();
+    assert_eq!(&slice[..len], &[44, 1]);
+    let (decoded, _): (LargeTagEnum, usize) =
+        bincode::decode_from_slice(&slice[..len], config).unwrap();
+    assert_eq!(decoded, LargeTagEnum::B);
+
+    // A variant index that is larger than an u16
+    let config = bincode::config::standard().with_u16_enum_tags();
+    let result = bincode::decode_from_slice::<LargeTagEnum, _>(&[252, 0, 0, 1, 0], config);
+    assert!(matches!(
+        result,
+        Err(DecodeError::InvalidIntegerType {
+            expected: bincode::error::IntegerType::U16,
+            found: bincode::error::IntegerType::U32,
+        })
+    ));
+}
+
+mod message_v1 {
+    #[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+    #[bincode(length_prefixed)]
+    pub enum Message<'a> {
+        Ping,
+        Text(u8),
+        #[bincode(other)]
+        Unknown(u32, &'a [u8]),
+    }
+}
+
+mod message_v2 {
+    #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+    #[bincode(length_prefixed)]
+    pub enum Message {
+        Ping,
+        Text(u8),
+        Move { x: i32, y: i32 },
+    }
+}
+
+// A newer version of `message_v2`, that added a field to `Text`
+mod message_v3 {
+    #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+    #[bincode(length_prefixed)]
+    pub enum Message {
+        Ping,
+        Text(u8, u16),
+        Move { x: i32, y: i32 },
+    }
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+#[bincode(length_prefixed)]
+enum SkippingMessage {
+    Ping,
+    #[bincode(other)]
+    Unknown,
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+enum Color {
+    Red,
+    Green,
+    #[bincode(other)]
+    Unknown,
+}
+
+#[test]
+fn test_other_variant() {
+    let mut slice = [0u8; 20];
+    let config = bincode::config::standard();
+
+    // Every variant is followed by the length of its body
+    let len = bincode::encode_into_slice(
+        message_v2::Message::Move { x: 1, y: -1 },
+        &mut slice,
+        config,
+    )
+    .unwrap();
+    assert_eq!(&slice[..len], &[2, 2, 2, 1]);
+    let len = bincode::encode_into_slice(message_v2::Message::Ping, &mut slice, config).unwrap();
+    assert_eq!(&slice[..len], &[0, 0]);
+
+    // Unknown variants keep their tag and raw bytes, and are encoded as they were read
+    let len = bincode::encode_into_slice(
+        message_v2::Message::Move { x: 1, y: -1 },
+        &mut slice,
+        config,
+    )
+    .unwrap();
+    let (decoded, read): (message_v1::Message, usize) =
+        bincode::borrow_decode_from_slice(&slice[..len], config).unwrap();
+    assert_eq!(read, len);
+    assert_eq!(decoded, message_v1::Message::Unknown(2, &[2, 1]));
+    let mut reencoded = [0u8; 20];
+    let reencoded_len = bincode::encode_into_slice(&decoded, &mut reencoded, config).unwrap();
+    assert_eq!(&reencoded[..reencoded_len], &slice[..len]);
+    let (decoded, _): (message_v2::Message, usize) =
+        bincode::decode_from_slice(&reencoded[..reencoded_len], config).unwrap();
+    assert_eq!(decoded, message_v2::Message::Move { x: 1, y: -1 });
+
+    // Known variants decode as usual
+    let len =
+        bincode::encode_into_slice(message_v2::Message::Text(b'a'), &mut slice, config).unwrap();
+    let (decoded, _): (message_v1::Message, usize) =
+        bincode::borrow_decode_from_slice(&slice[..len], config).unwrap();
+    assert_eq!(decoded, message_v1::Message::Text(b'a'));
+
+    // A unit `other` variant skips the body
+    let len = bincode::encode_into_slice(
+        message_v2::Message::Move { x: 1, y: -1 },
+        &mut slice,
+        config,
+    )
+    .unwrap();
+    let (decoded, read): (SkippingMessage, usize) =
+        bincode::decode_from_slice(&slice[..len], config).unwrap();
+    assert_eq!(decoded, SkippingMessage::Unknown);
+    assert_eq!(read, len);
+    assert!(matches!(
+        bincode::encode_into_slice(SkippingMessage::Unknown, &mut slice, config),
+        Err(bincode::error::EncodeError::Other(_))
+    ));
+
+    // Without a length prefix only the tag is consumed
+    let (decoded, read): (Color, usize) = bincode::decode_from_slice(&[1], config).unwrap();
+    assert_eq!((decoded, read), (Color::Green, 1));
+    let (decoded, read): (Color, usize) = bincode::decode_from_slice(&[7], config).unwrap();
+    assert_eq!((decoded, read), (Color::Unknown, 1));
+}
+
+#[test]
+fn test_length_prefixed_variant_skip_rest() {
+    let mut slice = [0u8; 20];
+    let config = bincode::config::standard();
+
+    // Fields that a newer version added to a known variant are skipped, and the value after it is still read from the right place
+    let len = bincode::encode_into_slice(
+        (message_v3::Message::Text(b'a', 1000), 7u8),
+        &mut slice,
+        config,
+    )
+    .unwrap();
+    let ((decoded, next), read): ((message_v2::Message, u8), usize) =
+        bincode::decode_from_slice(&slice[..len], config).unwrap();
+    assert_eq!(read, len);
+    assert_eq!(decoded, message_v2::Message::Text(b'a'));
+    assert_eq!(next, 7);
+
+    // The fields of a variant can't read more bytes than the length of its body
+    let (decoded, _): (message_v3::Message, usize) =
+        bincode::decode_from_slice(&[1, 2, b'a', 7], config).unwrap();
+    assert_eq!(decoded, message_v3::Message::Text(b'a', 7));
+    let result: Result<(message_v3::Message, usize), _> =
+        bincode::decode_from_slice(&[1, 2, b'a', 251, 232, 3], config);
+    assert!(matches!(
+        result,
+        Err(bincode::error::DecodeError::UnexpectedEnd { additional: 2 })
+    ));
+    // Also on readers that don't know their position
+    #[cfg(feature = "std")]
+    {
+        let result: Result<message_v3::Message, _> =
+            bincode::decode_from_std_read(&mut &[1, 2, b'a', 251, 232, 3][..], config);
+        assert!(matches!(
+            result,
+            Err(bincode::error::DecodeError::UnexpectedEnd { additional: 2 })
+        ));
+    }
 }
 
 macro_rules! macro_newtype {
@@ -403,6 +667,230 @@ fn test_enum_with_generics_roundtrip() {
     assert_eq!(start, decoded);
 }
 
+#[cfg(feature = "alloc")]
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+enum Expr {
+    Value(u32),
+    Negate(Box<Expr>),
+    Add(Vec<Expr>),
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct Wrapper<T> {
+    inner: T,
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_max_depth() {
+    let config = bincode::config::standard().with_max_depth::<8>();
+
+    // Every Negate adds two levels: the enum itself and its Box
+    let mut expr = Expr::Value(5);
+    for _ in 0..3 {
+        expr = Expr::Negate(Box::new(expr));
+    }
+    let bytes = bincode::encode_to_vec(&expr, config).unwrap();
+    let decoded: Expr = bincode::decode_from_slice(&bytes, config).unwrap().0;
+    assert_eq!(decoded, expr);
+
+    let expr = Expr::Negate(Box::new(expr));
+    let bytes = bincode::encode_to_vec(&expr, config).unwrap();
+    let result: Result<(Expr, usize), _> = bincode::decode_from_slice(&bytes, config);
+    assert!(matches!(result, Err(DecodeError::DepthLimitExceeded)));
+    let result: Result<(Expr, usize), _> = bincode::borrow_decode_from_slice(&bytes, config);
+    assert!(matches!(result, Err(DecodeError::DepthLimitExceeded)));
+
+    let expr = Expr::Add(vec![Expr::Value(1), Expr::Value(2)]);
+    let value = Wrapper {
+        inner: Wrapper { inner: expr },
+    };
+    let bytes = bincode::encode_to_vec(&value, config).unwrap();
+    let decoded: Wrapper<Wrapper<Expr>> = bincode::decode_from_slice(&bytes, config).unwrap().0;
+    assert_eq!(decoded, value);
+
+    let config = bincode::config::standard().with_max_depth::<4>();
+    let result: Result<(Wrapper<Wrapper<Expr>>, usize), _> =
+        bincode::decode_from_slice(&bytes, config);
+    assert!(matches!(result, Err(DecodeError::DepthLimitExceeded)));
+}
+
+/// A type that can't be encoded
+#[derive(PartialEq, Debug, Default)]
+struct Handle(u32);
+
+fn default_generation() -> u32 {
+    7
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+struct WithSkippedFields<'a> {
+    name: &'a str,
+    #[bincode(skip)]
+    handle: Handle,
+    value: u8,
+    #[bincode(skip, default = "default_generation")]
+    generation: u32,
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+enum EnumWithSkippedFields {
+    Tuple(#[bincode(skip)] Handle, u8),
+    Struct {
+        value: u8,
+        #[bincode(skip, default = "default_generation")]
+        generation: u32,
+    },
+}
+
+#[test]
+fn test_skip() {
+    let config = bincode::config::standard();
+
+    let value = WithSkippedFields {
+        name: "a",
+        handle: Handle(5),
+        value: 10,
+        generation: 1,
+    };
+    let mut slice = [0u8; 16];
+    let len = bincode::encode_into_slice(&value, &mut slice, config).unwrap();
+    assert_eq!(&slice[..len], &[1, b'a', 10]);
+    let (decoded, _): (WithSkippedFields, usize) =
+        bincode::borrow_decode_from_slice(&slice[..len], config).unwrap();
+    assert_eq!(
+        decoded,
+        WithSkippedFields {
+            name: "a",
+            handle: Handle(0),
+            value: 10,
+            generation: 7,
+        }
+    );
+
+    let len = bincode::encode_into_slice(
+        EnumWithSkippedFields::Tuple(Handle(5), 3),
+        &mut slice,
+        config,
+    )
+    .unwrap();
+    assert_eq!(&slice[..len], &[0, 3]);
+    let (decoded, _): (EnumWithSkippedFields, usize) =
+        bincode::decode_from_slice(&slice[..len], config).unwrap();
+    assert_eq!(decoded, EnumWithSkippedFields::Tuple(Handle(0), 3));
+
+    let value = EnumWithSkippedFields::Struct {
+        value: 3,
+        generation: 1,
+    };
+    let len = bincode::encode_into_slice(value, &mut slice, config).unwrap();
+    assert_eq!(&slice[..len], &[1, 3]);
+    let (decoded, _): (EnumWithSkippedFields, usize) =
+        bincode::decode_from_slice(&slice[..len], config).unwrap();
+    assert_eq!(
+        decoded,
+        EnumWithSkippedFields::Struct {
+            value: 3,
+            generation: 7,
+        }
+    );
+}
+
+mod foreign {
+    /// A type from another crate, which does not implement `Encode` or `Decode`
+    #[derive(PartialEq, Debug)]
+    pub struct Timestamp {
+        pub millis: i64,
+    }
+}
+
+mod timestamp_as_secs {
+    use super::foreign::Timestamp;
+    use bincode::{
+        de::Decoder,
+        enc::Encoder,
+        error::{DecodeError, EncodeError},
+        Decode, Encode,
+    };
+
+    pub fn encode<E: Encoder>(value: &Timestamp, encoder: &mut E) -> Result<(), EncodeError> {
+        (value.millis / 1000).encode(encoder)
+    }
+
+    pub fn decode<D: Decoder>(decoder: &mut D) -> Result<Timestamp, DecodeError> {
+        let secs = i64::decode(decoder)?;
+        Ok(Timestamp {
+            millis: secs * 1000,
+        })
+    }
+}
+
+fn encode_inverted<E: bincode::enc::Encoder>(
+    value: &u8,
+    encoder: &mut E,
+) -> Result<(), bincode::error::EncodeError> {
+    bincode::Encode::encode(&!*value, encoder)
+}
+
+fn decode_inverted<D: bincode::de::Decoder>(
+    decoder: &mut D,
+) -> Result<u8, bincode::error::DecodeError> {
+    let value: u8 = bincode::Decode::decode(decoder)?;
+    Ok(!value)
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct WithCustomCodec {
+    #[bincode(with = "timestamp_as_secs")]
+    created: foreign::Timestamp,
+    #[bincode(encode_with = "encode_inverted", decode_with = "decode_inverted")]
+    inverted: u8,
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+enum EnumWithCustomCodec<'a> {
+    Created(
+        #[bincode(with = "timestamp_as_secs")] foreign::Timestamp,
+        &'a str,
+    ),
+    Inverted {
+        #[bincode(encode_with = "encode_inverted", decode_with = "decode_inverted")]
+        value: u8,
+    },
+}
+
+#[test]
+fn test_custom_codec() {
+    let config = bincode::config::standard();
+    let mut slice = [0u8; 16];
+
+    let value = WithCustomCodec {
+        created: foreign::Timestamp { millis: 5_000 },
+        inverted: 0xF0,
+    };
+    let len = bincode::encode_into_slice(&value, &mut slice, config).unwrap();
+    // 5 seconds, zigzag encoded, followed by the inverted byte
+    assert_eq!(&slice[..len], &[10, 0x0F]);
+    let (decoded, _): (WithCustomCodec, usize) =
+        bincode::decode_from_slice(&slice[..len], config).unwrap();
+    assert_eq!(decoded, value);
+
+    let value = EnumWithCustomCodec::Created(foreign::Timestamp { millis: 2_000 }, "a");
+    let len = bincode::encode_into_slice(&value, &mut slice, config).unwrap();
+    assert_eq!(&slice[..len], &[0, 4, 1, b'a']);
+    let (decoded, _): (EnumWithCustomCodec, usize) =
+        bincode::borrow_decode_from_slice(&slice[..len], config).unwrap();
+    assert_eq!(decoded, value);
+
+    let value = EnumWithCustomCodec::Inverted { value: 1 };
+    let len = bincode::encode_into_slice(&value, &mut slice, config).unwrap();
+    assert_eq!(&slice[..len], &[1, 0xFE]);
+    let (decoded, _): (EnumWithCustomCodec, usize) =
+        bincode::borrow_decode_from_slice(&slice[..len], config).unwrap();
+    assert_eq!(decoded, value);
+}
+
 #[cfg(feature = "alloc")]
 mod zoxide {
     extern crate alloc;
@@ -444,3 +932,109 @@ mod zoxide {
         assert!(matches!(decoded[1].path, Cow::Borrowed("Bar")));
     }
 }
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Encode, bincode::Decode, Debug)]
+pub enum Shape {
+    Circle { radius: u32 },
+    Polygon(Vec<(u32, u32)>, bool),
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Encode, bincode::Decode, Debug)]
+pub struct Drawing {
+    name: String,
+    shapes: [Shape; 2],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Encode, bincode::BorrowDecode, Debug)]
+pub struct BorrowedDrawing<'a> {
+    name: &'a str,
+    shapes: [Shape; 2],
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_error_path() {
+    use bincode::error::PathSegment;
+
+    let config = bincode::config::standard();
+    let drawing = Drawing {
+        name: "house".into(),
+        shapes: [
+            Shape::Circle { radius: 3 },
+            Shape::Polygon(vec![(0, 0), (4, 0)], true),
+        ],
+    };
+    let mut bytes = bincode::encode_to_vec(&drawing, config).unwrap();
+    // The `bool` of the polygon
+    *bytes.last_mut().unwrap() = 2;
+
+    // The other decode functions return the error without the path
+    let error = bincode::decode_from_slice::<Drawing, _>(&bytes, config).unwrap_err();
+    assert!(matches!(error, DecodeError::InvalidBooleanValue(2)));
+
+    let error = bincode::decode_from_slice_with_context::<Drawing, _>(&bytes, config).unwrap_err();
+    assert!(matches!(error.error(), DecodeError::InvalidBooleanValue(2)));
+    assert_eq!(
+        error.path(),
+        [
+            PathSegment::Field {
+                type_name: "derive::Drawing",
+                field: "shapes"
+            },
+            PathSegment::Index(1),
+            PathSegment::VariantField {
+                type_name: "derive::Shape",
+                variant: "Polygon",
+                field: "1"
+            },
+        ]
+    );
+    assert_eq!(error.offset(), bytes.len());
+    let path: String = error.path().iter().map(|s| s.to_string()).collect();
+    assert_eq!(path, ".shapes[1]::Polygon.1");
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "invalid bool value, expected 0 or 1, found 2 at derive::Drawing.shapes[1]::Polygon.1, offset {}",
+            bytes.len()
+        )
+    );
+
+    let error =
+        bincode::borrow_decode_from_slice_with_context::<BorrowedDrawing, _>(&bytes, config)
+            .unwrap_err();
+    assert!(matches!(error.error(), DecodeError::InvalidBooleanValue(2)));
+    assert_eq!(error.path().len(), 3);
+
+    // An unknown variant inside a collection
+    bytes[8] = 7;
+    let error = bincode::decode_from_slice_with_context::<Drawing, _>(&bytes, config).unwrap_err();
+    assert!(matches!(
+        error.error(),
+        DecodeError::UnexpectedVariant { found: 7, .. }
+    ));
+    assert_eq!(error.path()[1], PathSegment::Index(1));
+    assert_eq!(error.offset(), 9);
+    assert!(matches!(
+        error.into_error(),
+        DecodeError::UnexpectedVariant { found: 7, .. }
+    ));
+
+    // Errors outside of a field have an offset, but no path
+    let error = bincode::decode_from_slice_with_context::<Shape, _>(&[9], config).unwrap_err();
+    assert!(error.path().is_empty());
+    assert_eq!(error.offset(), 1);
+    assert_eq!(
+        error.to_string(),
+        "invalid variant index of Shape, expected 0..=1, found 9, offset 1"
+    );
+    let error = bincode::decode_from_slice_with_context::<u32, _>(&[251, 1], config).unwrap_err();
+    assert!(matches!(
+        error.error(),
+        DecodeError::UnexpectedEnd { additional: 1 }
+    ));
+    assert_eq!(error.offset(), 1);
+}
@@ -5,6 +5,7 @@ extern crate bincode as bincode_new;
 // Make sure that the `bincode` crate exists, just symlink it to `core.
 extern crate core as bincode;
 
+#[allow(dead_code)]
 #[derive(bincode_new::Encode)]
 #[bincode(crate = "bincode_new")]
 struct DeriveRenameTest {
@@ -0,0 +1,273 @@
+#![cfg(all(feature = "derive", feature = "std"))]
+
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+mod v0 {
+    #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+    #[bincode(versioned)]
+    pub struct Player {
+        pub id: u32,
+        pub name: alloc::string::String,
+    }
+}
+
+mod v1 {
+    #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+    #[bincode(versioned)]
+    pub struct Player {
+        pub id: u32,
+        pub name: alloc::string::String,
+        #[bincode(version = 1)]
+        pub score: u64,
+        #[bincode(version = 1, default = "default_level")]
+        pub level: u8,
+    }
+
+    fn default_level() -> u8 {
+        1
+    }
+}
+
+mod v3 {
+    #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+    #[bincode(versioned)]
+    pub struct Player {
+        pub id: u32,
+        pub name: alloc::string::String,
+        #[bincode(version = 1)]
+        pub score: u64,
+        #[bincode(version = 1)]
+        pub level: u8,
+        // Nothing was added in version 2
+        #[bincode(version = 3)]
+        pub friends: alloc::vec::Vec<u32>,
+    }
+}
+
+// Version 1 of a newer build of `v1::Player`, that added a field to version 1
+mod v1_extended {
+    #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+    #[bincode(versioned)]
+    pub struct Player {
+        pub id: u32,
+        pub name: alloc::string::String,
+        #[bincode(version = 1)]
+        pub score: u64,
+        #[bincode(version = 1)]
+        pub level: u8,
+        #[bincode(version = 1)]
+        pub title: alloc::string::String,
+    }
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+#[bincode(versioned)]
+struct Tree {
+    value: u8,
+    #[bincode(version = 1)]
+    children: Vec<Tree>,
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+#[bincode(versioned)]
+struct Tuple(u8, #[bincode(version = 1)] u16);
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+#[bincode(versioned)]
+struct Borrowed<'a> {
+    id: u8,
+    #[bincode(version = 1)]
+    name: &'a str,
+}
+
+#[test]
+fn test_versioned_layout() {
+    let config = bincode::config::standard();
+
+    let player = v0::Player {
+        id: 5,
+        name: String::from("a"),
+    };
+    let bytes = bincode::encode_to_vec(&player, config).unwrap();
+    // version, id, name
+    assert_eq!(bytes, &[0, 5, 1, b'a']);
+
+    let player = v1::Player {
+        id: 5,
+        name: String::from("a"),
+        score: 300,
+        level: 2,
+    };
+    let bytes = bincode::encode_to_vec(&player, config).unwrap();
+    // version, id, name, length of version 1, score, level
+    assert_eq!(bytes, &[1, 5, 1, b'a', 4, 251, 44, 1, 2]);
+
+    let bytes = bincode::encode_to_vec(Tuple(1, 2), config).unwrap();
+    assert_eq!(bytes, &[1, 1, 1, 2]);
+    let (decoded, len): (Tuple, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, Tuple(1, 2));
+    assert_eq!(len, bytes.len());
+
+    let borrowed = Borrowed { id: 1, name: "a" };
+    let bytes = bincode::encode_to_vec(&borrowed, config).unwrap();
+    assert_eq!(bytes, &[1, 1, 2, 1, b'a']);
+    let (decoded, len): (Borrowed, usize) =
+        bincode::borrow_decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, borrowed);
+    assert_eq!(len, bytes.len());
+}
+
+#[test]
+fn test_versioned_read_older() {
+    let config = bincode::config::standard();
+
+    let bytes = bincode::encode_to_vec(
+        v0::Player {
+            id: 5,
+            name: String::from("a"),
+        },
+        config,
+    )
+    .unwrap();
+    let (decoded, len): (v3::Player, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(
+        decoded,
+        v3::Player {
+            id: 5,
+            name: String::from("a"),
+            score: 0,
+            level: 0,
+            friends: Vec::new(),
+        }
+    );
+
+    // Fields that are missing use their `default` function
+    let (decoded, _): (v1::Player, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded.level, 1);
+}
+
+#[test]
+fn test_versioned_read_newer() {
+    let config = bincode::config::standard();
+    let player = v3::Player {
+        id: 5,
+        name: String::from("a"),
+        score: 300,
+        level: 2,
+        friends: (0..100).collect(),
+    };
+    let bytes = bincode::encode_to_vec(&player, config).unwrap();
+
+    let (decoded, len): (v3::Player, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, player);
+    assert_eq!(len, bytes.len());
+
+    // Older versions skip the fields they don't know about
+    let (decoded, len): (v1::Player, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(
+        decoded,
+        v1::Player {
+            id: 5,
+            name: String::from("a"),
+            score: 300,
+            level: 2,
+        }
+    );
+
+    let (decoded, len): (v0::Player, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(decoded.id, 5);
+
+    // Skipping also works on readers that can't peek
+    let mut reader = std::io::BufReader::with_capacity(2, &bytes[..]);
+    let decoded: v0::Player = bincode::decode_from_std_read(&mut reader, config).unwrap();
+    assert_eq!(decoded.id, 5);
+    assert!(std::io::Read::read(&mut reader, &mut [0u8; 1]).unwrap() == 0);
+
+    let result: Result<(v0::Player, usize), _> =
+        bincode::decode_from_slice(&bytes[..bytes.len() - 1], config);
+    assert!(matches!(
+        result,
+        Err(bincode::error::DecodeError::UnexpectedEnd { .. })
+    ));
+}
+
+#[test]
+fn test_versioned_skip_rest_of_version() {
+    let config = bincode::config::standard();
+    let player = v1_extended::Player {
+        id: 5,
+        name: String::from("a"),
+        score: 300,
+        level: 2,
+        title: String::from("captain"),
+    };
+    let bytes = bincode::encode_to_vec((&player, 7u8), config).unwrap();
+
+    // The title is skipped, and the value after the player is still read from the right place
+    let expected = v1::Player {
+        id: 5,
+        name: String::from("a"),
+        score: 300,
+        level: 2,
+    };
+    let ((decoded, next), len): ((v1::Player, u8), usize) =
+        bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(decoded, expected);
+    assert_eq!(next, 7);
+
+    let (decoded, next): (v1::Player, u8) =
+        bincode::decode_from_std_read(&mut &bytes[..], config).unwrap();
+    assert_eq!(decoded, expected);
+    assert_eq!(next, 7);
+
+    // The fields of a version can't read more bytes than the length of that version
+    // version, id, name, length of version 1 (too short), score, level
+    let bytes = [1, 5, 1, b'a', 2, 251, 44, 1, 2];
+    let result: Result<(v1::Player, usize), _> = bincode::decode_from_slice(&bytes, config);
+    assert!(matches!(
+        result,
+        Err(bincode::error::DecodeError::UnexpectedEnd { additional: 1 })
+    ));
+    // Also on readers that don't know their position
+    let result: Result<v1::Player, _> = bincode::decode_from_std_read(&mut &bytes[..], config);
+    assert!(matches!(
+        result,
+        Err(bincode::error::DecodeError::UnexpectedEnd { additional: 1 })
+    ));
+    // The path of the error goes through the version
+    let error =
+        bincode::decode_from_slice_with_context::<v1::Player, _>(&bytes, config).unwrap_err();
+    assert_eq!(
+        error.path(),
+        [bincode::error::PathSegment::Field {
+            type_name: "derive_versioned::v1::Player",
+            field: "score"
+        }]
+    );
+}
+
+#[test]
+fn test_versioned_recursive() {
+    let config = bincode::config::standard();
+    let mut tree = Tree {
+        value: 0,
+        children: Vec::new(),
+    };
+    for value in 1..30 {
+        tree = Tree {
+            value,
+            children: alloc::vec![tree],
+        };
+    }
+    let bytes = bincode::encode_to_vec(&tree, config).unwrap();
+    let (decoded, len): (Tree, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(decoded, tree);
+}
@@ -0,0 +1,73 @@
+use bincode::error::{AllowedEnumVariants, DecodeError, EncodeError, IntegerType};
+
+#[test]
+fn test_decode_error_display() {
+    let error = DecodeError::UnexpectedVariant {
+        type_name: "Shape",
+        allowed: &AllowedEnumVariants::Range { min: 0, max: 3 },
+        found: 7,
+    };
+    assert_eq!(
+        error.to_string(),
+        "invalid variant index of Shape, expected 0..=3, found 7"
+    );
+    let error = DecodeError::UnexpectedVariant {
+        type_name: "Shape",
+        allowed: &AllowedEnumVariants::Allowed(&[1, 5, 9]),
+        found: 7,
+    };
+    assert_eq!(
+        error.to_string(),
+        "invalid variant index of Shape, expected one of 1, 5, 9, found 7"
+    );
+
+    let error = DecodeError::InvalidIntegerType {
+        expected: IntegerType::U16,
+        found: IntegerType::U64,
+    };
+    assert_eq!(error.to_string(), "invalid varint, expected u16, found u64");
+    let error =
+        bincode::decode_from_slice::<i16, _>(&[255], bincode::config::standard()).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "invalid varint, expected i16, found a reserved discriminant"
+    );
+
+    let error =
+        bincode::decode_from_slice::<u32, _>(&[251, 5], bincode::config::standard()).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "unexpected end of input, about 1 more bytes were needed"
+    );
+    let error =
+        bincode::decode_from_slice::<char, _>(&[0xff], bincode::config::standard()).unwrap_err();
+    assert_eq!(error.to_string(), "invalid char encoding [ff, 00, 00, 00]");
+    let error = DecodeError::FingerprintMismatch {
+        expected: 0xabcd,
+        found: 1,
+    };
+    assert_eq!(
+        error.to_string(),
+        "fingerprint mismatch, expected 0x000000000000abcd, found 0x0000000000000001"
+    );
+    assert_eq!(
+        DecodeError::NonCanonicalVarint.to_string(),
+        "a varint was encoded with more bytes than needed"
+    );
+}
+
+#[test]
+fn test_encode_error_display() {
+    let error = EncodeError::EnumTagOverflow {
+        tag: 300,
+        tag_type: bincode::config::EnumTagType::U8,
+    };
+    assert_eq!(
+        error.to_string(),
+        "the variant index 300 does not fit in an u8 enum tag"
+    );
+
+    let error = bincode::encode_into_slice(1000u32, &mut [0u8; 2], bincode::config::standard())
+        .unwrap_err();
+    assert_eq!(error.to_string(), "the writer ran out of storage");
+}
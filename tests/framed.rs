@@ -0,0 +1,170 @@
+#![cfg(feature = "std")]
+
+use bincode::{
+    error::DecodeError,
+    framed::{FrameDecoder, FrameEncoder},
+};
+use std::collections::VecDeque;
+
+/// A reader that returns the given chunks one at a time, and `WouldBlock` if there is an empty chunk.
+struct ChunkedReader {
+    chunks: VecDeque<Vec<u8>>,
+}
+
+impl std::io::Read for ChunkedReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let chunk = match self.chunks.front_mut() {
+            Some(chunk) => chunk,
+            None => return Ok(0),
+        };
+        if chunk.is_empty() {
+            self.chunks.pop_front();
+            return Err(std::io::ErrorKind::WouldBlock.into());
+        }
+        let len = buf.len().min(chunk.len());
+        buf[..len].copy_from_slice(&chunk[..len]);
+        chunk.drain(..len);
+        if chunk.is_empty() {
+            self.chunks.pop_front();
+        }
+        Ok(len)
+    }
+}
+
+#[test]
+fn test_framed_roundtrip() {
+    let config = bincode::config::standard();
+    let mut stream = Vec::new();
+    let mut encoder = FrameEncoder::new(&mut stream, config);
+    assert_eq!(encoder.encode(&5u32).unwrap(), 2);
+    assert_eq!(encoder.encode(&String::from("abc")).unwrap(), 5);
+    assert_eq!(encoder.encode(&vec![1u64; 300]).unwrap(), 306);
+
+    let mut decoder = FrameDecoder::new(stream.as_slice(), config);
+    assert_eq!(decoder.decode::<u32>().unwrap(), Some(5));
+    assert_eq!(
+        decoder.decode::<String>().unwrap(),
+        Some(String::from("abc"))
+    );
+    assert_eq!(decoder.decode::<Vec<u64>>().unwrap(), Some(vec![1u64; 300]));
+    assert_eq!(decoder.decode::<u32>().unwrap(), None);
+    assert!(!decoder.has_partial_frame());
+}
+
+#[test]
+fn test_framed_fixed_int_prefix() {
+    let config = bincode::config::legacy();
+    let mut stream = Vec::new();
+    FrameEncoder::new(&mut stream, config)
+        .encode(&0x0102u16)
+        .unwrap();
+    assert_eq!(stream, [2, 0, 0, 0, 0, 0, 0, 0, 0x02, 0x01]);
+
+    let mut decoder = FrameDecoder::new(stream.as_slice(), config);
+    assert_eq!(decoder.decode::<u16>().unwrap(), Some(0x0102));
+}
+
+#[test]
+fn test_framed_limit_per_frame() {
+    let config = bincode::config::standard();
+    let mut stream = Vec::new();
+    let mut encoder = FrameEncoder::new(&mut stream, config);
+    encoder.encode(&String::from("small")).unwrap();
+    encoder
+        .encode(&String::from("this frame is too large"))
+        .unwrap();
+    encoder.encode(&String::from("small")).unwrap();
+
+    let mut decoder = FrameDecoder::new(stream.as_slice(), config.with_limit::<20>());
+    assert_eq!(
+        decoder.decode::<String>().unwrap(),
+        Some(String::from("small"))
+    );
+    assert!(matches!(
+        decoder.decode::<String>(),
+        Err(DecodeError::LimitExceeded)
+    ));
+    assert!(decoder.has_partial_frame());
+    assert_eq!(
+        decoder.decode::<String>().unwrap(),
+        Some(String::from("small"))
+    );
+    assert_eq!(decoder.decode::<String>().unwrap(), None);
+}
+
+#[test]
+fn test_framed_partial_frame() {
+    let config = bincode::config::legacy();
+    let mut stream = Vec::new();
+    let mut encoder = FrameEncoder::new(&mut stream, config);
+    encoder.encode(&String::from("Hello world")).unwrap();
+    encoder.encode(&12345u32).unwrap();
+
+    let reader = ChunkedReader {
+        chunks: VecDeque::from(vec![
+            stream[..3].to_vec(),
+            Vec::new(),
+            stream[3..12].to_vec(),
+            Vec::new(),
+            stream[12..].to_vec(),
+        ]),
+    };
+    let mut decoder = FrameDecoder::new(reader, config);
+
+    match decoder.decode::<String>() {
+        Err(DecodeError::Io { inner, .. }) => {
+            assert_eq!(inner.kind(), std::io::ErrorKind::WouldBlock)
+        }
+        x => panic!("Expected WouldBlock, got {:?}", x),
+    }
+    assert!(decoder.has_partial_frame());
+    match decoder.decode::<String>() {
+        Err(DecodeError::Io { inner, .. }) => {
+            assert_eq!(inner.kind(), std::io::ErrorKind::WouldBlock)
+        }
+        x => panic!("Expected WouldBlock, got {:?}", x),
+    }
+    assert_eq!(
+        decoder.decode::<String>().unwrap(),
+        Some(String::from("Hello world"))
+    );
+    assert_eq!(decoder.decode::<u32>().unwrap(), Some(12345));
+    assert_eq!(decoder.decode::<u32>().unwrap(), None);
+}
+
+#[test]
+fn test_framed_unexpected_end() {
+    let config = bincode::config::standard();
+    let mut stream = Vec::new();
+    FrameEncoder::new(&mut stream, config)
+        .encode(&String::from("Hello world"))
+        .unwrap();
+    stream.truncate(5);
+
+    let mut decoder = FrameDecoder::new(stream.as_slice(), config);
+    assert!(matches!(
+        decoder.decode::<String>(),
+        Err(DecodeError::UnexpectedEnd { .. })
+    ));
+    assert!(decoder.has_partial_frame());
+}
+
+#[test]
+fn test_framed_invalid_payload() {
+    let config = bincode::config::standard();
+    // A frame of 2 bytes with a string that needs 4, a frame of 2 bytes with an `u8` and a trailing byte, and a valid frame
+    let stream = [2, 3, b'a', 2, 7, 8, 1, 9];
+
+    let mut decoder = FrameDecoder::new(&stream[..], config);
+    assert!(matches!(
+        decoder.decode::<String>(),
+        Err(DecodeError::FrameTooShort { additional: 2 })
+    ));
+    assert!(!decoder.has_partial_frame());
+    assert!(matches!(
+        decoder.decode::<u8>(),
+        Err(DecodeError::TrailingBytes { remaining: 1 })
+    ));
+    assert_eq!(decoder.decode::<u8>().unwrap(), Some(9));
+    assert_eq!(decoder.decode::<u8>().unwrap(), None);
+}
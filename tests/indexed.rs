@@ -0,0 +1,169 @@
+#![cfg(all(feature = "derive", feature = "alloc"))]
+
+use bincode::config::Config;
+use bincode::de::{read::SliceReader, DecoderImpl};
+use bincode::error::DecodeError;
+use bincode::{BorrowDecode, Decode, Encode, Indexed, IndexedSlice};
+
+#[derive(Encode, Decode, PartialEq, Debug)]
+struct Entry {
+    id: u32,
+    name: String,
+}
+
+#[derive(Encode, BorrowDecode, PartialEq, Debug)]
+struct BorrowedEntry<'a> {
+    id: u32,
+    name: &'a str,
+}
+
+fn make_entries() -> Vec<Entry> {
+    (0..10)
+        .map(|id| Entry {
+            id,
+            name: "x".repeat(id as usize * 30),
+        })
+        .collect()
+}
+
+#[test]
+fn test_indexed_roundtrip() {
+    let config = bincode::config::standard();
+    let entries = make_entries();
+    let bytes = bincode::encode_to_vec(Indexed(&entries[..]), config).unwrap();
+
+    // The slice and the vec have the same encoding
+    let vec_bytes = bincode::encode_to_vec(Indexed(make_entries()), config).unwrap();
+    assert_eq!(bytes, vec_bytes);
+
+    // The offset table is always 8 bytes per item
+    let plain = bincode::encode_to_vec(&entries, config).unwrap();
+    assert_eq!(bytes.len(), plain.len() + entries.len() * 8);
+
+    let (Indexed(decoded), len): (Indexed<Vec<Entry>>, usize) =
+        bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, entries);
+    assert_eq!(len, bytes.len());
+
+    let (Indexed(borrowed), _): (Indexed<Vec<BorrowedEntry>>, usize) =
+        bincode::borrow_decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(borrowed[9].name, entries[9].name);
+
+    // Skipping only reads the last offset
+    let bytes = bincode::encode_to_vec((Indexed(&entries[..]), 7u8), config).unwrap();
+    let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), config);
+    bincode::de::skip::<Indexed<Vec<Entry>>>(&mut decoder).unwrap();
+    assert_eq!(u8::decode(&mut decoder).unwrap(), 7);
+
+    let empty: &[Entry] = &[];
+    let bytes = bincode::encode_to_vec(Indexed(empty), config).unwrap();
+    assert_eq!(bytes, [0]);
+    let (Indexed(decoded), _): (Indexed<Vec<Entry>>, usize) =
+        bincode::decode_from_slice(&bytes, config).unwrap();
+    assert!(decoded.is_empty());
+}
+
+fn check_indexed_slice<C: Config>(config: C) {
+    let entries = make_entries();
+    let bytes = bincode::encode_to_vec((Indexed(&entries[..]), 7u8), config).unwrap();
+
+    let ((index, number), len): ((IndexedSlice<BorrowedEntry>, u8), usize) =
+        bincode::borrow_decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(number, 7);
+    assert_eq!(len, bytes.len());
+    assert_eq!(index.len(), 10);
+
+    let entry = index.get(7, config).unwrap().unwrap();
+    assert_eq!(entry.id, 7);
+    assert_eq!(entry.name, entries[7].name);
+    assert!(index.get(10, config).unwrap().is_none());
+
+    let item = index.get_bytes(3, config).unwrap().unwrap();
+    assert_eq!(item, bincode::encode_to_vec(&entries[3], config).unwrap());
+
+    let ids: Vec<u32> = index.iter(config).map(|entry| entry.unwrap().id).collect();
+    assert_eq!(ids, (0..10).collect::<Vec<_>>());
+    assert_eq!(index.iter(config).nth(4).unwrap().unwrap().id, 4);
+
+    // An IndexedSlice is encoded the same way as the Indexed it was decoded from
+    let reencoded = bincode::encode_to_vec((index, number), config).unwrap();
+    assert_eq!(reencoded, bytes);
+}
+
+#[test]
+fn test_indexed_slice() {
+    check_indexed_slice(bincode::config::standard());
+    check_indexed_slice(bincode::config::standard().with_big_endian());
+    check_indexed_slice(bincode::config::legacy());
+}
+
+#[test]
+fn test_indexed_invalid_offsets() {
+    let config = bincode::config::standard();
+    let entries = make_entries();
+    let mut bytes = bincode::encode_to_vec(Indexed(&entries[..]), config).unwrap();
+
+    // Make the third offset smaller than the second
+    let third = 1 + 2 * 8;
+    bytes[third..third + 8].copy_from_slice(&0u64.to_le_bytes());
+
+    let result: Result<(Indexed<Vec<Entry>>, usize), _> =
+        bincode::decode_from_slice(&bytes, config);
+    assert!(matches!(
+        result,
+        Err(DecodeError::InvalidIndexedOffset {
+            index: 2,
+            offset: 0
+        })
+    ));
+
+    let (index, _): (IndexedSlice<BorrowedEntry>, usize) =
+        bincode::borrow_decode_from_slice(&bytes, config).unwrap();
+    assert!(index.get(1, config).is_ok());
+    assert!(matches!(
+        index.get(2, config),
+        Err(DecodeError::InvalidIndexedOffset {
+            index: 2,
+            offset: 0
+        })
+    ));
+
+    // An item has to use exactly the bytes between its offsets
+    let bytes = bincode::encode_to_vec(Indexed(&[(1u8, 2u8)][..]), config).unwrap();
+    let result: Result<(Indexed<Vec<u8>>, usize), _> = bincode::decode_from_slice(&bytes, config);
+    assert!(matches!(
+        result,
+        Err(DecodeError::TrailingBytes { remaining: 1 })
+    ));
+    let (index, _): (IndexedSlice<u8>, usize) =
+        bincode::borrow_decode_from_slice(&bytes, config).unwrap();
+    assert!(matches!(
+        index.get(0, config),
+        Err(DecodeError::TrailingBytes { remaining: 1 })
+    ));
+}
+
+#[test]
+fn test_indexed_max_depth() {
+    let config = bincode::config::standard();
+    let items = vec![vec![1u32]];
+    let bytes = bincode::encode_to_vec(Indexed(&items[..]), config).unwrap();
+
+    // The items are decoded at the depth inside the sequence
+    let result = bincode::decode_from_slice::<Indexed<Vec<Vec<u32>>>, _>(
+        &bytes,
+        config.with_max_depth::<1>(),
+    );
+    assert!(matches!(result, Err(DecodeError::DepthLimitExceeded)));
+    let (Indexed(decoded), _): (Indexed<Vec<Vec<u32>>>, usize) =
+        bincode::decode_from_slice(&bytes, config.with_max_depth::<2>()).unwrap();
+    assert_eq!(decoded, items);
+
+    let config = config.with_max_depth::<1>();
+    let (index, _): (IndexedSlice<Vec<u32>>, usize) =
+        bincode::borrow_decode_from_slice(&bytes, config).unwrap();
+    assert!(matches!(
+        index.get(0, config),
+        Err(DecodeError::DepthLimitExceeded)
+    ));
+}
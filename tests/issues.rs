@@ -1,4 +1,5 @@
 #![no_std]
+#![allow(dead_code)]
 
 #[path = "issues/issue_431.rs"]
 mod issue_431;
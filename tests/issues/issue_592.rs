@@ -6,3 +6,24 @@ use bincode::{Decode, Encode};
 pub enum TypeOfFile {
     Unknown = -1,
 }
+
+// The encoding of earlier versions, which used the position of the variant
+#[derive(Encode, Decode)]
+pub enum PositionalTypeOfFile {
+    #[bincode(tag = 0)]
+    Unknown = -1,
+}
+
+#[test]
+fn test() {
+    let bytes = bincode::encode_to_vec(TypeOfFile::Unknown, bincode::config::legacy()).unwrap();
+    // Negative discriminants are encoded like `-1i32 as u32`
+    assert_eq!(bytes, u32::MAX.to_le_bytes());
+    let (decoded, _): (TypeOfFile, usize) =
+        bincode::decode_from_slice(&bytes, bincode::config::legacy()).unwrap();
+    assert!(matches!(decoded, TypeOfFile::Unknown));
+
+    let bytes =
+        bincode::encode_to_vec(PositionalTypeOfFile::Unknown, bincode::config::legacy()).unwrap();
+    assert_eq!(bytes, 0u32.to_le_bytes());
+}
@@ -0,0 +1,158 @@
+#![cfg(all(feature = "derive", feature = "alloc"))]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use bincode::de::{LazyMap, LazySeq};
+use bincode::error::DecodeError;
+use bincode::{BorrowDecode, Encode, Opaque};
+
+#[derive(Encode, BorrowDecode, PartialEq, Debug)]
+struct Record<'a> {
+    id: u32,
+    name: &'a str,
+    tags: Vec<String>,
+}
+
+#[derive(Encode, PartialEq, Debug)]
+struct Log<'a> {
+    header: u32,
+    records: Vec<Record<'a>>,
+    index: BTreeMap<&'a str, u32>,
+    trailer: u32,
+}
+
+#[derive(Encode, BorrowDecode, Debug)]
+struct LazyLog<'a> {
+    header: u32,
+    records: LazySeq<'a, Record<'a>>,
+    index: LazyMap<'a, &'a str, u32>,
+    trailer: u32,
+}
+
+fn names() -> Vec<String> {
+    (0..100).map(|i| format!("record {}", i)).collect()
+}
+
+fn log(names: &[String]) -> Log<'_> {
+    Log {
+        header: 1,
+        records: names
+            .iter()
+            .enumerate()
+            .map(|(id, name)| Record {
+                id: id as u32,
+                name,
+                tags: vec![String::from("tag"); id % 3],
+            })
+            .collect(),
+        index: names
+            .iter()
+            .enumerate()
+            .map(|(id, name)| (name.as_str(), id as u32))
+            .collect(),
+        trailer: 2,
+    }
+}
+
+#[test]
+fn test_lazy_seq() {
+    let config = bincode::config::standard();
+    let names = names();
+    let log = log(&names);
+    let bytes = bincode::encode_to_vec(&log, config).unwrap();
+
+    let (lazy, len): (LazyLog, usize) = bincode::borrow_decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(lazy.header, 1);
+    assert_eq!(lazy.trailer, 2);
+    assert_eq!(lazy.records.len(), 100);
+    assert!(!lazy.records.is_empty());
+
+    assert_eq!(
+        lazy.records.get(42, config).unwrap().as_ref(),
+        Some(&log.records[42])
+    );
+    assert_eq!(lazy.records.get(100, config).unwrap(), None);
+
+    let mut iter = lazy.records.iter(config);
+    assert_eq!(iter.len(), 100);
+    assert_eq!(iter.next().unwrap().unwrap(), log.records[0]);
+    assert_eq!(iter.nth(9).unwrap().unwrap(), log.records[10]);
+    assert_eq!(iter.len(), 89);
+    let rest: Vec<Record> = iter.collect::<Result<_, _>>().unwrap();
+    assert_eq!(rest, &log.records[11..]);
+
+    // A lazy sequence is encoded the same as the original
+    assert_eq!(bincode::encode_to_vec(&lazy, config).unwrap(), bytes);
+}
+
+#[test]
+fn test_lazy_map() {
+    let config = bincode::config::standard();
+    let names = names();
+    let log = log(&names);
+    let bytes = bincode::encode_to_vec(&log, config).unwrap();
+
+    let (lazy, _): (LazyLog, usize) = bincode::borrow_decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(lazy.index.len(), 100);
+    assert_eq!(lazy.index.get("record 57", config).unwrap(), Some(57));
+    assert_eq!(lazy.index.get("record 100", config).unwrap(), None);
+
+    let index: BTreeMap<&str, u32> = lazy.index.iter(config).collect::<Result<_, _>>().unwrap();
+    assert_eq!(index, log.index);
+}
+
+#[test]
+fn test_lazy_errors() {
+    let config = bincode::config::standard();
+    let bytes = bincode::encode_to_vec(vec![1u32, 2, 3], config).unwrap();
+
+    // The sequence is checked to be complete when it is decoded
+    let result = bincode::borrow_decode_from_slice::<LazySeq<u32>, _>(&bytes[..2], config);
+    assert!(matches!(result, Err(DecodeError::UnexpectedEnd { .. })));
+
+    // Invalid items are only found when they are decoded
+    let bytes = bincode::encode_to_vec(vec![&b"a"[..], &[0xFF][..], &b"c"[..]], config).unwrap();
+    let (seq, _): (LazySeq<&str>, usize) =
+        bincode::borrow_decode_from_slice(&bytes, config).unwrap();
+    let mut iter = seq.iter(config);
+    assert_eq!(iter.next().unwrap().unwrap(), "a");
+    assert!(matches!(iter.next(), Some(Err(DecodeError::Utf8 { .. }))));
+    assert!(iter.next().is_none());
+
+    // The reader of an `Opaque` value also holds its data in memory
+    let bytes = bincode::encode_to_vec(Opaque(vec![1u32, 2, 3]), config).unwrap();
+    let (Opaque(seq), _): (Opaque<LazySeq<u32>>, usize) =
+        bincode::borrow_decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(seq.get(2, config).unwrap(), Some(3));
+
+    // The items are decoded at the depth of the sequence
+    fn decode_items<C: bincode::config::Config>(
+        bytes: &[u8],
+        config: C,
+    ) -> Result<Vec<Vec<Vec<u32>>>, DecodeError> {
+        let (seq, _): (LazySeq<Vec<Vec<u32>>>, usize) =
+            bincode::borrow_decode_from_slice(bytes, config)?;
+        seq.iter(config).collect()
+    }
+    let bytes = bincode::encode_to_vec(vec![vec![vec![1u32]]], config).unwrap();
+    assert!(matches!(
+        decode_items(&bytes, config.with_max_depth::<2>()),
+        Err(DecodeError::DepthLimitExceeded)
+    ));
+    assert!(decode_items(&bytes, config.with_max_depth::<3>()).is_ok());
+
+    // and count towards the byte limit together with the bytes before the sequence
+    let bytes = bincode::encode_to_vec((String::from("a"), vec![vec![0u64; 2]]), config).unwrap();
+    let decode_items = |limit| {
+        let config = config.with_runtime_limit(limit);
+        let ((_, seq), _): ((String, LazySeq<Vec<u64>>), usize) =
+            bincode::borrow_decode_from_slice(&bytes, config)?;
+        seq.iter(config).collect::<Result<Vec<_>, _>>()
+    };
+    assert!(matches!(decode_items(40), Err(DecodeError::LimitExceeded)));
+    assert!(decode_items(41).is_ok());
+}
@@ -0,0 +1,262 @@
+#![cfg(all(feature = "derive", feature = "alloc", target_endian = "little"))]
+
+extern crate alloc;
+
+use alloc::borrow::Cow;
+use bincode::config::Config;
+use bincode::de::{read::SliceReader, DecoderImpl};
+use bincode::error::DecodeError;
+use bincode::{BorrowDecode, Encode};
+
+/// A buffer that is aligned for every number type
+#[repr(align(16))]
+struct Aligned([u8; 256]);
+
+/// Encodes `value` into an aligned buffer, so that the encoded slice starts at `offset` bytes from the alignment
+fn encode_at<T: Encode, C: Config>(
+    buffer: &mut Aligned,
+    offset: usize,
+    value: T,
+    config: C,
+) -> &[u8] {
+    let len = bincode::encode_into_slice(value, &mut buffer.0[offset..], config).unwrap();
+    &buffer.0[offset..offset + len]
+}
+
+#[derive(Encode, BorrowDecode, PartialEq, Debug)]
+struct Tensor<'a> {
+    shape: &'a [u32],
+    data: Cow<'a, [f32]>,
+    name: &'a str,
+}
+
+#[test]
+fn test_borrow_number_slice() {
+    let config = bincode::config::legacy();
+    let mut buffer = Aligned([0; 256]);
+    let numbers = [1u32, 2, 3, u32::MAX];
+    let bytes = encode_at(&mut buffer, 0, &numbers[..], config);
+
+    let (slice, len): (&[u32], usize) = bincode::borrow_decode_from_slice(bytes, config).unwrap();
+    assert_eq!(slice, numbers);
+    assert_eq!(len, bytes.len());
+    // The numbers are borrowed from the input, after the length
+    assert_eq!(slice.as_ptr().cast::<u8>(), bytes[8..].as_ptr());
+
+    let (cow, _): (Cow<[u32]>, usize) = bincode::borrow_decode_from_slice(bytes, config).unwrap();
+    assert!(matches!(cow, Cow::Borrowed(_)));
+    assert_eq!(cow, &numbers[..]);
+
+    let floats = [0.5f64, -1.0, f64::MAX];
+    let bytes = encode_at(&mut buffer, 0, &floats[..], config);
+    let (slice, _): (&[f64], usize) = bincode::borrow_decode_from_slice(bytes, config).unwrap();
+    assert_eq!(slice, floats);
+
+    let wide = [u128::MAX, 5];
+    let bytes = encode_at(&mut buffer, 8, &wide[..], config);
+    let (slice, _): (&[u128], usize) = bincode::borrow_decode_from_slice(bytes, config).unwrap();
+    assert_eq!(slice, wide);
+
+    // Floats and `i8` have a fixed size with `Varint` encoding as well
+    let config = bincode::config::standard();
+    let bytes = encode_at(&mut buffer, 3, &[1.5f32, 2.5][..], config);
+    let (slice, _): (&[f32], usize) = bincode::borrow_decode_from_slice(bytes, config).unwrap();
+    assert_eq!(slice, [1.5, 2.5]);
+    let bytes = encode_at(&mut buffer, 0, &[-1i8, 1][..], config);
+    let (slice, _): (&[i8], usize) = bincode::borrow_decode_from_slice(bytes, config).unwrap();
+    assert_eq!(slice, [-1, 1]);
+}
+
+#[test]
+fn test_number_slice_fallback() {
+    let numbers = [1u32, 2, 300, u32::MAX];
+    let mut buffer = Aligned([0; 256]);
+
+    // Misaligned
+    let config = bincode::config::legacy();
+    let bytes = encode_at(&mut buffer, 1, &numbers[..], config);
+    let result = bincode::borrow_decode_from_slice::<&[u32], _>(bytes, config);
+    assert!(matches!(
+        result,
+        Err(DecodeError::CannotBorrowSlice { type_name: "u32" })
+    ));
+    let (cow, len): (Cow<[u32]>, usize) = bincode::borrow_decode_from_slice(bytes, config).unwrap();
+    assert!(matches!(cow, Cow::Owned(_)));
+    assert_eq!(cow, &numbers[..]);
+    assert_eq!(len, bytes.len());
+
+    // Varint encoding
+    let config = bincode::config::standard();
+    let bytes = encode_at(&mut buffer, 0, &numbers[..], config);
+    let result = bincode::borrow_decode_from_slice::<&[u32], _>(bytes, config);
+    assert!(matches!(result, Err(DecodeError::CannotBorrowSlice { .. })));
+    let (cow, len): (Cow<[u32]>, usize) = bincode::borrow_decode_from_slice(bytes, config).unwrap();
+    assert_eq!(cow, &numbers[..]);
+    assert_eq!(len, bytes.len());
+
+    // Different endianness
+    let config = bincode::config::legacy().with_big_endian();
+    let bytes = encode_at(&mut buffer, 0, &numbers[..], config);
+    let result = bincode::borrow_decode_from_slice::<&[u32], _>(bytes, config);
+    assert!(matches!(result, Err(DecodeError::CannotBorrowSlice { .. })));
+    let (cow, _): (Cow<[u32]>, usize) = bincode::borrow_decode_from_slice(bytes, config).unwrap();
+    assert_eq!(cow, &numbers[..]);
+
+    // The data still has to be complete
+    let config = bincode::config::legacy();
+    let bytes = encode_at(&mut buffer, 0, &numbers[..], config);
+    let result = bincode::borrow_decode_from_slice::<Cow<[u32]>, _>(&bytes[..10], config);
+    assert!(matches!(result, Err(DecodeError::UnexpectedEnd { .. })));
+}
+
+#[test]
+fn test_borrow_number_slice_derived() {
+    let config = bincode::config::legacy();
+    let tensor = Tensor {
+        shape: &[2, 2],
+        data: Cow::Owned(vec![1.0, 2.0, 3.0, 4.0]),
+        name: "weights",
+    };
+    let mut buffer = Aligned([0; 256]);
+    let bytes = encode_at(&mut buffer, 0, (&tensor, 7u8), config);
+
+    let ((decoded, number), _): ((Tensor, u8), usize) =
+        bincode::borrow_decode_from_slice(bytes, config).unwrap();
+    assert_eq!(decoded, tensor);
+    assert!(matches!(decoded.data, Cow::Borrowed(_)));
+    assert_eq!(number, 7);
+
+    // Skipping does not need the numbers to be aligned
+    let bytes = encode_at(&mut buffer, 1, (&tensor, 9u8), config);
+    let mut decoder = DecoderImpl::new(SliceReader::new(bytes), config);
+    Tensor::borrow_skip(&mut decoder).unwrap();
+    assert_eq!(u8::borrow_decode(&mut decoder).unwrap(), 9);
+}
+
+/// Checks that a vec and an array of numbers are encoded the same as with the element by element encoding of `Wrapping<T>`
+fn check_bulk<T, const N: usize, C>(items: [T; N], config: C)
+where
+    T: Encode + bincode::Decode + for<'de> BorrowDecode<'de> + Copy + PartialEq + core::fmt::Debug,
+    core::num::Wrapping<T>: Encode,
+    C: Config,
+{
+    let expected = bincode::encode_to_vec(items.map(core::num::Wrapping), config).unwrap();
+    assert_eq!(bincode::encode_to_vec(items, config).unwrap(), expected);
+    let (decoded, _): ([T; N], usize) = bincode::decode_from_slice(&expected, config).unwrap();
+    assert_eq!(decoded, items);
+
+    let vec = items.to_vec();
+    let expected = bincode::encode_to_vec(
+        vec.iter()
+            .copied()
+            .map(core::num::Wrapping)
+            .collect::<Vec<_>>(),
+        config,
+    )
+    .unwrap();
+    assert_eq!(bincode::encode_to_vec(&vec, config).unwrap(), expected);
+    let (decoded, len): (Vec<T>, usize) = bincode::decode_from_slice(&expected, config).unwrap();
+    assert_eq!(decoded, vec);
+    assert_eq!(len, expected.len());
+    let (decoded, _): (Vec<T>, usize) =
+        bincode::borrow_decode_from_slice(&expected, config).unwrap();
+    assert_eq!(decoded, vec);
+
+    let mut bytes = expected;
+    bytes.push(7);
+    let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), config);
+    bincode::de::skip::<Vec<T>>(&mut decoder).unwrap();
+    assert_eq!(u8::borrow_decode(&mut decoder).unwrap(), 7);
+}
+
+fn check_bulk_all<C: Config>(config: C) {
+    check_bulk([1u16, 0x1234, u16::MAX], config);
+    check_bulk([1u32, 0x1234_5678, u32::MAX], config);
+    check_bulk([1u64, 0x1234_5678_9abc_def0, u64::MAX], config);
+    check_bulk([1u128, u128::MAX / 3, u128::MAX], config);
+    check_bulk([-1i8, 0, i8::MIN], config);
+    check_bulk([-1i16, 0, i16::MIN], config);
+    check_bulk([-1i32, 0, i32::MIN], config);
+    check_bulk([-1i64, 0, i64::MIN], config);
+    check_bulk([-1i128, 0, i128::MIN], config);
+    check_bulk([0.5f32, -1.0, f32::MAX], config);
+    check_bulk([0.5f64, -1.0, f64::MIN_POSITIVE], config);
+    // Bigger than the buffer that is used to reverse the bytes
+    let mut items = [0u64; 300];
+    items
+        .iter_mut()
+        .enumerate()
+        .for_each(|(i, item)| *item = i as u64 * 0x0101_0101);
+    check_bulk(items, config);
+}
+
+#[test]
+fn test_bulk_numbers() {
+    check_bulk_all(bincode::config::standard());
+    check_bulk_all(bincode::config::standard().with_big_endian());
+    check_bulk_all(bincode::config::legacy());
+    check_bulk_all(bincode::config::legacy().with_big_endian());
+
+    // The items are still limited by the config
+    let config = bincode::config::legacy().with_limit::<64>();
+    let bytes = bincode::encode_to_vec(vec![0u32; 100], bincode::config::legacy()).unwrap();
+    let result = bincode::decode_from_slice::<Vec<u32>, _>(&bytes, config);
+    assert!(matches!(result, Err(DecodeError::LimitExceeded)));
+    let result = bincode::decode_from_slice::<Vec<u32>, _>(&bytes[..20], bincode::config::legacy());
+    assert!(matches!(result, Err(DecodeError::UnexpectedEnd { .. })));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_batch_varint() {
+    let config = bincode::config::standard();
+    let values: Vec<u64> = (0..1000u64)
+        .map(|i| match i % 13 {
+            0 => u64::MAX - i,
+            1 => 70_000 + i,
+            2 => 300 + i,
+            _ => i % 250,
+        })
+        .collect();
+    let small: Vec<u32> = values.iter().map(|&v| v as u32).collect();
+
+    for bytes in [
+        bincode::encode_to_vec(&values, config).unwrap(),
+        bincode::encode_to_vec(&small, config).unwrap(),
+    ] {
+        let expected: Vec<u64> =
+            bincode::decode_from_slice::<Vec<core::num::Wrapping<u64>>, _>(&bytes, config)
+                .unwrap()
+                .0
+                .into_iter()
+                .map(|v| v.0)
+                .collect();
+        let (decoded, len): (Vec<u64>, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+        assert_eq!(decoded, expected);
+        assert_eq!(len, bytes.len());
+
+        // A reader that can only peek into a small buffer
+        let reader = std::io::BufReader::with_capacity(3, &bytes[..]);
+        let decoded: Vec<u64> = bincode::decode_from_reader(reader, config).unwrap();
+        assert_eq!(decoded, expected);
+
+        let result = bincode::decode_from_slice::<Vec<u64>, _>(&bytes[..bytes.len() - 1], config);
+        assert!(matches!(result, Err(DecodeError::UnexpectedEnd { .. })));
+    }
+
+    let bytes = bincode::encode_to_vec(&small, config).unwrap();
+    let (decoded, _): (Vec<u32>, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, small);
+
+    let bytes = bincode::encode_to_vec(&values, config).unwrap();
+    let result = bincode::decode_from_slice::<Vec<u32>, _>(&bytes, config);
+    assert!(matches!(
+        result,
+        Err(DecodeError::InvalidIntegerType { .. })
+    ));
+
+    let array: [u64; 20] = core::array::from_fn(|i| values[i]);
+    let bytes = bincode::encode_to_vec(array, config).unwrap();
+    let (decoded, _): ([u64; 20], usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, array);
+}
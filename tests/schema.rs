@@ -0,0 +1,293 @@
+#![cfg(all(feature = "derive", feature = "std"))]
+#![allow(dead_code)]
+
+use bincode::config::EnumTagType;
+use bincode::schema::{Field, Schema, Type};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::string::String;
+use std::vec::Vec;
+
+/// Renders a schema as a compact string. Structs and enums that are already being rendered are only printed by name.
+fn describe(ty: Type) -> String {
+    fn fields(fields: &[Field], seen: &mut Vec<&'static str>) -> String {
+        let fields: Vec<String> = fields
+            .iter()
+            .map(|field| match field.name {
+                Some(name) => format!("{}: {}", name, inner((field.schema)(), seen)),
+                None => inner((field.schema)(), seen),
+            })
+            .collect();
+        fields.join(", ")
+    }
+
+    fn inner(ty: Type, seen: &mut Vec<&'static str>) -> String {
+        match ty {
+            Type::Option(t) => format!("Option<{}>", inner(t(), seen)),
+            Type::Seq(t) => format!("[{}]", inner(t(), seen)),
+            Type::Array { element, len } => format!("[{}; {}]", inner(element(), seen), len),
+            Type::Map { key, value } => {
+                format!("{{{}: {}}}", inner(key(), seen), inner(value(), seen))
+            }
+            Type::Tuple(t) => {
+                let t: Vec<String> = t.iter().map(|t| inner(t(), seen)).collect();
+                format!("({})", t.join(", "))
+            }
+            Type::Struct { name, .. } | Type::Enum { name, .. } if seen.contains(&name) => {
+                name.to_string()
+            }
+            Type::Struct {
+                name, fields: f, ..
+            } => {
+                seen.push(name);
+                let result = format!("{} {{ {} }}", name, fields(f, seen));
+                seen.pop();
+                result
+            }
+            Type::Enum { name, variants, .. } => {
+                seen.push(name);
+                let variants: Vec<String> = variants
+                    .iter()
+                    .map(|v| format!("{} = {} {{ {} }}", v.name, v.index, fields(v.fields, seen)))
+                    .collect();
+                seen.pop();
+                format!("{} {{ {} }}", name, variants.join(", "))
+            }
+            other => format!("{:?}", other),
+        }
+    }
+
+    inner(ty, &mut Vec::new())
+}
+
+#[derive(bincode::Schema)]
+struct Unit;
+
+#[derive(bincode::Schema)]
+struct Tuple(u8, String);
+
+#[derive(bincode::Schema)]
+struct Generic<'a, T> {
+    value: T,
+    name: Cow<'a, str>,
+    bytes: &'a [u8],
+}
+
+#[derive(bincode::Schema)]
+struct Skipped {
+    value: u8,
+    #[bincode(skip)]
+    cache: Vec<u8>,
+}
+
+#[derive(bincode::Schema)]
+enum Tree {
+    Leaf(u32),
+    Node { left: Box<Tree>, right: Box<Tree> },
+    Empty,
+}
+
+#[derive(bincode::Schema)]
+enum Small {
+    A,
+    B(u8),
+}
+
+mod small_tag {
+    #[derive(bincode::Schema)]
+    #[bincode(tag_type = "u8")]
+    pub enum Small {
+        A,
+        B(u8),
+    }
+}
+
+#[test]
+fn test_derived_schema() {
+    assert_eq!(describe(Unit::SCHEMA), "Unit {  }");
+    assert_eq!(describe(Tuple::SCHEMA), "Tuple { U8, Str }");
+    assert_eq!(describe(Skipped::SCHEMA), "Skipped { value: U8 }");
+    assert_eq!(
+        describe(Generic::<'static, Option<i64>>::SCHEMA),
+        "Generic { value: Option<I64>, name: Str, bytes: [U8] }"
+    );
+    assert_eq!(
+        describe(Tree::SCHEMA),
+        "Tree { Leaf = 0 { U32 }, Node = 1 { left: Tree, right: Tree }, Empty = 2 {  } }"
+    );
+
+    // The tag type is only set if the enum overrides the tag type of the config
+    assert!(matches!(Small::SCHEMA, Type::Enum { tag_type: None, .. }));
+    assert!(matches!(
+        small_tag::Small::SCHEMA,
+        Type::Enum {
+            tag_type: Some(EnumTagType::U8),
+            ..
+        }
+    ));
+    assert!(matches!(
+        <Result<u8, u8>>::SCHEMA,
+        Type::Enum {
+            tag_type: Some(EnumTagType::U32),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_builtin_schema() {
+    assert_eq!(describe(<()>::SCHEMA), "Unit");
+    assert_eq!(describe(<[u16; 3]>::SCHEMA), "[U16; 3]");
+    assert_eq!(describe(<(bool, char, f64)>::SCHEMA), "(Bool, Char, F64)");
+    assert_eq!(describe(<Vec<Option<usize>>>::SCHEMA), "[Option<Usize>]");
+    assert_eq!(describe(<VecDeque<&str>>::SCHEMA), "[Str]");
+    assert_eq!(describe(<BTreeMap<u8, String>>::SCHEMA), "{U8: Str}");
+    assert_eq!(describe(<HashMap<i32, Vec<u8>>>::SCHEMA), "{I32: [U8]}");
+    assert_eq!(describe(<HashSet<i128>>::SCHEMA), "[I128]");
+    assert_eq!(
+        describe(<Result<u8, String>>::SCHEMA),
+        "Result { Ok = 0 { U8 }, Err = 1 { Str } }"
+    );
+    assert_eq!(
+        describe(<std::time::Duration>::SCHEMA),
+        "Duration { secs: U64, nanos: U32 }"
+    );
+    assert_eq!(
+        describe(<std::net::SocketAddr>::SCHEMA),
+        "SocketAddr { V4 = 0 { SocketAddrV4 { ip: [U8; 4], port: U16 } }, V6 = 1 { SocketAddrV6 { ip: [U8; 16], port: U16 } } }"
+    );
+    assert_eq!(
+        describe(<std::sync::Mutex<std::rc::Rc<std::num::NonZeroU32>>>::SCHEMA),
+        "U32"
+    );
+}
+
+mod v1 {
+    #[derive(bincode::Encode, bincode::Decode, bincode::Schema, PartialEq, Debug)]
+    pub struct Message {
+        pub id: u32,
+        pub text: String,
+    }
+}
+
+mod v2 {
+    // Renamed fields, same layout
+    #[derive(bincode::Encode, bincode::Decode, bincode::Schema, PartialEq, Debug)]
+    pub struct Message {
+        pub message_id: u32,
+        pub content: String,
+    }
+}
+
+mod v3 {
+    // Reordered fields
+    #[derive(bincode::Encode, bincode::Decode, bincode::Schema, PartialEq, Debug)]
+    pub struct Message {
+        pub text: String,
+        pub id: u32,
+    }
+}
+
+#[derive(bincode::Schema)]
+struct Wrapper<T> {
+    inner: T,
+}
+
+// Structs with the same name in different modules
+mod numbers {
+    #[derive(bincode::Schema)]
+    pub struct Node {
+        pub inner: inner::Node,
+    }
+
+    mod inner {
+        #[derive(bincode::Schema)]
+        pub struct Node {
+            pub value: u32,
+        }
+    }
+}
+
+mod strings {
+    #[derive(bincode::Schema)]
+    pub struct Node {
+        pub inner: inner::Node,
+    }
+
+    mod inner {
+        #[derive(bincode::Schema)]
+        pub struct Node {
+            pub value: String,
+        }
+    }
+}
+
+#[test]
+fn test_fingerprint() {
+    use bincode::schema::fingerprint;
+
+    // The fingerprint must not change between builds or releases, this is FNV-1a of the tag of `u32`
+    assert_eq!(fingerprint::<u32>(), 0xaf63_b94c_8601_b113);
+    assert_eq!(fingerprint::<v1::Message>(), fingerprint::<v2::Message>());
+    assert_ne!(fingerprint::<v1::Message>(), fingerprint::<v3::Message>());
+    assert_ne!(fingerprint::<v1::Message>(), fingerprint::<(u32, String)>());
+    assert_ne!(fingerprint::<[u8; 4]>(), fingerprint::<[u8; 5]>());
+    assert_ne!(fingerprint::<Vec<u8>>(), fingerprint::<Option<u8>>());
+    assert_ne!(
+        fingerprint::<Result<u8, u16>>(),
+        fingerprint::<Result<u16, u8>>()
+    );
+    assert_ne!(fingerprint::<Small>(), fingerprint::<small_tag::Small>());
+    // Nested types with the same name are not mistaken for recursion
+    assert_ne!(
+        fingerprint::<Wrapper<Wrapper<u32>>>(),
+        fingerprint::<Wrapper<Wrapper<String>>>()
+    );
+    assert_ne!(
+        fingerprint::<numbers::Node>(),
+        fingerprint::<strings::Node>()
+    );
+    // Recursive types terminate
+    assert_ne!(fingerprint::<Tree>(), fingerprint::<Option<Tree>>());
+}
+
+#[test]
+fn test_encode_with_fingerprint() {
+    use bincode::error::DecodeError;
+
+    let config = bincode::config::standard();
+    let message = v1::Message {
+        id: 5,
+        text: String::from("Hello"),
+    };
+
+    let mut buffer = [0u8; 32];
+    let len = bincode::encode_with_fingerprint(&message, &mut buffer, config).unwrap();
+    // 8 bytes fingerprint, 1 byte id, 1 byte length, 5 bytes text
+    assert_eq!(len, 15);
+    assert_eq!(
+        buffer[..8],
+        bincode::schema::fingerprint::<v1::Message>().to_le_bytes()
+    );
+
+    let (decoded, read): (v1::Message, usize) =
+        bincode::decode_with_fingerprint(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, message);
+    assert_eq!(read, len);
+
+    let (decoded, _): (v2::Message, usize) =
+        bincode::decode_with_fingerprint(&buffer[..len], config).unwrap();
+    assert_eq!(decoded.message_id, 5);
+
+    let result = bincode::decode_with_fingerprint::<v3::Message, _>(&buffer[..len], config);
+    match result {
+        Err(DecodeError::FingerprintMismatch { expected, found }) => {
+            assert_eq!(expected, bincode::schema::fingerprint::<v3::Message>());
+            assert_eq!(found, bincode::schema::fingerprint::<v1::Message>());
+        }
+        other => panic!("Expected FingerprintMismatch, got {:?}", other),
+    }
+
+    let result = bincode::decode_with_fingerprint::<v1::Message, _>(&buffer[..4], config);
+    assert!(matches!(result, Err(DecodeError::UnexpectedEnd { .. })));
+}
@@ -142,6 +142,56 @@ fn test_serialize_deserialize_owned_data() {
     assert_eq!(len, 13);
 }
 
+#[test]
+fn test_serde_trailing_bytes() {
+    let config = bincode::config::standard();
+    let input = SerdeWithOwnedData {
+        a: 5,
+        b: 0,
+        str: String::from("Hello"),
+    };
+    let mut bytes = bincode::serde::encode_to_vec(&input, config).unwrap();
+    bytes.push(0);
+
+    let (output, len): (SerdeWithOwnedData, usize) =
+        bincode::serde::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(output, input);
+    assert_eq!(len, bytes.len() - 1);
+
+    let result = bincode::serde::decode_from_slice_exact::<SerdeWithOwnedData, _>(&bytes, config);
+    assert!(matches!(
+        result,
+        Err(bincode::error::DecodeError::TrailingBytes { remaining: 1 })
+    ));
+    let result = bincode::serde::decode_from_slice::<SerdeWithOwnedData, _>(
+        &bytes,
+        config.with_reject_trailing_bytes(),
+    );
+    assert!(matches!(
+        result,
+        Err(bincode::error::DecodeError::TrailingBytes { remaining: 1 })
+    ));
+    let output: SerdeWithOwnedData =
+        bincode::serde::decode_from_slice_exact(&bytes[..bytes.len() - 1], config).unwrap();
+    assert_eq!(output, input);
+
+    let result = bincode::serde::borrow_decode_from_slice_exact::<&str, _>(&[1, b'a', 0], config);
+    assert!(matches!(
+        result,
+        Err(bincode::error::DecodeError::TrailingBytes { remaining: 1 })
+    ));
+    let result = bincode::serde::borrow_decode_from_slice::<&str, _>(
+        &[1, b'a', 0],
+        config.with_reject_trailing_bytes(),
+    );
+    assert!(matches!(
+        result,
+        Err(bincode::error::DecodeError::TrailingBytes { remaining: 1 })
+    ));
+    let output: &str = bincode::serde::borrow_decode_from_slice_exact(&[1, b'a'], config).unwrap();
+    assert_eq!(output, "a");
+}
+
 #[cfg(feature = "derive")]
 mod derive {
     use bincode::{
@@ -0,0 +1,186 @@
+#![cfg(all(feature = "derive", feature = "std"))]
+
+use bincode::de::{read::SliceReader, DecoderImpl};
+use bincode::error::DecodeError;
+use bincode::{Decode, Encode, Opaque};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+#[derive(Encode, Decode, PartialEq, Debug)]
+struct LogLine {
+    level: u8,
+    message: String,
+    tags: Vec<(String, Option<u64>)>,
+    #[bincode(skip)]
+    cache: Vec<u8>,
+}
+
+#[derive(Encode, Decode, PartialEq, Debug)]
+enum Event {
+    Started,
+    Line(LogLine),
+    Stopped { code: i32, reason: Box<str> },
+}
+
+#[derive(Encode, Decode, PartialEq, Debug)]
+#[bincode(versioned)]
+struct Versioned {
+    id: u32,
+    #[bincode(version = 1)]
+    name: String,
+    #[bincode(version = 2)]
+    extra: HashMap<u8, String>,
+}
+
+#[derive(Encode, Decode, PartialEq, Debug)]
+#[bincode(length_prefixed)]
+enum Prefixed {
+    A(String),
+    #[bincode(other)]
+    Unknown,
+}
+
+/// Skips a `T` in the encoding of `(value, u32::MAX)`, and checks that the `u32` is read correctly afterwards
+fn check_skip<T: Encode + Decode>(value: T) {
+    let config = bincode::config::standard();
+    let bytes = bincode::encode_to_vec((value, u32::MAX), config).unwrap();
+
+    let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), config);
+    bincode::de::skip::<T>(&mut decoder).unwrap();
+    assert_eq!(u32::decode(&mut decoder).unwrap(), u32::MAX);
+
+    // Also check a reader that can only peek a single byte
+    let reader = std::io::BufReader::with_capacity(1, &bytes[..]);
+    let mut decoder = DecoderImpl::new(reader, config);
+    bincode::de::skip::<T>(&mut decoder).unwrap();
+    assert_eq!(u32::decode(&mut decoder).unwrap(), u32::MAX);
+}
+
+fn log_line() -> LogLine {
+    LogLine {
+        level: 3,
+        message: String::from("disk almost full"),
+        tags: vec![
+            (String::from("host"), None),
+            (String::from("pid"), Some(1234)),
+        ],
+        cache: Vec::new(),
+    }
+}
+
+#[test]
+fn test_skip_builtin() {
+    check_skip(5u8);
+    check_skip(300u64);
+    check_skip(String::from("Hello world"));
+    check_skip(vec![1u8, 2, 3]);
+    check_skip(vec![String::from("a"), String::from("b")]);
+    check_skip([String::from("a"), String::from("b")]);
+    check_skip(Some(vec![1u32, 2, 3]));
+    check_skip(None::<String>);
+    check_skip(Ok::<String, u8>(String::from("ok")));
+    check_skip(Err::<String, u8>(3));
+    check_skip((1u8, String::from("a"), vec![0.5f32]));
+    check_skip(Box::new(String::from("boxed")));
+    check_skip(std::rc::Rc::<str>::from("rc"));
+    check_skip(BTreeMap::from([
+        (1u32, String::from("a")),
+        (2, String::from("b")),
+    ]));
+    check_skip(HashMap::from([(String::from("a"), vec![1u16])]));
+    check_skip(HashSet::from([String::from("a")]));
+    check_skip(std::path::PathBuf::from("/tmp"));
+}
+
+#[test]
+fn test_skip_derived() {
+    check_skip(log_line());
+    check_skip(Event::Started);
+    check_skip(Event::Line(log_line()));
+    check_skip(Event::Stopped {
+        code: -1,
+        reason: "crashed".into(),
+    });
+    check_skip(Versioned {
+        id: 1,
+        name: String::from("name"),
+        extra: HashMap::from([(1, String::from("one"))]),
+    });
+    check_skip(Prefixed::A(String::from("a")));
+
+    let config = bincode::config::standard();
+    let bytes = bincode::encode_to_vec(5u32, config).unwrap();
+    let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), config);
+    assert!(matches!(
+        bincode::de::skip::<Event>(&mut decoder),
+        Err(DecodeError::UnexpectedVariant { found: 5, .. })
+    ));
+}
+
+#[test]
+fn test_skip_does_not_validate() {
+    let config = bincode::config::standard();
+    let bytes = bincode::encode_to_vec((&[0xFFu8, 0xFE][..], 1u8), config).unwrap();
+    let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), config);
+    bincode::de::skip::<String>(&mut decoder).unwrap();
+    assert_eq!(u8::decode(&mut decoder).unwrap(), 1);
+
+    // Reading past the end is still an error
+    let mut decoder = DecoderImpl::new(SliceReader::new(&bytes[..2]), config);
+    assert!(matches!(
+        bincode::de::skip::<String>(&mut decoder),
+        Err(DecodeError::UnexpectedEnd { .. })
+    ));
+}
+
+#[test]
+fn test_opaque() {
+    let config = bincode::config::standard();
+    let line = log_line();
+    let bytes = bincode::encode_to_vec((Opaque(&line), 7u8), config).unwrap();
+
+    // The length is the size of the encoded value
+    let encoded_line = bincode::encode_to_vec(&line, config).unwrap();
+    assert_eq!(bytes[0] as usize, encoded_line.len());
+    assert_eq!(&bytes[1..bytes.len() - 1], &encoded_line[..]);
+
+    let ((Opaque(decoded), number), len): ((Opaque<LogLine>, u8), usize) =
+        bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, line);
+    assert_eq!(number, 7);
+    assert_eq!(len, bytes.len());
+
+    // The type of an opaque value does not have to be known to skip it
+    let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), config);
+    bincode::de::skip::<Opaque<()>>(&mut decoder).unwrap();
+    assert_eq!(u8::decode(&mut decoder).unwrap(), 7);
+    let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), config);
+    bincode::de::skip::<Vec<u8>>(&mut decoder).unwrap();
+    assert_eq!(u8::decode(&mut decoder).unwrap(), 7);
+
+    let bytes = bincode::encode_to_vec((Opaque("borrowed"), 7u8), config).unwrap();
+    let ((Opaque(message), _), _): ((Opaque<&str>, u8), usize) =
+        bincode::borrow_decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(message, "borrowed");
+
+    // The value has to use exactly the encoded length
+    let bytes = bincode::encode_to_vec(Opaque((1u8, 2u8)), config).unwrap();
+    let result: Result<(Opaque<u8>, usize), _> = bincode::decode_from_slice(&bytes, config);
+    assert!(matches!(
+        result,
+        Err(DecodeError::TrailingBytes { remaining: 1 })
+    ));
+    let result = bincode::decode_from_slice::<Opaque<(u8, u8, u8)>, _>(&bytes, config);
+    assert!(matches!(result, Err(DecodeError::UnexpectedEnd { .. })));
+
+    // The value inside an opaque value counts towards the max depth
+    let value = vec![Opaque(vec![vec![1u32]])];
+    let bytes = bincode::encode_to_vec(&value, config).unwrap();
+    let result = bincode::decode_from_slice::<Vec<Opaque<Vec<Vec<u32>>>>, _>(
+        &bytes,
+        config.with_max_depth::<2>(),
+    );
+    assert!(matches!(result, Err(DecodeError::DepthLimitExceeded)));
+    let (decoded, _): (Vec<Opaque<Vec<Vec<u32>>>>, usize) =
+        bincode::decode_from_slice(&bytes, config.with_max_depth::<3>()).unwrap();
+    assert_eq!(decoded, value);
+}
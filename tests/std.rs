@@ -127,6 +127,8 @@ fn test_std_commons() {
     let mut buffer = [0u8; 1024];
 
     // &CStr
+    // `c""` literals need Rust 1.77
+    #[allow(clippy::manual_c_str_literals)]
     let cstr = CStr::from_bytes_with_nul(b"Hello world\0").unwrap();
     let len = bincode::encode_into_slice(cstr, &mut buffer, config).unwrap();
     let (decoded, len): (CString, usize) =
@@ -200,3 +202,58 @@ fn test_decode_borrow_str_in_array() {
     .unwrap();
     assert_eq!(strs, ["abc", "def", "ghi"]);
 }
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_decode_with_count() {
+    let config = bincode::config::standard();
+    let values = (vec![1u64, 300, u64::MAX], String::from("abc"));
+    let mut bytes = bincode::encode_to_vec(&values, config).unwrap();
+    let len = bytes.len();
+    bytes.extend_from_slice(&[7, 8]);
+
+    let mut stream = Cursor::new(&bytes);
+    let (decoded, read): ((Vec<u64>, String), usize) =
+        bincode::decode_from_std_read_with_count(&mut stream, config).unwrap();
+    assert_eq!(decoded, values);
+    assert_eq!(read, len);
+    assert_eq!(stream.position(), len as u64);
+
+    // A reader that only returns a few bytes at a time
+    let reader = std::io::BufReader::with_capacity(3, &bytes[..]);
+    let (_, read): ((Vec<u64>, String), usize) =
+        bincode::decode_from_reader_with_count(reader, config).unwrap();
+    assert_eq!(read, len);
+
+    // The offset of an error is the position of the reader
+    let mut bytes = bincode::encode_to_vec(&values, config).unwrap();
+    let last = bytes.len() - 1;
+    bytes[last] = 0xff;
+    let error =
+        bincode::decode_from_std_read::<(Vec<u64>, String), _, _>(&mut Cursor::new(&bytes), config)
+            .unwrap_err();
+    assert!(matches!(error, DecodeError::Utf8 { .. }));
+    let error = bincode::decode_from_std_read_with_context::<(Vec<u64>, String), _, _>(
+        &mut Cursor::new(&bytes),
+        config,
+    )
+    .unwrap_err();
+    assert!(matches!(error.error(), DecodeError::Utf8 { .. }));
+    assert!(error.path().is_empty());
+    assert_eq!(error.offset(), bytes.len());
+
+    #[derive(bincode::Decode, Debug)]
+    struct Named {
+        _numbers: Vec<u64>,
+        _name: String,
+    }
+    let error =
+        bincode::decode_from_std_read::<Named, _, _>(&mut Cursor::new(&bytes), config).unwrap_err();
+    assert!(matches!(error, DecodeError::Utf8 { .. }));
+    let error =
+        bincode::decode_from_std_read_with_context::<Named, _, _>(&mut Cursor::new(&bytes), config)
+            .unwrap_err();
+    assert!(matches!(error.error(), DecodeError::Utf8 { .. }));
+    assert_eq!(error.path().len(), 1);
+    assert_eq!(error.offset(), bytes.len());
+}
@@ -0,0 +1,173 @@
+#![cfg(all(feature = "tokio", feature = "derive"))]
+
+use bincode::error::{DecodeError, EncodeError};
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct Message {
+    id: u64,
+    name: String,
+    payload: Vec<u8>,
+}
+
+#[tokio::test]
+async fn test_async_roundtrip() {
+    let config = bincode::config::standard();
+    let message = Message {
+        id: 1234,
+        name: String::from("Hello world"),
+        payload: vec![1, 2, 3, 4, 5],
+    };
+
+    let mut buffer = Vec::new();
+    let bytes_written = bincode::encode_into_async_write(&message, &mut buffer, config)
+        .await
+        .unwrap();
+    assert_eq!(bytes_written, buffer.len());
+    assert_eq!(buffer, bincode::encode_to_vec(&message, config).unwrap());
+
+    let decoded: Message = bincode::decode_from_async_read(&mut buffer.as_slice(), config)
+        .await
+        .unwrap();
+    assert_eq!(decoded, message);
+}
+
+/// Returns at most 100 bytes per read, and is not ready once every `pending_every` polls
+struct SlowReader<'a> {
+    bytes: &'a [u8],
+    polls: usize,
+    pending_every: usize,
+}
+
+impl<'a> SlowReader<'a> {
+    fn new(bytes: &'a [u8], pending_every: usize) -> Self {
+        Self {
+            bytes,
+            polls: 0,
+            pending_every,
+        }
+    }
+}
+
+impl tokio::io::AsyncRead for SlowReader<'_> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        self.polls += 1;
+        if self.polls == self.pending_every {
+            self.polls = 0;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        let len = self.bytes.len().min(buf.remaining()).min(100);
+        buf.put_slice(&self.bytes[..len]);
+        self.bytes = &self.bytes[len..];
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[tokio::test]
+async fn test_async_read_does_not_overread() {
+    let config = bincode::config::standard();
+    let mut buffer = Vec::new();
+    bincode::encode_into_async_write(String::from("first"), &mut buffer, config)
+        .await
+        .unwrap();
+    bincode::encode_into_async_write(5u32, &mut buffer, config)
+        .await
+        .unwrap();
+
+    let mut reader = buffer.as_slice();
+    let first: String = bincode::decode_from_async_read(&mut reader, config)
+        .await
+        .unwrap();
+    let second: u32 = bincode::decode_from_async_read(&mut reader, config)
+        .await
+        .unwrap();
+    assert_eq!(first, "first");
+    assert_eq!(second, 5);
+    assert!(reader.is_empty());
+}
+
+#[tokio::test]
+async fn test_async_read_decoder() {
+    let config = bincode::config::standard();
+    let values: Vec<u64> = (0..200_000u64).map(|i| i * i).collect();
+    let message = Message {
+        id: 1234,
+        name: String::from("Hello world"),
+        payload: vec![7; 500],
+    };
+    let mut bytes = bincode::encode_to_vec(&values, config).unwrap();
+    bytes.extend(bincode::encode_to_vec(&message, config).unwrap());
+    bytes.extend(bincode::encode_to_vec(5u32, config).unwrap());
+
+    // Bytes after a value are kept for the next one
+    let mut decoder = bincode::AsyncReadDecoder::new(SlowReader::new(&bytes, 1000), config);
+    assert_eq!(decoder.decode::<Vec<u64>>().await.unwrap(), Some(values));
+    assert_eq!(decoder.decode::<Message>().await.unwrap(), Some(message));
+    assert_eq!(decoder.decode::<u32>().await.unwrap(), Some(5));
+    assert_eq!(decoder.decode::<u32>().await.unwrap(), None);
+    assert!(decoder.get_ref().bytes.is_empty());
+
+    // A value is decoded when the reader is not ready, even if the buffer is not full
+    let bytes = bincode::encode_to_vec(String::from("Hello world"), config).unwrap();
+    let mut decoder = bincode::AsyncReadDecoder::new(SlowReader::new(&bytes, 2), config);
+    let decoded: Option<String> = decoder.decode().await.unwrap();
+    assert_eq!(decoded.as_deref(), Some("Hello world"));
+
+    let mut decoder =
+        bincode::AsyncReadDecoder::new(SlowReader::new(&bytes[..bytes.len() - 1], 2), config);
+    let result: Result<Option<String>, _> = decoder.decode().await;
+    assert!(matches!(
+        result,
+        Err(DecodeError::UnexpectedEnd { additional: 1 })
+    ));
+    assert_eq!(decoder.buffer(), &bytes[..bytes.len() - 1]);
+
+    let mut decoder =
+        bincode::AsyncReadDecoder::new(&[10, b'a', b'b'][..], config.with_limit::<5>());
+    let result: Result<Option<String>, _> = decoder.decode().await;
+    assert!(matches!(result, Err(DecodeError::LimitExceeded)));
+}
+
+#[tokio::test]
+async fn test_async_read_errors() {
+    let config = bincode::config::standard();
+
+    // The string claims to have 10 bytes, but only 2 are available
+    let mut reader: &[u8] = &[10, b'a', b'b'];
+    let result: Result<String, _> = bincode::decode_from_async_read(&mut reader, config).await;
+    match result {
+        Err(DecodeError::Io { inner, additional }) => {
+            assert_eq!(inner.kind(), std::io::ErrorKind::UnexpectedEof);
+            assert_eq!(additional, 10);
+        }
+        x => panic!("Expected DecodeError::Io, got {:?}", x),
+    }
+
+    let mut reader: &[u8] = &[10, b'a', b'b'];
+    let result: Result<String, _> =
+        bincode::decode_from_async_read(&mut reader, config.with_limit::<5>()).await;
+    assert!(matches!(result, Err(DecodeError::LimitExceeded)));
+}
+
+#[tokio::test]
+async fn test_async_write_errors() {
+    let config = bincode::config::standard();
+    let mut slice = [0u8; 2];
+    let mut writer = std::io::Cursor::new(&mut slice[..]);
+    let result = bincode::encode_into_async_write(12345u32, &mut writer, config).await;
+    match result {
+        Err(EncodeError::Io { inner, index }) => {
+            assert_eq!(inner.kind(), std::io::ErrorKind::WriteZero);
+            assert_eq!(index, 2);
+        }
+        x => panic!("Expected EncodeError::Io, got {:?}", x),
+    }
+}
@@ -124,6 +124,11 @@ where
             .with_variable_int_encoding(),
         &cmp,
     );
+    the_same_with_config(
+        &element,
+        bincode::config::standard().with_leb128_int_encoding(),
+        &cmp,
+    );
 }
 
 #[cfg(feature = "serde")]